@@ -1,12 +1,10 @@
-use super::{ApiLinks, ApiMeta};
-use super::{HasPagination, HasResponse, HasValue};
+use super::{HasResponse, HasValue};
 use crate::method::{Get, List};
 use crate::request::ActionRequest;
 use crate::request::Request;
 use crate::{ROOT_URL, STATIC_URL_ERROR};
 use chrono::{DateTime, Utc};
 use getset::{Getters, Setters};
-use url::Url;
 
 const ACTIONS_SEGMENT: &str = "actions";
 
@@ -37,10 +35,19 @@ pub struct Action {
 
 	/// The type of action that the event is executing (reboot, power_off,
 	/// etc.).
+	///
+	/// *Note:* Since `type` is a keyword in Rust `kind` is used instead.
+	#[serde(rename = "type")]
+	kind: String,
+	// 'type' is reserved in Rust.
+
+	/// A time value given in ISO8601 combined date and time format that
+	/// represents when the action was initiated.
 	started_at: DateTime<Utc>,
 
 	/// A time value given in ISO8601 combined date and time format that
 	/// represents when the action was completed.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
 	completed_at: Option<DateTime<Utc>>,
 
 	/// A unique identifier for the resource that the action is associated
@@ -55,6 +62,7 @@ pub struct Action {
 	// #[deprecated(since = "0.0.1", note = "DigitalOcean has deprecated this.")]
 	// region: Option<Region>,
 	/// A slug representing the region where the action occurred.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
 	region_slug: Option<String>
 }
 
@@ -67,7 +75,9 @@ impl Action {
 			.push(ACTIONS_SEGMENT)
 			.push(&id.to_string());
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
 	}
 
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-actions)
@@ -99,28 +109,4 @@ impl HasResponse for Action {
 	type Response = ActionResponse;
 }
 
-/// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct ActionListResponse {
-	actions: Vec<Action>,
-	links: ApiLinks,
-	meta: ApiMeta
-}
-
-impl HasResponse for Vec<Action> {
-	type Response = ActionListResponse;
-}
-
-impl HasPagination for ActionListResponse {
-	fn next_page(&self) -> Option<Url> {
-		self.links.next()
-	}
-}
-
-impl HasValue for ActionListResponse {
-	type Value = Vec<Action>;
-
-	fn value(self) -> Vec<Action> {
-		self.actions
-	}
-}
+list_response!(ActionListResponse, Action, actions);
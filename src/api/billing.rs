@@ -0,0 +1,98 @@
+use crate::method::List;
+use crate::request::BillingRequest;
+use crate::request::Request;
+use crate::{ROOT_URL, STATIC_URL_ERROR};
+use chrono::{DateTime, Utc};
+use getset::{Getters, Setters};
+
+const CUSTOMERS_SEGMENT: &str = "customers";
+const MY_SEGMENT: &str = "my";
+const INVOICES_SEGMENT: &str = "invoices";
+const BILLING_HISTORY_SEGMENT: &str = "billing_history";
+
+/// An invoice for a month of usage.
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/invoices_list)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct Invoice {
+	/// The UUID of the invoice, which is used to retrieve its details.
+	invoice_uuid: String,
+
+	/// The ID of the invoice as shown in the control panel.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	invoice_id: Option<String>,
+
+	/// The total amount of the invoice, in USD, such as "12.34".
+	amount: String,
+
+	/// The billing period of the invoice, such as "2019-12".
+	invoice_period: String,
+
+	/// When the invoice was last updated.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	updated_at: Option<DateTime<Utc>>
+}
+
+impl Invoice {
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/invoices_list)
+	pub fn list() -> BillingRequest<List, Vec<Invoice>> {
+		let mut url = ROOT_URL.clone();
+		url.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(CUSTOMERS_SEGMENT)
+			.push(MY_SEGMENT)
+			.push(INVOICES_SEGMENT);
+
+		Request::new(url)
+	}
+}
+
+/// An entry in the account's billing history, such as an invoice, a payment
+/// or a credit.
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/billingHistory_list)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct BillingHistory {
+	/// A description of the entry, such as "Invoice for May 2018".
+	description: String,
+
+	/// The amount of the entry, in USD, such as "12.34". Payments and credits
+	/// are negative.
+	amount: String,
+
+	/// The ID of the invoice this entry is for, if any.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	invoice_id: Option<String>,
+
+	/// The UUID of the invoice this entry is for, if any.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	invoice_uuid: Option<String>,
+
+	/// When the entry was made.
+	date: DateTime<Utc>,
+
+	/// The type of entry, such as "Invoice", "Payment" or "Credit".
+	///
+	/// *Note:* Since `type` is a keyword in Rust `kind` is used instead.
+	#[serde(rename = "type")]
+	kind: String
+}
+
+impl BillingHistory {
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/billingHistory_list)
+	pub fn list() -> BillingRequest<List, Vec<BillingHistory>> {
+		let mut url = ROOT_URL.clone();
+		url.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(CUSTOMERS_SEGMENT)
+			.push(MY_SEGMENT)
+			.push(BILLING_HISTORY_SEGMENT);
+
+		Request::new(url)
+	}
+}
+
+list_response!(InvoiceListResponse, Invoice, invoices);
+list_response!(BillingHistoryListResponse, BillingHistory, billing_history);
@@ -1,14 +1,13 @@
-use super::{ApiLinks, ApiMeta};
-use super::{HasPagination, HasResponse, HasValue};
+use super::{HasResponse, HasValue};
 use crate::method::{Create, Delete, Get, List};
 use crate::request::CertificateRequest;
 use crate::request::Request;
+use crate::validation;
 use crate::{ROOT_URL, STATIC_URL_ERROR};
 use chrono::{DateTime, Utc};
 use getset::{Getters, Setters};
 use serde::Serialize;
 use std::fmt::Display;
-use url::Url;
 
 const CERTIFICATES_SEGMENT: &str = "certificates";
 
@@ -31,15 +30,31 @@ pub struct Certificate {
 	not_after: DateTime<Utc>,
 
 	/// A unique identifier generated from the SHA-1 fingerprint of the
-	/// certificate.
+	/// certificate. Empty until a Let's Encrypt certificate is issued.
+	#[serde(default)]
 	sha1_fingerprint: String,
 
 	/// A time value given in ISO8601 combined date and time format that
 	/// represents when the certificate was created.
-	created_at: DateTime<Utc>
+	created_at: DateTime<Utc>,
+
+	/// Either "custom" for uploaded certificates or "lets_encrypt".
+	#[serde(rename = "type", default)]
+	kind: String,
+
+	/// The domain names the certificate is for.
+	#[serde(default)]
+	dns_names: Vec<String>,
+
+	/// Either "pending", "verified" or "error". Let's Encrypt certificates
+	/// are "pending" until they have been issued.
+	#[serde(default)]
+	state: String
 }
 
 impl Certificate {
+	/// `name`, `private_key`, and `leaf_certificate` must not be empty.
+	///
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-certificate)
 	pub fn create<S>(name: S, private_key: S, leaf_certificate: S) -> CertificateRequest<Create, Certificate>
 		where S: AsRef<str> + Serialize + Display {
@@ -49,12 +64,50 @@ impl Certificate {
 			.push(CERTIFICATES_SEGMENT);
 
 		let mut req = Request::new(url);
+		req.check("name", validation::non_empty(name.as_ref()));
+		req.check("private_key", validation::non_empty(private_key.as_ref()));
+		req.check("leaf_certificate", validation::non_empty(leaf_certificate.as_ref()));
 
 		req.set_body(json!({
 			"name": name,
 			"private_key": private_key,
 			"leaf_certificate": leaf_certificate,
 		}));
+		req.require(&["name", "private_key", "leaf_certificate"]);
+
+		req
+	}
+
+	/// Have Let's Encrypt issue a certificate for `dns_names`, which must be
+	/// managed by DigitalOcean DNS. It starts out "pending"; see
+	/// [`lets_encrypt_and_wait()`](../struct.DigitalOcean.html#method.lets_encrypt_and_wait)
+	/// to wait for it to be issued.
+	///
+	/// `name` must not be empty and there must be at least one DNS name.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/certificates_create)
+	pub fn create_lets_encrypt<S, D>(name: S, dns_names: Vec<D>) -> CertificateRequest<Create, Certificate>
+		where S: AsRef<str> + Serialize + Display, D: AsRef<str> + Serialize {
+		let mut url = ROOT_URL.clone();
+		url.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(CERTIFICATES_SEGMENT);
+
+		let mut req = Request::new(url);
+		req.check("name", validation::non_empty(name.as_ref()));
+		if dns_names.is_empty() {
+			req.invalidate("dns_names", "must not be empty");
+		}
+		for dns_name in &dns_names {
+			req.check("dns_names", validation::non_empty(dns_name.as_ref()));
+		}
+
+		req.set_body(json!({
+			"name": name,
+			"type": "lets_encrypt",
+			"dns_names": dns_names,
+		}));
+		req.require(&["name", "type", "dns_names"]);
 
 		req
 	}
@@ -77,7 +130,9 @@ impl Certificate {
 			.push(CERTIFICATES_SEGMENT)
 			.push(id.as_ref());
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
 	}
 
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#delete-a-certificate)
@@ -88,7 +143,9 @@ impl Certificate {
 			.push(CERTIFICATES_SEGMENT)
 			.push(id.as_ref());
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
 	}
 }
 
@@ -121,28 +178,4 @@ impl HasValue for CertificateResponse {
 	}
 }
 
-/// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct CertificateListResponse {
-	certificates: Vec<Certificate>,
-	links: ApiLinks,
-	meta: ApiMeta
-}
-
-impl HasResponse for Vec<Certificate> {
-	type Response = CertificateListResponse;
-}
-
-impl HasPagination for CertificateListResponse {
-	fn next_page(&self) -> Option<Url> {
-		self.links.next()
-	}
-}
-
-impl HasValue for CertificateListResponse {
-	type Value = Vec<Certificate>;
-
-	fn value(self) -> Vec<Certificate> {
-		self.certificates
-	}
-}
+list_response!(CertificateListResponse, Certificate, certificates);
@@ -59,10 +59,10 @@ pub struct CustomImage {
 
 impl CustomImage {
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-custom-image)
-	pub fn create<S: AsRef<str> + Display + Serialize>(
+	pub fn create<S: AsRef<str> + Display + Serialize, R: AsRef<str> + Display + Serialize>(
 		name: S,
 		image_url: S,
-		region: S,
+		region: R,
 		distribution: S,
 		desc: S,
 		tags: Vec<S>,
@@ -81,6 +81,7 @@ impl CustomImage {
 			"description": desc,
 			"tags": tags
 		}));
+		req.require(&["name", "url", "region", "distribution", "description", "tags"]);
 		req
 	}
 }
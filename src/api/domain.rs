@@ -1,14 +1,13 @@
-use super::{ApiLinks, ApiMeta};
-use super::{HasPagination, HasResponse, HasValue};
+use super::{HasResponse, Wrapped, WrapperKey};
 use crate::method::{Create, Delete, Get, List};
 use crate::request::DomainRequest;
 use crate::request::Request;
+use crate::validation;
 use crate::{ROOT_URL, STATIC_URL_ERROR};
 use getset::{Getters, Setters};
 use serde::Serialize;
 use std::fmt::Display;
 use std::net::IpAddr;
-use url::Url;
 
 const DOMAINS_SEGMENT: &str = "domains";
 
@@ -26,6 +25,7 @@ pub struct Domain {
 	/// This value is the time to live for the records on this domain, in
     /// seconds. This defines the time frame that clients can cache queried
     /// information before a refresh should be requested.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
 	ttl: Option<usize>,
 
 	/// This attribute contains the complete contents of the zone file for the
@@ -33,10 +33,13 @@ pub struct Domain {
     /// get more granular control over records. However, this attribute can
     /// also be used to get information about the SOA record, which is created
     /// automatically and is not accessible as an individual record resource.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
 	zone_file: Option<String>
 }
 
 impl Domain {
+	/// `name` must not be empty.
+	///
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-domain)
 	pub fn create<N, I>(name: N, ip_address: I) -> DomainRequest<Create, Domain>
 		where N: AsRef<str> + Serialize + Display,
@@ -47,10 +50,12 @@ impl Domain {
 			.push(DOMAINS_SEGMENT);
 
 		let mut req = Request::new(url);
+		req.check("name", validation::non_empty(name.as_ref()));
 		req.set_body(json!({
             "name": name,
             "ip_address": ip_address,
         }));
+		req.require(&["name", "ip_address"]);
 		req
 	}
 
@@ -72,7 +77,9 @@ impl Domain {
 			.push(DOMAINS_SEGMENT)
 			.push(name.as_ref());
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("name");
+		req
 	}
 
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#delete-a-domain)
@@ -83,50 +90,24 @@ impl Domain {
 			.push(DOMAINS_SEGMENT)
 			.push(name.as_ref());
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("name");
+		req
 	}
 }
 
-/// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct DomainResponse {
-	domain: Domain
-}
+/// The key a domain is wrapped under in responses.
+pub struct DomainKey;
 
-impl HasResponse for Domain {
-	type Response = DomainResponse;
-}
-
-impl HasValue for DomainResponse {
-	type Value = Domain;
-
-	fn value(self) -> Domain {
-		self.domain
-	}
+impl WrapperKey for DomainKey {
+	const KEY: &'static str = "domain";
 }
 
 /// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct DomainListResponse {
-	domains: Vec<Domain>,
-	links: ApiLinks,
-	meta: ApiMeta
-}
+pub type DomainResponse = Wrapped<DomainKey, Domain>;
 
-impl HasResponse for Vec<Domain> {
-	type Response = DomainListResponse;
-}
-
-impl HasPagination for DomainListResponse {
-	fn next_page(&self) -> Option<Url> {
-		self.links.next()
-	}
+impl HasResponse for Domain {
+	type Response = DomainResponse;
 }
 
-impl HasValue for DomainListResponse {
-	type Value = Vec<Domain>;
-
-	fn value(self) -> Vec<Domain> {
-		self.domains
-	}
-}
+list_response!(DomainListResponse, Domain, domains);
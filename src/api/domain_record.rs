@@ -1,13 +1,11 @@
 use super::domain::Domain;
-use super::{ApiLinks, ApiMeta};
-use super::{HasPagination, HasResponse, HasValue};
+use super::{HasResponse, HasValue};
 use crate::method::{Create, Delete, Get, List, Update};
 use crate::request::{DomainRecordRequest, DomainRequest};
 use crate::STATIC_URL_ERROR;
 use getset::{Getters, Setters};
 use serde::Serialize;
 use std::fmt::Display;
-use url::Url;
 
 const DOMAIN_RECORDS_SEGMENT: &str = "records";
 
@@ -40,9 +38,11 @@ pub struct DomainRecord {
 	data: String,
 
 	/// The priority for SRV and MX records.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
 	priority: Option<usize>,
 
 	/// The port for SRV records.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
 	port: Option<usize>,
 
 	/// This value is the time to live for the record, in seconds. This defines
@@ -51,6 +51,7 @@ pub struct DomainRecord {
 	ttl: usize,
 
 	/// The weight for SRV records.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
 	weight: Option<usize>
 }
 
@@ -81,6 +82,7 @@ impl DomainRecordRequest<List, Vec<DomainRecord>> {
 			"name": name,
 			"data": data
 		}));
+		self.require(&["type", "name", "data"]);
 
 		self.transmute()
 	}
@@ -92,6 +94,7 @@ impl DomainRecordRequest<List, Vec<DomainRecord>> {
 			.expect(STATIC_URL_ERROR)
 			.push(&id.to_string());
 
+		self.path_param("record_id");
 		self.transmute()
 	}
 
@@ -102,6 +105,7 @@ impl DomainRecordRequest<List, Vec<DomainRecord>> {
 			.expect(STATIC_URL_ERROR)
 			.push(&id.to_string());
 
+		self.path_param("record_id");
 		self.transmute()
 	}
 
@@ -112,6 +116,7 @@ impl DomainRecordRequest<List, Vec<DomainRecord>> {
 			.expect(STATIC_URL_ERROR)
 			.push(&id.to_string());
 
+		self.path_param("record_id");
 		self.transmute()
 	}
 }
@@ -231,28 +236,4 @@ impl HasResponse for DomainRecord {
 	type Response = DomainRecordResponse;
 }
 
-/// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct DomainRecordListResponse {
-	domain_records: Vec<DomainRecord>,
-	links: ApiLinks,
-	meta: ApiMeta
-}
-
-impl HasResponse for Vec<DomainRecord> {
-	type Response = DomainRecordListResponse;
-}
-
-impl HasPagination for DomainRecordListResponse {
-	fn next_page(&self) -> Option<Url> {
-		self.links.next()
-	}
-}
-
-impl HasValue for DomainRecordListResponse {
-	type Value = Vec<DomainRecord>;
-
-	fn value(self) -> Vec<DomainRecord> {
-		self.domain_records
-	}
-}
+list_response!(DomainRecordListResponse, DomainRecord, domain_records);
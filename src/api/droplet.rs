@@ -1,15 +1,20 @@
-use self::droplet_fields::{Kernel, Networks, NextBackupWindow};
+use self::droplet_fields::{
+	BackupPolicy, DropletImage, DropletStatus, Kernel, ListFilter, Networks, NextBackupWindow,
+};
 use super::snapshot::Snapshot;
-use super::{ApiLinks, ApiMeta};
-use super::{HasPagination, HasResponse, HasValue};
-use super::{Image, Region, Size};
+use super::ssh_key::ssh_key_fields::SshKeyIdent;
+use super::{ActionLinks, ApiLinks, ApiMeta};
+use super::{HasPagination, HasResponse, HasValue, Raw};
+use super::{Firewall, Image, Region, Size};
 use crate::method::{Create, Delete, Get, List};
 use crate::request::Request;
-use crate::request::{DropletRequest, SnapshotRequest};
+use crate::request::{DropletRequest, FirewallRequest, MapResponse, SnapshotRequest};
+use crate::validation;
 use crate::{ROOT_URL, STATIC_URL_ERROR};
 use chrono::{DateTime, Utc};
 use getset::{Getters, Setters};
 use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
 use url::Url;
 
@@ -19,6 +24,16 @@ const DROPLET_NEIGHBORS_SEGMENT: &str = "droplet_neighbors";
 const NEIGHBORS_SEGMENT: &str = "neighbors";
 const SNAPSHOTS_SEGMENT: &str = "snapshots";
 const BACKUPS_SEGMENT: &str = "backups";
+const POLICY_SEGMENT: &str = "policy";
+const FIREWALLS_SEGMENT: &str = "firewalls";
+const POLICIES_SEGMENT: &str = "policies";
+const SUPPORTED_POLICIES_SEGMENT: &str = "supported_policies";
+
+// Defined in https://developers.digitalocean.com/documentation/v2/#create-a-new-droplet
+const MAX_USER_DATA_SIZE: usize = 64 * 1024;
+
+// Defined in https://developers.digitalocean.com/documentation/v2/#create-multiple-droplets
+const MAX_NAMES: usize = 10;
 
 /// A Droplet is a DigitalOcean virtual machine. By sending requests to the
 /// Droplet endpoint, you can list, create, or delete Droplets.
@@ -56,9 +71,8 @@ pub struct Droplet {
     /// represents when the Droplet was created.
 	created_at: DateTime<Utc>,
 
-	/// A status string indicating the state of the Droplet instance. This may
-    /// be "new", "active", "off", or "archive".
-	status: String,
+	/// The state of the Droplet instance.
+	status: DropletStatus,
 
 	/// An array of backup IDs of any backups that have been taken of the
     /// Droplet instance. Droplet backups are enabled at the time of the
@@ -102,11 +116,13 @@ pub struct Droplet {
 
 	/// The current kernel. This will initially be set to the kernel of the
 	/// base image when the Droplet is created.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
 	kernel: Option<Kernel>,
 
 	/// The details of the Droplet's backups feature, if backups are configured
 	/// for the Droplet. This object contains keys for the start and end times
 	/// of the window during which the backup will start.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
 	next_backup_window: Option<NextBackupWindow>,
 
 	/// An array of Tags the Droplet has been tagged with.
@@ -117,11 +133,307 @@ pub struct Droplet {
 	volume_ids: Vec<String>
 }
 
+/// The backup settings of a single Droplet.
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_get_backup_policy)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct DropletBackupPolicy {
+	/// The unique identifier of the Droplet.
+	droplet_id: usize,
+
+	/// Whether backups are enabled for the Droplet.
+	backup_enabled: bool,
+
+	/// How often backups are taken, if backups are enabled.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	backup_policy: Option<BackupPolicy>,
+
+	/// When the next backup will start, if backups are enabled.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	next_backup_window: Option<NextBackupWindow>
+}
+
+/// A backup plan which can be used when enabling backups on a Droplet.
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_list_supported_backup_policies)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct SupportedBackupPolicy {
+	/// The name of the plan, such as "daily" or "weekly".
+	name: String,
+
+	/// The hours of the day a backup window may start at.
+	possible_window_starts: Vec<usize>,
+
+	/// How long a backup window lasts, in hours.
+	window_length_hours: usize,
+
+	/// How long backups are kept, in days.
+	retention_period_days: usize,
+
+	/// The days of the week a backup may be taken on, such as "SUN".
+	possible_days: Vec<String>
+}
+
 /// Fields which exists inside Droplets.
 pub mod droplet_fields {
 	use chrono::{DateTime, Utc};
+	use std::convert::TryFrom;
+	use std::fmt;
 	use std::net::{Ipv4Addr, Ipv6Addr};
 
+	/// The state of a Droplet. Statuses this crate does not know about yet are
+	/// kept as `Other`, so they still round trip.
+	#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+	#[serde(from = "String", into = "String")]
+	pub enum DropletStatus {
+		New,
+		Active,
+		Off,
+		Archive,
+		Other(String)
+	}
+
+	impl DropletStatus {
+		/// The status as sent by the API, such as "active".
+		pub fn as_str(&self) -> &str {
+			match *self {
+				DropletStatus::New => "new",
+				DropletStatus::Active => "active",
+				DropletStatus::Off => "off",
+				DropletStatus::Archive => "archive",
+				DropletStatus::Other(ref status) => status
+			}
+		}
+
+		/// Whether the Droplet is still being created.
+		pub fn is_new(&self) -> bool {
+			*self == DropletStatus::New
+		}
+
+		/// Whether the Droplet is powered on.
+		pub fn is_running(&self) -> bool {
+			*self == DropletStatus::Active
+		}
+	}
+
+	impl From<String> for DropletStatus {
+		fn from(status: String) -> Self {
+			match status.as_str() {
+				"new" => DropletStatus::New,
+				"active" => DropletStatus::Active,
+				"off" => DropletStatus::Off,
+				"archive" => DropletStatus::Archive,
+				_ => DropletStatus::Other(status)
+			}
+		}
+	}
+
+	impl From<DropletStatus> for String {
+		fn from(status: DropletStatus) -> Self {
+			match status {
+				DropletStatus::Other(status) => status,
+				known => known.as_str().to_string()
+			}
+		}
+	}
+
+	impl PartialEq<str> for DropletStatus {
+		fn eq(&self, other: &str) -> bool {
+			self.as_str() == other
+		}
+	}
+
+	impl fmt::Display for DropletStatus {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			f.write_str(self.as_str())
+		}
+	}
+
+	/// Filters for [`Droplet::list_filtered()`](../struct.Droplet.html#method.list_filtered).
+	#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+	pub struct ListFilter {
+		/// Only list Droplets with this tag.
+		pub tag: Option<String>,
+
+		/// Only list Droplets with exactly this name, ignoring case.
+		pub name: Option<String>
+	}
+
+	/// The image a Droplet is created from: either the slug of a public
+	/// image, such as "ubuntu-20-04-x64", or the id of a snapshot, backup or
+	/// custom image.
+	#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+	#[serde(untagged)]
+	pub enum DropletImage {
+		Id(u64),
+		Slug(String)
+	}
+
+	impl<'a> From<&'a str> for DropletImage {
+		fn from(slug: &'a str) -> Self {
+			DropletImage::Slug(slug.to_string())
+		}
+	}
+
+	impl From<String> for DropletImage {
+		fn from(slug: String) -> Self {
+			DropletImage::Slug(slug)
+		}
+	}
+
+	impl From<u64> for DropletImage {
+		fn from(id: u64) -> Self {
+			DropletImage::Id(id)
+		}
+	}
+
+	impl From<usize> for DropletImage {
+		fn from(id: usize) -> Self {
+			DropletImage::Id(id as u64)
+		}
+	}
+
+	impl From<u32> for DropletImage {
+		fn from(id: u32) -> Self {
+			DropletImage::Id(u64::from(id))
+		}
+	}
+
+	/// So that integer literals, which default to `i32`, can be given as
+	/// ids. A negative id is kept as written and rejected when the request is
+	/// validated.
+	impl From<i32> for DropletImage {
+		fn from(id: i32) -> Self {
+			match u64::try_from(id) {
+				Ok(id) => DropletImage::Id(id),
+				Err(_) => DropletImage::Slug(id.to_string())
+			}
+		}
+	}
+
+	impl fmt::Display for DropletImage {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			match *self {
+				DropletImage::Id(id) => write!(f, "{}", id),
+				DropletImage::Slug(ref slug) => write!(f, "{}", slug)
+			}
+		}
+	}
+
+	/// How a Droplet is resized by
+	/// [`resize()`](../../request/struct.Request.html#method.resize).
+	#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+	pub enum ResizeKind {
+		/// Only CPU and RAM are changed. The disk keeps its size, so the
+		/// Droplet can later be resized back down.
+		CpuOnly,
+
+		/// The disk is grown along with CPU and RAM. This is permanent: the
+		/// Droplet can never be resized to a size with a smaller disk.
+		PermanentWithDisk
+	}
+
+	/// An action which can be taken on every Droplet with a tag at once by
+	/// [`Droplet::bulk_action()`](../struct.Droplet.html#method.bulk_action).
+	#[derive(Debug, Clone, PartialEq, Eq)]
+	pub enum BulkAction {
+		PowerCycle,
+		PowerOn,
+		PowerOff,
+		Shutdown,
+		EnablePrivateNetworking,
+		EnableIpv6,
+		EnableBackups,
+		DisableBackups,
+
+		/// Snapshot each Droplet, naming the snapshots with the given name.
+		Snapshot(String)
+	}
+
+	impl BulkAction {
+		/// The action's `type` as sent to the API, such as `power_off`.
+		pub fn kind(&self) -> &'static str {
+			match *self {
+				BulkAction::PowerCycle => "power_cycle",
+				BulkAction::PowerOn => "power_on",
+				BulkAction::PowerOff => "power_off",
+				BulkAction::Shutdown => "shutdown",
+				BulkAction::EnablePrivateNetworking => "enable_private_networking",
+				BulkAction::EnableIpv6 => "enable_ipv6",
+				BulkAction::EnableBackups => "enable_backups",
+				BulkAction::DisableBackups => "disable_backups",
+				BulkAction::Snapshot(_) => "snapshot"
+			}
+		}
+	}
+
+	const WEEKDAYS: &[&str] = &["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+	/// This exists in the `backup_policy` field of a Droplet's backup
+	/// settings, and can be given when enabling backups.
+	#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+	pub struct BackupPolicy {
+		/// The backup plan, either "daily" or "weekly".
+		pub plan: String,
+
+		/// The day of the week backups are taken on, such as "SUN". Only
+		/// used by weekly plans.
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub weekday: Option<String>,
+
+		/// The hour of the day, in UTC, the backup window starts at. This
+		/// must be one of 0, 4, 8, 12, 16 or 20.
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub hour: Option<usize>,
+
+		/// How long the backup window lasts, in hours. Set by the API.
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub window_length_hours: Option<usize>,
+
+		/// How long backups are kept, in days. Set by the API.
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub retention_period_days: Option<usize>
+	}
+
+	impl BackupPolicy {
+		pub fn new<S: AsRef<str>>(plan: S) -> Self {
+			BackupPolicy {
+				plan: plan.as_ref().to_string(),
+				weekday: None,
+				hour: None,
+				window_length_hours: None,
+				retention_period_days: None,
+			}
+		}
+
+		pub fn weekday<S: AsRef<str>>(mut self, weekday: S) -> Self {
+			self.weekday = Some(weekday.as_ref().to_string());
+			self
+		}
+
+		pub fn hour(mut self, hour: usize) -> Self {
+			self.hour = Some(hour);
+			self
+		}
+
+		pub(crate) fn validate(&self) -> Result<(), String> {
+			if let Some(ref weekday) = self.weekday {
+				if !WEEKDAYS.contains(&weekday.as_str()) {
+					return Err(format!("weekday must be one of {}", WEEKDAYS.join(", ")));
+				}
+			}
+
+			match self.hour {
+				Some(hour) if hour > 20 || hour % 4 != 0 => {
+					Err("hour must be one of 0, 4, 8, 12, 16 or 20".to_string())
+				}
+				_ => Ok(())
+			}
+		}
+	}
+
 	/// This exists in the `networks` field of a droplet.
 	#[derive(Deserialize, Serialize, Debug, Clone)]
 	pub struct Networks {
@@ -168,48 +480,79 @@ pub mod droplet_fields {
 }
 
 impl Droplet {
+	/// `name` and `size` must not be empty, and `region` must be a region slug.
+	/// `image` is a slug or, to create from a snapshot or backup, a positive
+	/// id such as `6372321`.
+	///
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-droplet)
-	pub fn create<S, D>(name: S, region: S, size: S, image: D) -> DropletRequest<Create, Droplet>
+	pub fn create<S, R, D>(name: S, region: R, size: S, image: D) -> DropletRequest<Create, Droplet>
 		where
 			S: AsRef<str> + Serialize + Display,
-			D: Serialize + Display {
+			R: AsRef<str> + Serialize + Display,
+			D: Into<DropletImage> {
 		let mut url = ROOT_URL.clone();
 		url.path_segments_mut()
 			.expect(STATIC_URL_ERROR)
 			.push(DROPLETS_SEGMENT);
 
 		let mut req = Request::new(url);
+		req.check("name", validation::non_empty(name.as_ref()));
+		req.check("region", validation::region_slug(region.as_ref()));
+		req.check("size", validation::non_empty(size.as_ref()));
+
+		let image: DropletImage = image.into();
+		req.check("image", validation::image(&image.to_string()));
 		req.set_body(json!({
 			"name": name,
 			"region": region,
 			"size": size,
-			"image": format!("{}", image),
+			"image": image,
 		}));
+		req.require(&["name", "region", "size", "image"]);
 		req
 	}
 
+	/// `names` must have between 1 and 10 names, none of them empty. `size`
+	/// must not be empty, and `region` must be a region slug. `image` is as in
+	/// [`create()`](#method.create).
+	///
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-multiple-droplets)
-	pub fn create_multiple<S, D>(
+	pub fn create_multiple<S, R, D>(
 		names: Vec<S>,
-		region: S,
+		region: R,
 		size: S,
 		image: D,
 	) -> DropletRequest<Create, Vec<Droplet>>
 		where
 			S: AsRef<str> + Serialize + Display,
-			D: Serialize + Display {
+			R: AsRef<str> + Serialize + Display,
+			D: Into<DropletImage> {
 		let mut url = ROOT_URL.clone();
 		url.path_segments_mut()
 			.expect(STATIC_URL_ERROR)
 			.push(DROPLETS_SEGMENT);
 
 		let mut req = Request::new(url);
+		if names.is_empty() {
+			req.invalidate("names", "must not be empty");
+		} else if names.len() > MAX_NAMES {
+			req.invalidate("names", format!("must not have more than {} names", MAX_NAMES));
+		}
+		for name in &names {
+			req.check("names", validation::non_empty(name.as_ref()));
+		}
+		req.check("region", validation::region_slug(region.as_ref()));
+		req.check("size", validation::non_empty(size.as_ref()));
+
+		let image: DropletImage = image.into();
+		req.check("image", validation::image(&image.to_string()));
 		req.set_body(json!({
 			"names": names,
 			"region": region,
 			"size": size,
-			"image": format!("{}", image),
+			"image": image,
 		}));
+		req.require(&["names", "region", "size", "image"]);
 		req
 	}
 
@@ -221,7 +564,9 @@ impl Droplet {
 			.push(DROPLETS_SEGMENT)
 			.push(&id.to_string());
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
 	}
 
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-droplets)
@@ -234,6 +579,51 @@ impl Droplet {
 		Request::new(url)
 	}
 
+	/// List every Droplet, grouped by the slug of its region. Droplets keep
+	/// the order they were listed in within each group.
+	///
+	/// ```rust,no_run
+	/// # use digitalocean::prelude::*;
+	/// # let client = DigitalOcean::new("token").unwrap();
+	/// let by_region = Droplet::list_grouped_by_region().execute(&client);
+	/// ```
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_list)
+	pub fn list_grouped_by_region() -> GroupedDropletRequest {
+		Droplet::list().map_response(group_by_region)
+	}
+
+	/// List Droplets matching a filter. DigitalOcean does not support
+	/// filtering by tag and name at once, so giving both is invalid, as is
+	/// giving an empty tag or name.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_list)
+	pub fn list_filtered(filter: ListFilter) -> DropletRequest<List, Vec<Droplet>> {
+		let mut url = ROOT_URL.clone();
+		url.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(DROPLETS_SEGMENT);
+
+		if let Some(ref tag) = filter.tag {
+			url.query_pairs_mut().append_pair("tag_name", tag);
+		}
+		if let Some(ref name) = filter.name {
+			url.query_pairs_mut().append_pair("name", name);
+		}
+
+		let mut req = Request::new(url);
+		if let Some(ref tag) = filter.tag {
+			req.check("tag", validation::tag_name(tag));
+		}
+		if let Some(ref name) = filter.name {
+			req.check("name", validation::non_empty(name));
+		}
+		if filter.tag.is_some() && filter.name.is_some() {
+			req.invalidate("name", "cannot be combined with a tag filter");
+		}
+		req
+	}
+
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#listing-droplets-by-tag)
 	pub fn list_by_tag<S: AsRef<str> + Serialize>(name: S) -> DropletRequest<List, Vec<Droplet>> {
 		let mut url = ROOT_URL.clone();
@@ -246,6 +636,13 @@ impl Droplet {
 		Request::new(url)
 	}
 
+	/// Shorthand for `Droplet::get(id).firewalls()`.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_list_firewalls)
+	pub fn list_firewalls(id: usize) -> FirewallRequest<List, Vec<Firewall>> {
+		Droplet::get(id).firewalls()
+	}
+
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#delete-a-droplet)
 	pub fn delete(id: usize) -> DropletRequest<Delete, ()> {
 		let mut url = ROOT_URL.clone();
@@ -254,9 +651,14 @@ impl Droplet {
 			.push(DROPLETS_SEGMENT)
 			.push(&id.to_string());
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
 	}
 
+	/// Deletes every Droplet with the given tag. The tag name is validated,
+	/// so an empty name is rejected rather than sent.
+	///
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#deleting-droplets-by-tag)
 	pub fn delete_by_tag<S: AsRef<str> + Serialize>(name: S) -> DropletRequest<Delete, ()> {
 		let mut url = ROOT_URL.clone();
@@ -266,6 +668,32 @@ impl Droplet {
 
 		url.query_pairs_mut().append_pair("tag_name", name.as_ref());
 
+		let mut req = Request::new(url);
+		req.check("tag_name", validation::tag_name(name.as_ref()));
+		req
+	}
+
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_list_backup_policies)
+	pub fn backup_policies() -> DropletRequest<List, Vec<DropletBackupPolicy>> {
+		let mut url = ROOT_URL.clone();
+		url.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(DROPLETS_SEGMENT)
+			.push(BACKUPS_SEGMENT)
+			.push(POLICIES_SEGMENT);
+
+		Request::new(url)
+	}
+
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_list_supported_backup_policies)
+	pub fn supported_backup_policies() -> DropletRequest<Get, Vec<SupportedBackupPolicy>> {
+		let mut url = ROOT_URL.clone();
+		url.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(DROPLETS_SEGMENT)
+			.push(BACKUPS_SEGMENT)
+			.push(SUPPORTED_POLICIES_SEGMENT);
+
 		Request::new(url)
 	}
 
@@ -283,13 +711,18 @@ impl Droplet {
 
 impl DropletRequest<Create, Droplet> {
 	/// An array containing the IDs or fingerprints of the SSH keys that you
-	/// wish to embed in the Droplet's root account upon creation.
+	/// wish to embed in the Droplet's root account upon creation. Give a
+	/// `Vec<SshKeyIdent>` to mix the two.
 	///
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-droplet)
 	pub fn ssh_keys<D>(mut self, val: Vec<D>) -> Self
 		where
-			D: Display + Serialize,
+			D: Into<SshKeyIdent>,
 	{
+		let val: Vec<SshKeyIdent> = val.into_iter().map(Into::into).collect();
+		for key in &val {
+			self.check("ssh_keys", validation::ssh_key(&key.to_string()));
+		}
 		self.body_mut()["ssh_keys"] = json!(val);
 		self
 	}
@@ -327,7 +760,13 @@ impl DropletRequest<Create, Droplet> {
 	/// It must be plain text and may not exceed 64 KiB in size.
 	///
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-droplet)
-	pub fn user_data(mut self, val: bool) -> Self {
+	pub fn user_data<S: AsRef<str> + Serialize + Display>(mut self, val: S) -> Self {
+		if val.as_ref().len() > MAX_USER_DATA_SIZE {
+			self.invalidate(
+				"user_data",
+				format!("must not exceed {} bytes", MAX_USER_DATA_SIZE),
+			);
+		}
 		self.body_mut()["user_data"] = json!(val);
 		self
 	}
@@ -363,13 +802,18 @@ impl DropletRequest<Create, Droplet> {
 
 impl DropletRequest<Create, Vec<Droplet>> {
 	/// An array containing the IDs or fingerprints of the SSH keys that you
-	/// wish to embed in the Droplet's root account upon creation.
+	/// wish to embed in the Droplet's root account upon creation. Give a
+	/// `Vec<SshKeyIdent>` to mix the two.
 	///
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-droplet)
 	pub fn ssh_keys<D>(mut self, val: Vec<D>) -> Self
 		where
-			D: Display + Serialize,
+			D: Into<SshKeyIdent>,
 	{
+		let val: Vec<SshKeyIdent> = val.into_iter().map(Into::into).collect();
+		for key in &val {
+			self.check("ssh_keys", validation::ssh_key(&key.to_string()));
+		}
 		self.body_mut()["ssh_keys"] = json!(val);
 		self
 	}
@@ -407,7 +851,13 @@ impl DropletRequest<Create, Vec<Droplet>> {
 	/// It must be plain text and may not exceed 64 KiB in size.
 	///
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-droplet)
-	pub fn user_data(mut self, val: bool) -> Self {
+	pub fn user_data<S: AsRef<str> + Serialize + Display>(mut self, val: S) -> Self {
+		if val.as_ref().len() > MAX_USER_DATA_SIZE {
+			self.invalidate(
+				"user_data",
+				format!("must not exceed {} bytes", MAX_USER_DATA_SIZE),
+			);
+		}
 		self.body_mut()["user_data"] = json!(val);
 		self
 	}
@@ -462,6 +912,17 @@ impl DropletRequest<Get, Droplet> {
 		self.transmute()
 	}
 
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_get_backup_policy)
+	pub fn backup_policy(mut self) -> DropletRequest<Get, DropletBackupPolicy> {
+		self.url_mut()
+			.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(BACKUPS_SEGMENT)
+			.push(POLICY_SEGMENT);
+
+		self.transmute()
+	}
+
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-neighbors-for-a-droplet)
 	pub fn neighbors(mut self) -> DropletRequest<List, Vec<Droplet>> {
 		self.url_mut()
@@ -471,12 +932,122 @@ impl DropletRequest<Get, Droplet> {
 
 		self.transmute()
 	}
+
+	/// The firewalls currently applied to the Droplet, whether by its ID or
+	/// by one of its tags.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_list_firewalls)
+	pub fn firewalls(mut self) -> FirewallRequest<List, Vec<Firewall>> {
+		self.url_mut()
+			.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(FIREWALLS_SEGMENT);
+
+		self.transmute()
+	}
 }
 
 /// Response type returned from Digital Ocean.
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct DropletResponse {
-	droplet: Droplet
+	droplet: Droplet,
+	#[serde(default)]
+	links: ActionLinks
+}
+
+impl DropletResponse {
+	/// The Droplet the response is for.
+	pub fn droplet(&self) -> &Droplet {
+		&self.droplet
+	}
+
+	/// The id of the action creating the Droplet, present when the response
+	/// comes from creating one. Poll it with
+	/// [`Action::get()`](struct.Action.html#method.get).
+	pub fn create_action_id(&self) -> Option<usize> {
+		self.links.action("create")
+	}
+}
+
+impl DropletRequest<Create, Droplet> {
+	/// Return the whole response rather than just the Droplet, so that the
+	/// action creating it can be polled at your own pace. See
+	/// [`create_droplet_and_wait()`](../struct.DigitalOcean.html#method.create_droplet_and_wait)
+	/// to wait for it instead.
+	///
+	/// ```rust,no_run
+	/// # use digitalocean::prelude::*;
+	/// # use digitalocean::api::Raw;
+	/// # let client = DigitalOcean::new("token").unwrap();
+	/// let Raw(response) = Droplet::create("bear", "tor1", "s-1vcpu-1gb", "ubuntu-18-04-x64")
+	///     .full_response()
+	///     .execute(&client)
+	///     .unwrap();
+	///
+	/// if let Some(id) = response.create_action_id() {
+	///     let action = Action::get(id).execute(&client);
+	/// }
+	/// ```
+	pub fn full_response(self) -> DropletRequest<Create, Raw<DropletResponse>> {
+		self.transmute()
+	}
+
+	/// Use this request as a template for several Droplets, creating one for
+	/// each of `names` in a single request. Every other setting is shared, and
+	/// the name given to [`create()`](struct.Droplet.html#method.create) is
+	/// replaced.
+	///
+	/// `names` must have between 1 and 10 names, and each name must be
+	/// non-empty and unique. Since the template's name is replaced, it may be
+	/// empty.
+	///
+	/// ```rust,no_run
+	/// # use digitalocean::prelude::*;
+	/// # let client = DigitalOcean::new("token").unwrap();
+	/// let template = Droplet::create("web", "nyc3", "s-1vcpu-1gb", "ubuntu-22-04-x64")
+	///     .monitoring(true);
+	/// let droplets = template
+	///     .instances(vec!["web-1", "web-2", "web-3"])
+	///     .execute(&client);
+	/// ```
+	///
+	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-multiple-droplets)
+	pub fn instances<S>(mut self, names: Vec<S>) -> DropletRequest<Create, Vec<Droplet>>
+		where S: AsRef<str> + Serialize + Display {
+		if names.is_empty() {
+			self.invalidate("names", "must not be empty");
+		} else if names.len() > MAX_NAMES {
+			self.invalidate("names", format!("must not have more than {} names", MAX_NAMES));
+		}
+		let mut seen = HashSet::new();
+		for name in &names {
+			self.check("names", validation::non_empty(name.as_ref()));
+			if !seen.insert(name.as_ref()) {
+				self.invalidate("names", format!("`{}` is given more than once", name));
+			}
+		}
+
+		self.replace_field("name", "names", json!(names));
+		self.transmute()
+	}
+}
+
+/// Droplets keyed by the slug of their region.
+pub type DropletsByRegion = HashMap<String, Vec<Droplet>>;
+
+/// Returned by [`Droplet::list_grouped_by_region()`](struct.Droplet.html#method.list_grouped_by_region).
+pub type GroupedDropletRequest =
+	MapResponse<DropletRequest<List, Vec<Droplet>>, Vec<Droplet>, fn(Vec<Droplet>) -> DropletsByRegion>;
+
+fn group_by_region(droplets: Vec<Droplet>) -> DropletsByRegion {
+	let mut groups = DropletsByRegion::new();
+
+	for droplet in droplets {
+		groups.entry(droplet.region.slug().clone())
+			.or_default()
+			.push(droplet);
+	}
+	groups
 }
 
 impl HasResponse for Droplet {
@@ -491,46 +1062,97 @@ impl HasValue for DropletResponse {
 	}
 }
 
+list_response!(DropletListResponse, Droplet, droplets);
+
+/// Response type returned from Digital Ocean
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct DropletNeighborsResponse {
+	neighbors: Vec<Vec<Droplet>>
+}
+
+impl HasResponse for Vec<Vec<Droplet>> {
+	type Response = DropletNeighborsResponse;
+}
+
+impl HasValue for DropletNeighborsResponse {
+	type Value = Vec<Vec<Droplet>>;
+
+	fn value(self) -> Vec<Vec<Droplet>> {
+		self.neighbors
+	}
+}
+
 /// Response type returned from Digital Ocean.
 #[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct DropletListResponse {
-	droplets: Vec<Droplet>,
+pub struct DropletBackupPolicyResponse {
+	policy: DropletBackupPolicy
+}
+
+impl HasResponse for DropletBackupPolicy {
+	type Response = DropletBackupPolicyResponse;
+}
+
+impl HasValue for DropletBackupPolicyResponse {
+	type Value = DropletBackupPolicy;
+
+	fn value(self) -> DropletBackupPolicy {
+		self.policy
+	}
+}
+
+/// Response type returned from Digital Ocean. Policies are keyed by
+/// Droplet id.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct DropletBackupPolicyListResponse {
+	policies: HashMap<String, DropletBackupPolicy>,
+	#[serde(default)]
 	links: ApiLinks,
+	#[serde(default)]
 	meta: ApiMeta
 }
 
-impl HasResponse for Vec<Droplet> {
-	type Response = DropletListResponse;
+impl HasResponse for Vec<DropletBackupPolicy> {
+	type Response = DropletBackupPolicyListResponse;
 }
 
-impl HasPagination for DropletListResponse {
+impl HasPagination for DropletBackupPolicyListResponse {
 	fn next_page(&self) -> Option<Url> {
 		self.links.next()
 	}
+
+	fn prev_page(&self) -> Option<Url> {
+		self.links.prev()
+	}
+
+	fn total(&self) -> Option<u64> {
+		self.meta.total
+	}
 }
 
-impl HasValue for DropletListResponse {
-	type Value = Vec<Droplet>;
+impl HasValue for DropletBackupPolicyListResponse {
+	type Value = Vec<DropletBackupPolicy>;
 
-	fn value(self) -> Vec<Droplet> {
-		self.droplets
+	fn value(self) -> Vec<DropletBackupPolicy> {
+		let mut policies: Vec<DropletBackupPolicy> = self.policies.into_values().collect();
+		policies.sort_by_key(|v| v.droplet_id);
+		policies
 	}
 }
 
-/// Response type returned from Digital Ocean
+/// Response type returned from Digital Ocean.
 #[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct DropletNeighborsResponse {
-	neighbors: Vec<Vec<Droplet>>
+pub struct SupportedBackupPolicyListResponse {
+	supported_policies: Vec<SupportedBackupPolicy>
 }
 
-impl HasResponse for Vec<Vec<Droplet>> {
-	type Response = DropletNeighborsResponse;
+impl HasResponse for Vec<SupportedBackupPolicy> {
+	type Response = SupportedBackupPolicyListResponse;
 }
 
-impl HasValue for DropletNeighborsResponse {
-	type Value = Vec<Vec<Droplet>>;
+impl HasValue for SupportedBackupPolicyListResponse {
+	type Value = Vec<SupportedBackupPolicy>;
 
-	fn value(self) -> Vec<Vec<Droplet>> {
-		self.neighbors
+	fn value(self) -> Vec<SupportedBackupPolicy> {
+		self.supported_policies
 	}
 }
@@ -1,11 +1,15 @@
 use super::action::Action;
+use super::droplet::droplet_fields::{BackupPolicy, BulkAction, DropletImage, ResizeKind};
 use super::droplet::Droplet;
+use super::Target;
 use crate::method::{Create, Get, List};
-use crate::request::{DropletActionRequest, DropletRequest};
-use crate::STATIC_URL_ERROR;
+use crate::request::{DropletActionRequest, DropletRequest, Request};
+use crate::validation;
+use crate::{ROOT_URL, STATIC_URL_ERROR};
 use serde::Serialize;
 use std::fmt::Display;
 
+const DROPLETS_SEGMENT: &str = "droplets";
 const DROPLET_ACTIONS_SEGMENT: &str = "actions";
 
 impl DropletRequest<Get, Droplet> {
@@ -19,8 +23,14 @@ impl DropletRequest<Get, Droplet> {
 		self.transmute()
 	}
 
+	/// Without a policy, the default plan is used. The policy's `weekday` and
+	/// `hour` are validated.
+	///
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#enable-backups)
-	pub fn enable_backups(mut self) -> DropletActionRequest<Create, Action> {
+	pub fn enable_backups(
+		mut self,
+		policy: Option<BackupPolicy>,
+	) -> DropletActionRequest<Create, Action> {
 		self.url_mut()
 			.path_segments_mut()
 			.expect(STATIC_URL_ERROR)
@@ -29,6 +39,12 @@ impl DropletRequest<Get, Droplet> {
 		self.set_body(json!({
 			"type": "enable_backups",
 		}));
+		self.require(&["type"]);
+
+		if let Some(policy) = policy {
+			self.check("backup_policy", policy.validate());
+			self.body_mut()["backup_policy"] = json!(policy);
+		}
 
 		self.transmute()
 	}
@@ -43,6 +59,7 @@ impl DropletRequest<Get, Droplet> {
 		self.set_body(json!({
 			"type": "disable_backups",
 		}));
+		self.require(&["type"]);
 
 		self.transmute()
 	}
@@ -57,6 +74,7 @@ impl DropletRequest<Get, Droplet> {
 		self.set_body(json!({
 			"type": "reboot",
 		}));
+		self.require(&["type"]);
 
 		self.transmute()
 	}
@@ -71,6 +89,7 @@ impl DropletRequest<Get, Droplet> {
 		self.set_body(json!({
 			"type": "power_cycle",
 		}));
+		self.require(&["type"]);
 
 		self.transmute()
 	}
@@ -85,6 +104,7 @@ impl DropletRequest<Get, Droplet> {
 		self.set_body(json!({
 			"type": "shutdown",
 		}));
+		self.require(&["type"]);
 
 		self.transmute()
 	}
@@ -99,21 +119,28 @@ impl DropletRequest<Get, Droplet> {
 		self.set_body(json!({
 			"type": if val { "power_on" } else { "power_off" },
 		}));
+		self.require(&["type"]);
 
 		self.transmute()
 	}
 
+	/// `image` is usually the id of one of the Droplet's backups, and must be
+	/// positive if it is an id. Ids are sent as numbers and slugs as strings.
+	///
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#restore-a-droplet)
-	pub fn restore<D: Display>(mut self, image: D) -> DropletActionRequest<Create, Action> {
+	pub fn restore<D: Into<DropletImage>>(mut self, image: D) -> DropletActionRequest<Create, Action> {
 		self.url_mut()
 			.path_segments_mut()
 			.expect(STATIC_URL_ERROR)
 			.push(DROPLET_ACTIONS_SEGMENT);
 
+		let image: DropletImage = image.into();
+		self.check("image", validation::image(&image.to_string()));
 		self.set_body(json!({
 			"type": "restore",
-			"image": format!("{}", image),
+			"image": image,
 		}));
+		self.require(&["type", "image"]);
 
 		self.transmute()
 	}
@@ -128,12 +155,17 @@ impl DropletRequest<Get, Droplet> {
 		self.set_body(json!({
 			"type": "password_reset",
 		}));
+		self.require(&["type"]);
 
 		self.transmute()
 	}
 
+	/// `size` must be a size slug. Resizing with
+	/// `ResizeKind::PermanentWithDisk` cannot be undone, and the API refuses
+	/// any resize to a size with a smaller disk than the Droplet has.
+	///
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#resize-a-droplet)
-	pub fn resize<S>(mut self, size: S, disk: bool) -> DropletActionRequest<Create, Action>
+	pub fn resize<S>(mut self, size: S, kind: ResizeKind) -> DropletActionRequest<Create, Action>
 		where
 			S: AsRef<str> + Serialize + Display,
 	{
@@ -142,11 +174,13 @@ impl DropletRequest<Get, Droplet> {
 			.expect(STATIC_URL_ERROR)
 			.push(DROPLET_ACTIONS_SEGMENT);
 
+		self.check("size", validation::size_slug(size.as_ref()));
 		self.set_body(json!({
 			"type": "resize",
-			"disk": disk,
+			"disk": kind == ResizeKind::PermanentWithDisk,
 			"size": size.as_ref(),
 		}));
+		self.require(&["type", "disk", "size"]);
 
 		self.transmute()
 	}
@@ -165,6 +199,7 @@ impl DropletRequest<Get, Droplet> {
 			"type": "rebuild",
 			"image": image.as_ref(),
 		}));
+		self.require(&["type", "image"]);
 
 		self.transmute()
 	}
@@ -183,6 +218,7 @@ impl DropletRequest<Get, Droplet> {
 			"type": "rename",
 			"name": name.as_ref(),
 		}));
+		self.require(&["type", "name"]);
 
 		self.transmute()
 	}
@@ -198,6 +234,7 @@ impl DropletRequest<Get, Droplet> {
 			"type": "change_kernel",
 			"kernel": kernel,
 		}));
+		self.require(&["type", "kernel"]);
 
 		self.transmute()
 	}
@@ -212,6 +249,7 @@ impl DropletRequest<Get, Droplet> {
 		self.set_body(json!({
 			"type": "enable_ipv6",
 		}));
+		self.require(&["type"]);
 
 		self.transmute()
 	}
@@ -226,6 +264,7 @@ impl DropletRequest<Get, Droplet> {
 		self.set_body(json!({
 			"type": "enable_private_networking",
 		}));
+		self.require(&["type"]);
 
 		self.transmute()
 	}
@@ -244,6 +283,7 @@ impl DropletRequest<Get, Droplet> {
 			"type": "snapshot",
 			"name": name.as_ref(),
 		}));
+		self.require(&["type", "name"]);
 
 		self.transmute()
 	}
@@ -256,8 +296,57 @@ impl DropletRequest<Get, Droplet> {
 			.push(DROPLET_ACTIONS_SEGMENT)
 			.push(&id.to_string());
 
+		self.path_param("action_id");
 		self.transmute()
 	}
 }
 
-// TODO: https://developers.digitalocean.com/documentation/v2/#acting-on-tagged-droplets
+impl Droplet {
+	/// Take an action on every Droplet with a tag at once, returning an
+	/// action for each Droplet.
+	///
+	/// The API can only act on a single tag, so `target` must be
+	/// `Target::Tags` with exactly one tag name; anything else is invalid.
+	///
+	/// ```rust,no_run
+	/// # use digitalocean::prelude::*;
+	/// # use digitalocean::api::Target;
+	/// # use digitalocean::api::droplet_fields::BulkAction;
+	/// # let client = DigitalOcean::new("token").unwrap();
+	/// let actions = Droplet::bulk_action(Target::tag("staging"), BulkAction::PowerOff)
+	///     .execute(&client);
+	/// ```
+	///
+	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#acting-on-tagged-droplets)
+	pub fn bulk_action(target: Target, action: BulkAction) -> DropletActionRequest<Create, Vec<Action>> {
+		let mut url = ROOT_URL.clone();
+		url.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(DROPLETS_SEGMENT)
+			.push(DROPLET_ACTIONS_SEGMENT);
+
+		let tag = match target {
+			Target::Tags(ref tags) if tags.len() == 1 => Some(tags[0].clone()),
+			_ => None
+		};
+		if let Some(ref tag) = tag {
+			url.query_pairs_mut().append_pair("tag_name", tag);
+		}
+
+		let mut req = Request::new(url);
+		match tag {
+			Some(ref tag) => req.check("target", validation::tag_name(tag)),
+			None => req.invalidate("target", "must be a single tag"),
+		}
+
+		req.set_body(json!({
+			"type": action.kind(),
+		}));
+		req.require(&["type"]);
+		if let BulkAction::Snapshot(name) = action {
+			req.check("name", validation::non_empty(&name));
+			req.body_mut()["name"] = json!(name);
+		}
+		req
+	}
+}
@@ -0,0 +1,239 @@
+use self::destroy_fields::{AssociatedResource, DestroyedResource, DestroyedResources};
+use self::destroy_fields::SelectiveDestroy;
+use super::droplet::Droplet;
+use super::{HasResponse, HasValue};
+use crate::method::{Create, Delete, Get};
+use crate::request::DropletRequest;
+use crate::STATIC_URL_ERROR;
+use chrono::{DateTime, Utc};
+use getset::{Getters, Setters};
+
+const DESTROY_SEGMENT: &str = "destroy_with_associated_resources";
+const SELECTIVE_SEGMENT: &str = "selective";
+const DANGEROUS_SEGMENT: &str = "dangerous";
+const STATUS_SEGMENT: &str = "status";
+const RETRY_SEGMENT: &str = "retry";
+
+/// The resources associated with a Droplet which can be destroyed along
+/// with it. Costs are given in US dollars per month.
+///
+/// Requests with this output this type are accessed via [`Droplet::get(..).associated_resources()`](../request/type.DropletRequest.html#method.associated_resources).
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_list_associatedResources)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct AssociatedResources {
+	/// Reserved IPs assigned to the Droplet.
+	#[serde(default)]
+	reserved_ips: Vec<AssociatedResource>,
+
+	/// Floating IPs assigned to the Droplet.
+	#[serde(default)]
+	floating_ips: Vec<AssociatedResource>,
+
+	/// Snapshots of the Droplet.
+	#[serde(default)]
+	snapshots: Vec<AssociatedResource>,
+
+	/// Volumes attached to the Droplet.
+	#[serde(default)]
+	volumes: Vec<AssociatedResource>,
+
+	/// Snapshots of the volumes attached to the Droplet.
+	#[serde(default)]
+	volume_snapshots: Vec<AssociatedResource>
+}
+
+/// The progress of destroying a Droplet with its associated resources.
+///
+/// Requests with this output this type are accessed via [`Droplet::get(..).destroy_status()`](../request/type.DropletRequest.html#method.destroy_status).
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_get_destroyAssociatedResourcesStatus)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct DestroyStatus {
+	/// The Droplet being destroyed.
+	droplet: DestroyedResource,
+
+	/// The associated resources being destroyed.
+	resources: DestroyedResources,
+
+	/// When every resource was destroyed, if that has happened.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	completed_at: Option<DateTime<Utc>>,
+
+	/// How many resources failed to be destroyed.
+	failures: usize
+}
+
+/// Fields which exists inside associated resources and destroy statuses.
+pub mod destroy_fields {
+	use chrono::{DateTime, Utc};
+
+	/// A resource associated with a Droplet.
+	#[derive(Deserialize, Serialize, Debug, Clone)]
+	pub struct AssociatedResource {
+		pub id: String,
+		pub name: String,
+		/// The monthly cost of the resource in US dollars, if it has one.
+		pub cost: Option<String>
+	}
+
+	/// A resource in the `status` of a destroy.
+	#[derive(Deserialize, Serialize, Debug, Clone)]
+	pub struct DestroyedResource {
+		pub id: String,
+		pub name: String,
+		pub destroyed_at: Option<DateTime<Utc>>,
+		pub error_message: Option<String>
+	}
+
+	/// This exists in the `resources` field of a destroy status.
+	#[derive(Deserialize, Serialize, Debug, Clone)]
+	pub struct DestroyedResources {
+		#[serde(default)]
+		pub reserved_ips: Vec<DestroyedResource>,
+		#[serde(default)]
+		pub floating_ips: Vec<DestroyedResource>,
+		#[serde(default)]
+		pub snapshots: Vec<DestroyedResource>,
+		#[serde(default)]
+		pub volumes: Vec<DestroyedResource>,
+		#[serde(default)]
+		pub volume_snapshots: Vec<DestroyedResource>
+	}
+
+	/// The associated resources to destroy along with a Droplet. Each list
+	/// holds resource ids.
+	#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+	pub struct SelectiveDestroy {
+		#[serde(default, skip_serializing_if = "Vec::is_empty")]
+		pub reserved_ips: Vec<String>,
+		#[serde(default, skip_serializing_if = "Vec::is_empty")]
+		pub snapshots: Vec<String>,
+		#[serde(default, skip_serializing_if = "Vec::is_empty")]
+		pub volumes: Vec<String>,
+		#[serde(default, skip_serializing_if = "Vec::is_empty")]
+		pub volume_snapshots: Vec<String>
+	}
+
+	impl SelectiveDestroy {
+		pub fn is_empty(&self) -> bool {
+			self.reserved_ips.is_empty()
+				&& self.snapshots.is_empty()
+				&& self.volumes.is_empty()
+				&& self.volume_snapshots.is_empty()
+		}
+	}
+}
+
+impl DropletRequest<Get, Droplet> {
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_list_associatedResources)
+	pub fn associated_resources(mut self) -> DropletRequest<Get, AssociatedResources> {
+		self.url_mut()
+			.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(DESTROY_SEGMENT);
+
+		self.transmute()
+	}
+
+	/// Destroy the Droplet along with the given associated resources.
+	///
+	/// This cannot be undone, so `confirm` must be `true` and at least one
+	/// resource must be given. Otherwise the request is invalid.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_destroy_withAssociatedResourcesSelective)
+	pub fn destroy_selective(
+		mut self,
+		resources: SelectiveDestroy,
+		confirm: bool,
+	) -> DropletRequest<Delete, ()> {
+		self.url_mut()
+			.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(DESTROY_SEGMENT)
+			.push(SELECTIVE_SEGMENT);
+
+		if !confirm {
+			self.invalidate("confirm", "must be true to destroy a Droplet and its resources");
+		}
+		if resources.is_empty() {
+			self.invalidate("resources", "must name at least one resource");
+		}
+		self.set_body(json!(resources));
+
+		self.transmute()
+	}
+
+	/// Destroy the Droplet along with *every* associated resource.
+	///
+	/// This cannot be undone, so `confirm` must be `true`. Otherwise the
+	/// request is invalid.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_destroy_withAssociatedResourcesDangerous)
+	pub fn destroy_dangerous(mut self, confirm: bool) -> DropletRequest<Delete, ()> {
+		self.url_mut()
+			.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(DESTROY_SEGMENT)
+			.push(DANGEROUS_SEGMENT);
+
+		if !confirm {
+			self.invalidate("confirm", "must be true to destroy a Droplet and its resources");
+		}
+
+		self.header("X-Dangerous", "true").transmute()
+	}
+
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_get_destroyAssociatedResourcesStatus)
+	pub fn destroy_status(mut self) -> DropletRequest<Get, DestroyStatus> {
+		self.url_mut()
+			.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(DESTROY_SEGMENT)
+			.push(STATUS_SEGMENT);
+
+		self.transmute()
+	}
+
+	/// Retry destroying the associated resources which failed to be
+	/// destroyed.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_destroy_retryWithAssociatedResources)
+	pub fn retry_destroy(mut self) -> DropletRequest<Create, ()> {
+		self.url_mut()
+			.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(DESTROY_SEGMENT)
+			.push(RETRY_SEGMENT);
+
+		self.transmute()
+	}
+}
+
+// These endpoints respond with the value itself rather than wrapping it.
+
+impl HasResponse for AssociatedResources {
+	type Response = AssociatedResources;
+}
+
+impl HasValue for AssociatedResources {
+	type Value = AssociatedResources;
+
+	fn value(self) -> AssociatedResources {
+		self
+	}
+}
+
+impl HasResponse for DestroyStatus {
+	type Response = DestroyStatus;
+}
+
+impl HasValue for DestroyStatus {
+	type Value = DestroyStatus;
+
+	fn value(self) -> DestroyStatus {
+		self
+	}
+}
@@ -0,0 +1,235 @@
+use self::firewall_fields::{InboundRule, OutboundRule};
+use super::{HasResponse, Target, Wrapped, WrapperKey};
+use crate::method::{Create, Delete, Get, List};
+use crate::request::FirewallRequest;
+use crate::request::Request;
+use crate::validation;
+use crate::{ROOT_URL, STATIC_URL_ERROR};
+use chrono::{DateTime, Utc};
+use getset::{Getters, Setters};
+use serde::Serialize;
+use std::fmt::Display;
+
+const FIREWALLS_SEGMENT: &str = "firewalls";
+
+/// Cloud Firewalls filter traffic to and from Droplets, which are chosen by ID
+/// or by tag.
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#tag/Firewalls)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct Firewall {
+	/// A unique ID that can be used to identify and reference a firewall.
+	id: String,
+
+	/// A human-readable name for the firewall.
+	name: String,
+
+	/// The state of the firewall, either "waiting", "succeeded" or "failed".
+	status: String,
+
+	/// When the firewall was created.
+	created_at: DateTime<Utc>,
+
+	/// Rules for traffic into the Droplets.
+	#[serde(default)]
+	inbound_rules: Vec<InboundRule>,
+
+	/// Rules for traffic out of the Droplets.
+	#[serde(default)]
+	outbound_rules: Vec<OutboundRule>,
+
+	/// The IDs of the Droplets the firewall is assigned to.
+	#[serde(default)]
+	droplet_ids: Vec<usize>,
+
+	/// The tags of the Droplets the firewall is assigned to.
+	#[serde(default)]
+	tags: Vec<String>
+}
+
+/// Fields which exists inside firewalls.
+pub mod firewall_fields {
+	use crate::api::Target;
+
+	/// Where traffic may come from or go to. Any combination of fields may
+	/// be given.
+	#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+	pub struct Endpoints {
+		/// IPv4 or IPv6 addresses or CIDR ranges.
+		#[serde(default, skip_serializing_if = "Vec::is_empty")]
+		pub addresses: Vec<String>,
+
+		#[serde(default, skip_serializing_if = "Vec::is_empty")]
+		pub droplet_ids: Vec<usize>,
+
+		#[serde(default, skip_serializing_if = "Vec::is_empty")]
+		pub tags: Vec<String>,
+
+		#[serde(default, skip_serializing_if = "Vec::is_empty")]
+		pub load_balancer_uids: Vec<String>
+	}
+
+	impl From<Target> for Endpoints {
+		fn from(target: Target) -> Self {
+			match target {
+				Target::Droplets(droplet_ids) => Endpoints {
+					droplet_ids,
+					..Endpoints::default()
+				},
+				Target::Tags(tags) => Endpoints {
+					tags,
+					..Endpoints::default()
+				}
+			}
+		}
+	}
+
+	/// This exists in the `inbound_rules` field of a firewall.
+	#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+	pub struct InboundRule {
+		/// Either "tcp", "udp" or "icmp".
+		pub protocol: String,
+
+		/// A port, such as "22", a range, such as "8000-9000", or "all".
+		/// Empty for ICMP.
+		#[serde(default)]
+		pub ports: String,
+
+		pub sources: Endpoints
+	}
+
+	/// This exists in the `outbound_rules` field of a firewall.
+	#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+	pub struct OutboundRule {
+		/// Either "tcp", "udp" or "icmp".
+		pub protocol: String,
+
+		/// A port, such as "22", a range, such as "8000-9000", or "all".
+		/// Empty for ICMP.
+		#[serde(default)]
+		pub ports: String,
+
+		pub destinations: Endpoints
+	}
+}
+
+impl Firewall {
+	/// Add rules and choose the Droplets it applies to by chaining
+	/// `.inbound_rules()`, `.outbound_rules()` and `.target()` onto this.
+	///
+	/// `name` must not be empty.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/firewalls_create)
+	pub fn create<S>(name: S) -> FirewallRequest<Create, Firewall>
+		where S: AsRef<str> + Serialize + Display {
+		let mut url = ROOT_URL.clone();
+		url.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(FIREWALLS_SEGMENT);
+
+		let mut req = Request::new(url);
+		req.check("name", validation::non_empty(name.as_ref()));
+		req.set_body(json!({
+			"name": name,
+		}));
+		req.require(&["name"]);
+		req
+	}
+
+	/// Create a firewall which applies to every Droplet with `tag`, including
+	/// ones tagged later.
+	///
+	/// `name` must not be empty and `tag` must be a valid tag name.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/firewalls_create)
+	pub fn create_for_tag<S>(
+		name: S,
+		tag: S,
+		inbound: Vec<InboundRule>,
+		outbound: Vec<OutboundRule>,
+	) -> FirewallRequest<Create, Firewall>
+		where S: AsRef<str> + Serialize + Display {
+		Firewall::create(name)
+			.target(Target::tag(tag.as_ref()))
+			.inbound_rules(inbound)
+			.outbound_rules(outbound)
+	}
+
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/firewalls_get)
+	pub fn get<S>(id: S) -> FirewallRequest<Get, Firewall>
+		where S: AsRef<str> + Serialize + Display {
+		let mut url = ROOT_URL.clone();
+		url.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(FIREWALLS_SEGMENT)
+			.push(id.as_ref());
+
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
+	}
+
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/firewalls_list)
+	pub fn list() -> FirewallRequest<List, Vec<Firewall>> {
+		let mut url = ROOT_URL.clone();
+		url.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(FIREWALLS_SEGMENT);
+
+		Request::new(url)
+	}
+
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/firewalls_delete)
+	pub fn delete<S>(id: S) -> FirewallRequest<Delete, ()>
+		where S: AsRef<str> + Serialize + Display {
+		let mut url = ROOT_URL.clone();
+		url.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(FIREWALLS_SEGMENT)
+			.push(id.as_ref());
+
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
+	}
+}
+
+impl FirewallRequest<Create, Firewall> {
+	/// The Droplets the firewall applies to, by ID or by tag. Tag names are
+	/// validated.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/firewalls_create)
+	pub fn target(mut self, target: Target) -> Self {
+		target.apply(&mut self);
+		self
+	}
+
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/firewalls_create)
+	pub fn inbound_rules(mut self, rules: Vec<InboundRule>) -> Self {
+		self.body_mut()["inbound_rules"] = json!(rules);
+		self
+	}
+
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/firewalls_create)
+	pub fn outbound_rules(mut self, rules: Vec<OutboundRule>) -> Self {
+		self.body_mut()["outbound_rules"] = json!(rules);
+		self
+	}
+}
+
+/// The key a firewall is wrapped under in responses.
+pub struct FirewallKey;
+
+impl WrapperKey for FirewallKey {
+	const KEY: &'static str = "firewall";
+}
+
+/// Response type returned from Digital Ocean.
+pub type FirewallResponse = Wrapped<FirewallKey, Firewall>;
+
+impl HasResponse for Firewall {
+	type Response = FirewallResponse;
+}
+
+list_response!(FirewallListResponse, Firewall, firewalls);
@@ -1,15 +1,14 @@
-use super::{ApiLinks, ApiMeta};
 use super::{Droplet, Region};
-use super::{HasPagination, HasResponse, HasValue};
+use super::{HasResponse, HasValue};
 use crate::method::{Create, Delete, Get, List};
 use crate::request::FloatingIpRequest;
 use crate::request::Request;
+use crate::validation;
 use crate::{ROOT_URL, STATIC_URL_ERROR};
 use getset::{Getters, Setters};
 use serde::Serialize;
 use std::fmt::Display;
 use std::net::IpAddr;
-use url::Url;
 
 const FLOATING_IP_SEGMENT: &str = "floating_ips";
 
@@ -35,6 +34,7 @@ pub struct FloatingIp {
 	/// The Droplet that the Floating IP has been assigned to. When you query
 	/// a Floating IP, if it is assigned to a Droplet, the entire Droplet
 	/// object will be returned. If it is not assigned, the value will be null.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
 	droplet: Option<Droplet>
 }
 
@@ -60,9 +60,12 @@ impl FloatingIp {
 		req.set_body(json!({
 			"droplet_id": id,
 		}));
+		req.require(&["droplet_id"]);
 		req
 	}
 
+	/// `id` must be a region slug.
+	///
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-floating-ip-reserved-to-a-region)
 	pub fn for_region<S>(id: S) -> FloatingIpRequest<Create, FloatingIp>
 		where
@@ -73,9 +76,11 @@ impl FloatingIp {
 			.push(FLOATING_IP_SEGMENT);
 
 		let mut req = Request::new(url);
+		req.check("region", validation::region_slug(id.as_ref()));
 		req.set_body(json!({
 			"region": id,
 		}));
+		req.require(&["region"]);
 		req
 	}
 
@@ -87,7 +92,9 @@ impl FloatingIp {
 			.push(FLOATING_IP_SEGMENT)
 			.push(&id.into().to_string());
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("ip");
+		req
 	}
 
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#retrieve-an-existing-floating-ip)
@@ -98,7 +105,9 @@ impl FloatingIp {
 			.push(FLOATING_IP_SEGMENT)
 			.push(&id.into().to_string());
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("ip");
+		req
 	}
 }
 
@@ -120,28 +129,4 @@ impl HasValue for FloatingIpResponse {
 	}
 }
 
-/// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct FloatingIpListResponse {
-	floating_ips: Vec<FloatingIp>,
-	links: ApiLinks,
-	meta: ApiMeta
-}
-
-impl HasResponse for Vec<FloatingIp> {
-	type Response = FloatingIpListResponse;
-}
-
-impl HasPagination for FloatingIpListResponse {
-	fn next_page(&self) -> Option<Url> {
-		self.links.next()
-	}
-}
-
-impl HasValue for FloatingIpListResponse {
-	type Value = Vec<FloatingIp>;
-
-	fn value(self) -> Vec<FloatingIp> {
-		self.floating_ips
-	}
-}
+list_response!(FloatingIpListResponse, FloatingIp, floating_ips);
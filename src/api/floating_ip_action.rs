@@ -25,6 +25,7 @@ impl FloatingIpRequest<Get, FloatingIp> {
 			.push(FLOATING_IP_ACTIONS_SEGMENT)
 			.push(&id.to_string());
 
+		self.path_param("action_id");
 		self.transmute()
 	}
 
@@ -38,6 +39,7 @@ impl FloatingIpRequest<Get, FloatingIp> {
 		self.set_body(json!({
 			"type": "unassign",
 		}));
+		self.require(&["type"]);
 
 		self.transmute()
 	}
@@ -53,6 +55,7 @@ impl FloatingIpRequest<Get, FloatingIp> {
 			"type": "assign",
 			"droplet_id": id,
 		}));
+		self.require(&["type", "droplet_id"]);
 
 		self.transmute()
 	}
@@ -1,5 +1,4 @@
-use super::{ApiLinks, ApiMeta};
-use super::{HasPagination, HasResponse, HasValue};
+use super::{HasResponse, HasValue};
 use crate::method::{Delete, Get, List, Update};
 use crate::request::ImageRequest;
 use crate::request::Request;
@@ -8,7 +7,6 @@ use chrono::{DateTime, Utc};
 use getset::{Getters, Setters};
 use serde::Serialize;
 use std::fmt::Display;
-use url::Url;
 
 const IMAGES_SEGMENT: &str = "images";
 
@@ -46,6 +44,7 @@ pub struct Image {
 	/// A uniquely identifying string that is associated with each of the
 	/// DigitalOcean-provided public images. These can be used to reference
 	/// a public image as an alternative to the numeric id.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
 	slug: Option<String>,
 
 	/// This is a boolean value that indicates whether the image in question
@@ -61,6 +60,7 @@ pub struct Image {
 	min_disk_size: usize,
 
 	/// The size of the image in gigabytes.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
 	size_gigabytes: Option<f32>,
 
 	/// A time value given in ISO8601 combined date and time format that
@@ -125,7 +125,9 @@ impl Image {
 			.push(IMAGES_SEGMENT)
 			.push(&format!("{}", id));
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
 	}
 
 	/// `id` is either an `id` (numeric) or a `slug` (string).
@@ -138,7 +140,9 @@ impl Image {
 			.push(IMAGES_SEGMENT)
 			.push(&format!("{}", id));
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
 	}
 
 	/// `id` is either an `id` (numeric) or a `slug` (string).
@@ -151,7 +155,9 @@ impl Image {
 			.push(IMAGES_SEGMENT)
 			.push(&format!("{}", id));
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
 	}
 }
 
@@ -183,28 +189,4 @@ impl HasValue for ImageResponse {
 	}
 }
 
-/// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct ImageListResponse {
-	images: Vec<Image>,
-	links: ApiLinks,
-	meta: ApiMeta
-}
-
-impl HasResponse for Vec<Image> {
-	type Response = ImageListResponse;
-}
-
-impl HasPagination for ImageListResponse {
-	fn next_page(&self) -> Option<Url> {
-		self.links.next()
-	}
-}
-
-impl HasValue for ImageListResponse {
-	type Value = Vec<Image>;
-
-	fn value(self) -> Vec<Image> {
-		self.images
-	}
-}
+list_response!(ImageListResponse, Image, images);
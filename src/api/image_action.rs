@@ -31,6 +31,7 @@ impl ImageRequest<Get, Image> {
 			"type": "transfer",
 			"region": region,
 		}));
+		self.require(&["type", "region"]);
 
 		self.transmute()
 	}
@@ -45,6 +46,7 @@ impl ImageRequest<Get, Image> {
 		self.set_body(json!({
 			"type": "convert",
 		}));
+		self.require(&["type"]);
 
 		self.transmute()
 	}
@@ -57,6 +59,7 @@ impl ImageRequest<Get, Image> {
 			.push(IMAGE_ACTIONS_SEGMENT)
 			.push(&id.to_string());
 
+		self.path_param("action_id");
 		self.transmute()
 	}
 }
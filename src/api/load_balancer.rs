@@ -1,17 +1,16 @@
 use self::load_balancer_fields::{ForwardingRule, HealthCheck, StickySessions};
-use super::Region;
-use super::{ApiLinks, ApiMeta};
-use super::{HasPagination, HasResponse, HasValue};
+use super::{Region, Target};
+use super::{HasResponse, HasValue};
 use crate::method::{Create, Delete, Get, List, Update};
 use crate::request::LoadBalancerRequest;
 use crate::request::Request;
+use crate::validation;
 use crate::{ROOT_URL, STATIC_URL_ERROR};
 use chrono::{DateTime, Utc};
 use getset::{Getters, Setters};
 use serde::Serialize;
 use std::fmt::Display;
 use std::net::IpAddr;
-use url::Url;
 
 const LOAD_BALANCERS_SEGMENT: &str = "load_balancers";
 const DROPLETS_SEGMENT: &str = "droplets";
@@ -226,20 +225,25 @@ impl LoadBalancer {
 	///
 	/// **Note:** It may contain one of the droplets_ids or tag attributes as they are mutually exclusive.
 	///
+	/// `name` must not be empty and `region` must be a region slug.
+	///
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-load-balancer)
-	pub fn create<S>(name: S, region: S) -> LoadBalancerRequest<Create, LoadBalancer>
-		where S: AsRef<str> + Serialize + Display {
+	pub fn create<S, R>(name: S, region: R) -> LoadBalancerRequest<Create, LoadBalancer>
+		where S: AsRef<str> + Serialize + Display, R: AsRef<str> + Serialize + Display {
 		let mut url = ROOT_URL.clone();
 		url.path_segments_mut()
 			.expect(STATIC_URL_ERROR)
 			.push(LOAD_BALANCERS_SEGMENT);
 
 		let mut req = Request::new(url);
+		req.check("name", validation::non_empty(name.as_ref()));
+		req.check("region", validation::region_slug(region.as_ref()));
 		req.set_body(json!({
 			"name": name,
 			"region": region,
 			"forwarding_rules": [],
 		}));
+		req.require(&["name", "region", "forwarding_rules"]);
 		req
 	}
 
@@ -252,7 +256,9 @@ impl LoadBalancer {
 			.push(LOAD_BALANCERS_SEGMENT)
 			.push(id.as_ref());
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
 	}
 
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-load-balancers)
@@ -278,7 +284,9 @@ impl LoadBalancer {
 			.push(LOAD_BALANCERS_SEGMENT)
 			.push(id.as_ref());
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
 	}
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#delete-a-load-balancer)
 	pub fn delete<S>(id: S) -> LoadBalancerRequest<Delete, ()>
@@ -289,7 +297,9 @@ impl LoadBalancer {
 			.push(LOAD_BALANCERS_SEGMENT)
 			.push(id.as_ref());
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
 	}
 }
 
@@ -432,6 +442,26 @@ impl LoadBalancerRequest<Create, LoadBalancer> {
 		self.body_mut()["tag"] = json!(tag);
 		self
 	}
+
+	/// The Droplets to be assigned to the Load Balancer, by ID or by tag. A
+	/// Load Balancer can only follow a single tag, so more than one is
+	/// invalid.
+	///
+	/// **Note:** Not intended to be used alongside the `droplets` or `tag`
+	/// functions.
+	pub fn target(mut self, target: Target) -> LoadBalancerRequest<Create, LoadBalancer> {
+		match target {
+			Target::Droplets(ids) => self.droplets(ids),
+			Target::Tags(ref tags) if tags.len() == 1 => {
+				self.check("tag", validation::tag_name(&tags[0]));
+				self.tag(tags[0].as_str())
+			},
+			Target::Tags(_) => {
+				self.invalidate("target", "must be Droplet IDs or exactly one tag");
+				self
+			}
+		}
+	}
 }
 
 impl LoadBalancerRequest<Update, LoadBalancer> {
@@ -595,6 +625,26 @@ impl LoadBalancerRequest<Update, LoadBalancer> {
 		self.body_mut()["tag"] = json!(tag);
 		self
 	}
+
+	/// The Droplets to be assigned to the Load Balancer, by ID or by tag. A
+	/// Load Balancer can only follow a single tag, so more than one is
+	/// invalid.
+	///
+	/// **Note:** Not intended to be used alongside the `droplets` or `tag`
+	/// functions.
+	pub fn target(mut self, target: Target) -> LoadBalancerRequest<Update, LoadBalancer> {
+		match target {
+			Target::Droplets(ids) => self.droplets(ids),
+			Target::Tags(ref tags) if tags.len() == 1 => {
+				self.check("tag", validation::tag_name(&tags[0]));
+				self.tag(tags[0].as_str())
+			},
+			Target::Tags(_) => {
+				self.invalidate("target", "must be Droplet IDs or exactly one tag");
+				self
+			}
+		}
+	}
 }
 
 impl LoadBalancerRequest<Get, LoadBalancer> {
@@ -610,6 +660,7 @@ impl LoadBalancerRequest<Get, LoadBalancer> {
 		self.set_body(json!({
 			"droplet_ids": ids,
 		}));
+		self.require(&["droplet_ids"]);
 
 		self.transmute()
 	}
@@ -626,6 +677,7 @@ impl LoadBalancerRequest<Get, LoadBalancer> {
 		self.set_body(json!({
 			"droplet_ids": ids,
 		}));
+		self.require(&["droplet_ids"]);
 
 		self.transmute()
 	}
@@ -711,28 +763,4 @@ impl HasValue for LoadBalancerResponse {
 	}
 }
 
-/// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct LoadBalancerListResponse {
-	load_balancers: Vec<LoadBalancer>,
-	links: ApiLinks,
-	meta: ApiMeta
-}
-
-impl HasResponse for Vec<LoadBalancer> {
-	type Response = LoadBalancerListResponse;
-}
-
-impl HasPagination for LoadBalancerListResponse {
-	fn next_page(&self) -> Option<Url> {
-		self.links.next()
-	}
-}
-
-impl HasValue for LoadBalancerListResponse {
-	type Value = Vec<LoadBalancer>;
-
-	fn value(self) -> Vec<LoadBalancer> {
-		self.load_balancers
-	}
-}
+list_response!(LoadBalancerListResponse, LoadBalancer, load_balancers);
@@ -1,51 +1,123 @@
 //! API specific documentation.
 
+/// Declares the response wrapping a paginated listing, where the values are
+/// found under `$key`, along with its `HasResponse`, `HasPagination` and
+/// `HasValue` impls. A response without `links` is a single page.
+///
+/// ```ignore
+/// list_response!(DomainListResponse, Domain, domains);
+/// ```
+macro_rules! list_response {
+	($response:ident, $value:ty, $key:ident) => {
+		/// Response type returned from Digital Ocean.
+		#[derive(Deserialize, Serialize, Debug, Clone)]
+		pub struct $response {
+			$key: Vec<$value>,
+			#[serde(default)]
+			links: $crate::api::ApiLinks,
+			#[serde(default)]
+			meta: $crate::api::ApiMeta
+		}
+
+		impl $crate::api::HasResponse for Vec<$value> {
+			type Response = $response;
+		}
+
+		impl $crate::api::HasPagination for $response {
+			fn next_page(&self) -> Option<::url::Url> {
+				self.links.next()
+			}
+
+			fn prev_page(&self) -> Option<::url::Url> {
+				self.links.prev()
+			}
+
+			fn total(&self) -> Option<u64> {
+				self.meta.total
+			}
+		}
+
+		impl $crate::api::HasValue for $response {
+			type Value = Vec<$value>;
+
+			fn value(self) -> Vec<$value> {
+				self.$key
+			}
+		}
+	};
+}
+
 mod account;
 mod action;
+mod billing;
 mod certificate;
 mod custom_image;
 mod domain;
 mod domain_record;
 mod droplet;
 mod droplet_action;
+mod droplet_destroy;
+mod firewall;
 mod floating_ip;
 mod floating_ip_action;
 mod image;
 mod image_action;
 mod load_balancer;
+mod monitoring;
 mod region;
+mod reserved_ipv6;
 mod size;
 mod snapshot;
+mod spaces;
 mod ssh_key;
 mod tag;
+mod uptime;
 mod volume;
 mod volume_action;
 
-use serde::de::DeserializeOwned;
+use crate::method::Method;
+use crate::request::Request;
+use crate::validation;
+use serde::de::{self, DeserializeOwned};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::fmt;
+use std::marker::PhantomData;
 use url::Url;
 use url_serde;
 
 pub use self::account::Account;
 pub use self::action::Action;
+pub use self::billing::{BillingHistory, Invoice};
 pub use self::certificate::Certificate;
 pub use self::custom_image::CustomImage;
 pub use self::domain::Domain;
 pub use self::domain_record::DomainRecord;
-pub use self::droplet::{droplet_fields, Droplet};
+pub use self::droplet::{
+	droplet_fields, Droplet, DropletBackupPolicy, DropletResponse, DropletsByRegion,
+	GroupedDropletRequest, SupportedBackupPolicy,
+};
+pub use self::droplet_destroy::{destroy_fields, AssociatedResources, DestroyStatus};
+pub use self::firewall::{firewall_fields, Firewall};
 pub use self::floating_ip::FloatingIp;
 pub use self::image::Image;
 pub use self::load_balancer::{load_balancer_fields, LoadBalancer};
-pub use self::region::Region;
-pub use self::size::Size;
+pub use self::monitoring::{monitoring_fields, Metrics};
+pub use self::region::{region_fields, Region};
+pub use self::reserved_ipv6::ReservedIpv6;
+pub use self::size::{size_fields, Size};
 pub use self::snapshot::Snapshot;
-pub use self::ssh_key::SshKey;
+pub use self::spaces::{spaces_endpoint, CdnEndpoint, SpacesGrant, SpacesKey};
+pub use self::ssh_key::{ssh_key_fields, SshKey};
 pub use self::tag::Tag;
+pub use self::uptime::{uptime_fields, CheckState, UptimeAlert, UptimeCheck};
 pub use self::volume::Volume;
 
 // Defined in https://developers.digitalocean.com/documentation/v2/#links
 pub const MAX_PER_PAGE: usize = 200;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
 struct ApiLinks {
 	pages: Option<ApiPages>
 }
@@ -60,6 +132,10 @@ impl ApiLinks {
 			None => None
 		}
 	}
+
+	fn prev(&self) -> Option<Url> {
+		self.pages.as_ref().and_then(|pages| pages.prev.clone())
+	}
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -77,13 +153,233 @@ struct ApiPages {
 	last: Option<Url>
 }
 
+/// The `links` of a response which started actions, such as creating a Droplet.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+struct ActionLinks {
+	#[serde(default)]
+	actions: Vec<ActionLink>
+}
+
+impl ActionLinks {
+	/// The id of the action with the given relation, such as "create".
+	fn action(&self, rel: &str) -> Option<usize> {
+		self.actions.iter().find(|a| a.rel == rel).map(|a| a.id)
+	}
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
+struct ActionLink {
+	id: usize,
+	rel: String,
+	#[serde(with = "url_serde")]
+	href: Url
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
 struct ApiMeta {
-	total: usize
+	#[serde(default)]
+	total: Option<u64>
+}
+
+/// A saved position in a paginated listing, taken from the `next` link of a
+/// response. It can be serialized to checkpoint long-running jobs and later
+/// handed to [`resume_from()`](../request/struct.Request.html#method.resume_from).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct PageCursor(#[serde(with = "url_serde")] Url);
+
+impl PageCursor {
+	/// The URL of the page this cursor points at.
+	pub fn url(&self) -> &Url {
+		&self.0
+	}
+}
+
+impl From<Url> for PageCursor {
+	fn from(url: Url) -> Self {
+		PageCursor(url)
+	}
+}
+
+/// A single page of a listing, along with where it sits among the others.
+/// Returned by [`DigitalOcean::execute_page()`](../struct.DigitalOcean.html#method.execute_page).
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Page<T> {
+	pub items: Vec<T>,
+
+	/// The number of values across every page, such as for showing "20 of
+	/// 340".
+	pub total: Option<u64>,
+
+	#[serde(with = "url_serde")]
+	pub next: Option<Url>,
+
+	#[serde(with = "url_serde")]
+	pub prev: Option<Url>
+}
+
+impl<T> Page<T> {
+	/// A cursor pointing at the next page, if there is one.
+	pub fn cursor(&self) -> Option<PageCursor> {
+		self.next.clone().map(PageCursor)
+	}
+}
+
+/// An arbitrary response body, used by [`DigitalOcean::raw()`](../struct.DigitalOcean.html#method.raw)
+/// to reach endpoints which are not modelled by this crate.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+pub struct Raw<T>(pub T);
+
+impl<T: DeserializeOwned + Clone> HasResponse for Raw<T> {
+	type Response = Raw<T>;
+}
+
+impl<T: DeserializeOwned + Clone> HasValue for Raw<T> {
+	type Value = Raw<T>;
+
+	fn value(self) -> Raw<T> {
+		self
+	}
+}
+
+/// The Droplets a resource applies to, either by ID or by tag. It is sent as
+/// `droplet_ids` or `tags` respectively.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub enum Target {
+	#[serde(rename = "droplet_ids")]
+	Droplets(Vec<usize>),
+
+	#[serde(rename = "tags")]
+	Tags(Vec<String>)
+}
+
+impl Target {
+	/// Every Droplet with the given tag.
+	pub fn tag<S: Into<String>>(tag: S) -> Self {
+		Target::Tags(vec![tag.into()])
+	}
+
+	/// Set the target's key in the body of `request`. Tag names are
+	/// validated.
+	pub(crate) fn apply<M: Method, V>(&self, request: &mut Request<M, V>) {
+		if let Target::Tags(ref tags) = *self {
+			for tag in tags {
+				request.check("tags", validation::tag_name(tag));
+			}
+		}
+
+		if let Value::Object(fields) = json!(self) {
+			for (key, value) in fields {
+				request.body_mut()[&key] = value;
+			}
+		}
+	}
+}
+
+/// The key a resource is wrapped under in a response, such as `"droplet"`.
+pub trait WrapperKey {
+	const KEY: &'static str;
+}
+
+/// A value found under `K::KEY` in a response, such as the Droplet in
+/// `{"droplet": {...}}`. Any other keys, such as `links`, are ignored.
+///
+/// This saves declaring a response type for each resource, and can be used
+/// with [`DigitalOcean::raw()`](../struct.DigitalOcean.html#method.raw) to
+/// unwrap responses from endpoints which are not modelled by this crate.
+///
+/// ```rust,no_run
+/// # use digitalocean::prelude::*;
+/// # use digitalocean::api::{Wrapped, WrapperKey};
+/// # use digitalocean::method::Get;
+/// # use serde_json::Value;
+/// # let client = DigitalOcean::new("token").unwrap();
+/// struct AppKey;
+///
+/// impl WrapperKey for AppKey {
+///     const KEY: &'static str = "app";
+/// }
+///
+/// let app = DigitalOcean::raw::<Get, Wrapped<AppKey, Value>>("apps/4f6c71e2", None)
+///     .execute(&client)
+///     .map(|raw| raw.0.into_inner());
+/// ```
+pub struct Wrapped<K, T> {
+	value: T,
+	key: PhantomData<fn() -> K>
+}
+
+impl<K, T> Wrapped<K, T> {
+	pub fn new(value: T) -> Self {
+		Wrapped {
+			value,
+			key: PhantomData
+		}
+	}
+
+	/// The value which was found under the key.
+	pub fn into_inner(self) -> T {
+		self.value
+	}
+}
+
+impl<K, T: Clone> Clone for Wrapped<K, T> {
+	fn clone(&self) -> Self {
+		Wrapped::new(self.value.clone())
+	}
+}
+
+impl<K: WrapperKey, T: fmt::Debug> fmt::Debug for Wrapped<K, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Wrapped")
+			.field("key", &K::KEY)
+			.field("value", &self.value)
+			.finish()
+	}
+}
+
+impl<'de, K: WrapperKey, T: DeserializeOwned> Deserialize<'de> for Wrapped<K, T> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let mut object = serde_json::Map::deserialize(deserializer)?;
+		let value = object.remove(K::KEY).ok_or_else(|| de::Error::missing_field(K::KEY))?;
+
+		T::deserialize(value).map(Wrapped::new).map_err(de::Error::custom)
+	}
+}
+
+impl<K: WrapperKey, T: Serialize> Serialize for Wrapped<K, T> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut map = serializer.serialize_map(Some(1))?;
+		map.serialize_entry(K::KEY, &self.value)?;
+		map.end()
+	}
+}
+
+impl<K: WrapperKey, T: HasResponse> HasValue for Wrapped<K, T> {
+	type Value = T;
+
+	fn value(self) -> T {
+		self.value
+	}
 }
 
 pub trait HasPagination {
 	fn next_page(&self) -> Option<Url>;
+
+	/// The URL of the previous page, if there is one.
+	fn prev_page(&self) -> Option<Url> {
+		None
+	}
+
+	/// The number of values across every page, if the response says.
+	fn total(&self) -> Option<u64> {
+		None
+	}
+
+	/// A cursor pointing at the next page, if there is one.
+	fn cursor(&self) -> Option<PageCursor> {
+		self.next_page().map(PageCursor)
+	}
 }
 
 pub trait HasValue {
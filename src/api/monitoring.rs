@@ -0,0 +1,349 @@
+use self::monitoring_fields::{Direction, Interface, Series};
+use super::{HasResponse, HasValue};
+use crate::method::Get;
+use crate::request::MonitoringRequest;
+use crate::request::Request;
+use crate::{ROOT_URL, STATIC_URL_ERROR};
+use chrono::{DateTime, Utc};
+use getset::{Getters, Setters};
+
+const MONITORING_SEGMENT: &str = "monitoring";
+const METRICS_SEGMENT: &str = "metrics";
+const DROPLET_SEGMENT: &str = "droplet";
+const BANDWIDTH_SEGMENT: &str = "bandwidth";
+const MEMORY_TOTAL_SEGMENT: &str = "memory_total";
+const MEMORY_FREE_SEGMENT: &str = "memory_free";
+const MEMORY_AVAILABLE_SEGMENT: &str = "memory_available";
+const MEMORY_CACHED_SEGMENT: &str = "memory_cached";
+const FILESYSTEM_FREE_SEGMENT: &str = "filesystem_free";
+const FILESYSTEM_SIZE_SEGMENT: &str = "filesystem_size";
+const LOAD_1_SEGMENT: &str = "load_1";
+const LOAD_5_SEGMENT: &str = "load_5";
+const LOAD_15_SEGMENT: &str = "load_15";
+
+/// Metrics are time series collected by the DigitalOcean agent running on a
+/// Droplet. Monitoring must be enabled on a Droplet for it to report them.
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#tag/Monitoring)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct Metrics {
+	/// The kind of result returned. For time series this is "matrix".
+	#[serde(rename = "resultType")]
+	result_type: String,
+
+	/// Each time series matching the query.
+	result: Vec<Series>
+}
+
+/// Fields which exists inside Metrics.
+pub mod monitoring_fields {
+	use chrono::{DateTime, Utc};
+	use std::collections::HashMap;
+	use std::fmt;
+
+	/// This exists in the `result` field of metrics.
+	#[derive(Deserialize, Serialize, Debug, Clone)]
+	pub struct Series {
+		/// Labels identifying the series, such as `host_id` and `direction`.
+		pub metric: HashMap<String, String>,
+
+		/// Samples as pairs of a Unix timestamp and a value.
+		pub values: Vec<(i64, String)>
+	}
+
+	/// The network interface a bandwidth metric is measured on.
+	#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+	#[serde(rename_all = "lowercase")]
+	pub enum Interface {
+		Public,
+		Private
+	}
+
+	impl fmt::Display for Interface {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			match *self {
+				Interface::Public => write!(f, "public"),
+				Interface::Private => write!(f, "private")
+			}
+		}
+	}
+
+	/// The direction of traffic a bandwidth metric is measured for.
+	#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+	#[serde(rename_all = "lowercase")]
+	pub enum Direction {
+		Inbound,
+		Outbound
+	}
+
+	impl fmt::Display for Direction {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			match *self {
+				Direction::Inbound => write!(f, "inbound"),
+				Direction::Outbound => write!(f, "outbound")
+			}
+		}
+	}
+
+	/// The traffic a Droplet sent or received over a period, as returned by
+	/// [`DigitalOcean::droplet_transfer()`](../../struct.DigitalOcean.html#method.droplet_transfer).
+	#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+	pub struct Transfer {
+		pub start: DateTime<Utc>,
+		pub end: DateTime<Utc>,
+
+		/// The total traffic, in bytes.
+		pub bytes: f64,
+
+		/// The number of samples the total was worked out from. This is `0`
+		/// if monitoring reported no data for the period.
+		pub samples: usize
+	}
+
+	impl Transfer {
+		/// The total traffic, in gibibytes.
+		pub fn gib(&self) -> f64 {
+			self.bytes / (1u64 << 30) as f64
+		}
+	}
+}
+
+impl Metrics {
+	/// `end` must be after `start`.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/monitoring_get_dropletBandwidthMetrics)
+	pub fn droplet_bandwidth(
+		host_id: usize,
+		interface: Interface,
+		direction: Direction,
+		start: DateTime<Utc>,
+		end: DateTime<Utc>,
+	) -> MonitoringRequest<Get, Metrics> {
+		let mut req = droplet_metric(BANDWIDTH_SEGMENT, host_id, start, end);
+		req.url_mut()
+			.query_pairs_mut()
+			.append_pair("interface", &interface.to_string())
+			.append_pair("direction", &direction.to_string());
+		req
+	}
+
+	/// Total memory, in bytes. `end` must be after `start`.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/monitoring_get_dropletTotalMemoryMetrics)
+	pub fn droplet_memory_total(
+		host_id: usize,
+		start: DateTime<Utc>,
+		end: DateTime<Utc>,
+	) -> MonitoringRequest<Get, Metrics> {
+		droplet_metric(MEMORY_TOTAL_SEGMENT, host_id, start, end)
+	}
+
+	/// Free memory, in bytes. `end` must be after `start`.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/monitoring_get_dropletFreeMemoryMetrics)
+	pub fn droplet_memory_free(
+		host_id: usize,
+		start: DateTime<Utc>,
+		end: DateTime<Utc>,
+	) -> MonitoringRequest<Get, Metrics> {
+		droplet_metric(MEMORY_FREE_SEGMENT, host_id, start, end)
+	}
+
+	/// Memory available to new processes, in bytes. `end` must be after `start`.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/monitoring_get_dropletAvailableMemoryMetrics)
+	pub fn droplet_memory_available(
+		host_id: usize,
+		start: DateTime<Utc>,
+		end: DateTime<Utc>,
+	) -> MonitoringRequest<Get, Metrics> {
+		droplet_metric(MEMORY_AVAILABLE_SEGMENT, host_id, start, end)
+	}
+
+	/// Memory used by the page cache, in bytes. `end` must be after `start`.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/monitoring_get_dropletCachedMemoryMetrics)
+	pub fn droplet_memory_cached(
+		host_id: usize,
+		start: DateTime<Utc>,
+		end: DateTime<Utc>,
+	) -> MonitoringRequest<Get, Metrics> {
+		droplet_metric(MEMORY_CACHED_SEGMENT, host_id, start, end)
+	}
+
+	/// Free space on each filesystem, in bytes. `end` must be after `start`.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/monitoring_get_dropletFilesystemFreeMetrics)
+	pub fn droplet_filesystem_free(
+		host_id: usize,
+		start: DateTime<Utc>,
+		end: DateTime<Utc>,
+	) -> MonitoringRequest<Get, Metrics> {
+		droplet_metric(FILESYSTEM_FREE_SEGMENT, host_id, start, end)
+	}
+
+	/// Size of each filesystem, in bytes. `end` must be after `start`.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/monitoring_get_dropletFilesystemSizeMetrics)
+	pub fn droplet_filesystem_size(
+		host_id: usize,
+		start: DateTime<Utc>,
+		end: DateTime<Utc>,
+	) -> MonitoringRequest<Get, Metrics> {
+		droplet_metric(FILESYSTEM_SIZE_SEGMENT, host_id, start, end)
+	}
+
+	/// The one minute load average. `end` must be after `start`.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/monitoring_get_dropletLoad1Metrics)
+	pub fn droplet_load_1(
+		host_id: usize,
+		start: DateTime<Utc>,
+		end: DateTime<Utc>,
+	) -> MonitoringRequest<Get, Metrics> {
+		droplet_metric(LOAD_1_SEGMENT, host_id, start, end)
+	}
+
+	/// The five minute load average. `end` must be after `start`.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/monitoring_get_dropletLoad5Metrics)
+	pub fn droplet_load_5(
+		host_id: usize,
+		start: DateTime<Utc>,
+		end: DateTime<Utc>,
+	) -> MonitoringRequest<Get, Metrics> {
+		droplet_metric(LOAD_5_SEGMENT, host_id, start, end)
+	}
+
+	/// The fifteen minute load average. `end` must be after `start`.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/monitoring_get_dropletLoad15Metrics)
+	pub fn droplet_load_15(
+		host_id: usize,
+		start: DateTime<Utc>,
+		end: DateTime<Utc>,
+	) -> MonitoringRequest<Get, Metrics> {
+		droplet_metric(LOAD_15_SEGMENT, host_id, start, end)
+	}
+}
+
+impl Metrics {
+	/// The mean of every sample across all series, or `None` if there are
+	/// none. Samples which are not finite numbers are skipped.
+	pub fn average(&self) -> Option<f64> {
+		let (sum, count) = self.samples()
+			.fold((0.0, 0usize), |(sum, count), (_, v)| (sum + v, count + 1));
+		if count == 0 {
+			None
+		} else {
+			Some(sum / count as f64)
+		}
+	}
+
+	/// The largest sample across all series, or `None` if there are none.
+	pub fn max(&self) -> Option<f64> {
+		self.samples().map(|(_, v)| v).fold(None, |max, v| match max {
+			Some(max) if max >= v => Some(max),
+			_ => Some(v)
+		})
+	}
+
+	/// The smallest sample across all series, or `None` if there are none.
+	pub fn min(&self) -> Option<f64> {
+		self.samples().map(|(_, v)| v).fold(None, |min, v| match min {
+			Some(min) if min <= v => Some(min),
+			_ => Some(v)
+		})
+	}
+
+	/// The most recent sample across all series, or `None` if there are
+	/// none.
+	pub fn latest(&self) -> Option<f64> {
+		self.samples()
+			.fold(None, |latest: Option<(i64, f64)>, (t, v)| match latest {
+				Some((lt, _)) if lt > t => latest,
+				_ => Some((t, v))
+			})
+			.map(|(_, v)| v)
+	}
+
+	/// The total traffic described by bandwidth metrics, in bytes, and the
+	/// number of samples it was worked out from.
+	///
+	/// Bandwidth is sampled in megabits per second, so each series is
+	/// integrated over time, averaging each pair of neighbouring samples.
+	/// Samples which do not parse are skipped, so the rate is assumed to
+	/// change evenly across gaps in the data.
+	pub fn transfer_bytes(&self) -> (f64, usize) {
+		self.result.iter().fold((0.0, 0), |(bytes, count), series| {
+			let samples = parse_samples(series).collect::<Vec<_>>();
+			let megabits: f64 = samples.windows(2)
+				.map(|w| (w[1].0 - w[0].0) as f64 * (w[0].1 + w[1].1) / 2.0)
+				.sum();
+
+			(bytes + megabits * 1_000_000.0 / 8.0, count + samples.len())
+		})
+	}
+
+	/// Every sample as a timestamp and a number, skipping those which do not
+	/// parse or are not finite, such as `NaN`.
+	fn samples(&self) -> impl Iterator<Item = (i64, f64)> + '_ {
+		self.result.iter().flat_map(parse_samples)
+	}
+}
+
+fn parse_samples(series: &Series) -> impl Iterator<Item = (i64, f64)> + '_ {
+	series.values.iter()
+		.filter_map(|&(t, ref v)| match v.parse::<f64>() {
+			Ok(v) if v.is_finite() => Some((t, v)),
+			_ => None
+		})
+}
+
+/// All Droplet metrics share the same shape: a path under
+/// `monitoring/metrics/droplet` and a host and time range in the query.
+fn droplet_metric(
+	metric: &str,
+	host_id: usize,
+	start: DateTime<Utc>,
+	end: DateTime<Utc>,
+) -> MonitoringRequest<Get, Metrics> {
+	let mut url = ROOT_URL.clone();
+	url.path_segments_mut()
+		.expect(STATIC_URL_ERROR)
+		.push(MONITORING_SEGMENT)
+		.push(METRICS_SEGMENT)
+		.push(DROPLET_SEGMENT)
+		.push(metric);
+
+	url.query_pairs_mut()
+		.append_pair("host_id", &host_id.to_string())
+		.append_pair("start", &start.timestamp().to_string())
+		.append_pair("end", &end.timestamp().to_string());
+
+	let mut req = Request::new(url);
+	if end <= start {
+		req.invalidate("end", "must be after start");
+	}
+	req
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct MetricsResponse {
+	status: String,
+	data: Metrics
+}
+
+impl HasResponse for Metrics {
+	type Response = MetricsResponse;
+}
+
+impl HasValue for MetricsResponse {
+	type Value = Metrics;
+
+	fn value(self) -> Metrics {
+		self.data
+	}
+}
@@ -1,11 +1,8 @@
-use super::{ApiLinks, ApiMeta};
-use super::{HasPagination, HasResponse, HasValue};
 use crate::method::List;
 use crate::request::RegionRequest;
 use crate::request::Request;
 use crate::{ROOT_URL, STATIC_URL_ERROR};
 use getset::{Getters, Setters};
-use url::Url;
 
 const REGIONS_SEGMENT: &str = "regions";
 
@@ -41,6 +38,102 @@ pub struct Region {
 	features: Vec<String>,
 }
 
+/// Fields which exists inside regions.
+pub mod region_fields {
+	use std::convert::Infallible;
+	use std::fmt;
+	use std::str::FromStr;
+
+	macro_rules! region_slugs {
+		($($variant:ident => $slug:expr),* $(,)?) => {
+			/// The slug of a region, such as `nyc3`. Regions this crate does
+			/// not know about yet are kept as `Other`, so they still round
+			/// trip.
+			///
+			/// It implements `AsRef<str>`, `Display` and `Serialize`, so it
+			/// may be given to any builder which takes a region slug.
+			#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+			#[serde(from = "String", into = "String")]
+			pub enum RegionSlug {
+				$($variant,)*
+				Other(String)
+			}
+
+			impl RegionSlug {
+				/// The slug as sent to and received from the API.
+				pub fn as_str(&self) -> &str {
+					match *self {
+						$(RegionSlug::$variant => $slug,)*
+						RegionSlug::Other(ref slug) => slug
+					}
+				}
+			}
+
+			impl From<String> for RegionSlug {
+				fn from(slug: String) -> Self {
+					match slug.as_str() {
+						$($slug => RegionSlug::$variant,)*
+						_ => RegionSlug::Other(slug)
+					}
+				}
+			}
+		};
+	}
+
+	region_slugs! {
+		Nyc1 => "nyc1",
+		Nyc2 => "nyc2",
+		Nyc3 => "nyc3",
+		Sfo1 => "sfo1",
+		Sfo2 => "sfo2",
+		Sfo3 => "sfo3",
+		Ams2 => "ams2",
+		Ams3 => "ams3",
+		Sgp1 => "sgp1",
+		Lon1 => "lon1",
+		Fra1 => "fra1",
+		Tor1 => "tor1",
+		Blr1 => "blr1",
+		Syd1 => "syd1",
+		Atl1 => "atl1",
+	}
+
+	impl<'a> From<&'a str> for RegionSlug {
+		fn from(slug: &'a str) -> Self {
+			RegionSlug::from(slug.to_string())
+		}
+	}
+
+	impl From<RegionSlug> for String {
+		fn from(slug: RegionSlug) -> Self {
+			match slug {
+				RegionSlug::Other(slug) => slug,
+				known => known.as_str().to_string()
+			}
+		}
+	}
+
+	impl FromStr for RegionSlug {
+		type Err = Infallible;
+
+		fn from_str(slug: &str) -> Result<Self, Infallible> {
+			Ok(RegionSlug::from(slug))
+		}
+	}
+
+	impl AsRef<str> for RegionSlug {
+		fn as_ref(&self) -> &str {
+			self.as_str()
+		}
+	}
+
+	impl fmt::Display for RegionSlug {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			f.write_str(self.as_str())
+		}
+	}
+}
+
 impl Region {
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-regions)
 	pub fn list() -> RegionRequest<List, Vec<Region>> {
@@ -53,28 +146,4 @@ impl Region {
 	}
 }
 
-/// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct RegionListResponse {
-	regions: Vec<Region>,
-	links: ApiLinks,
-	meta: ApiMeta
-}
-
-impl HasResponse for Vec<Region> {
-	type Response = RegionListResponse;
-}
-
-impl HasPagination for RegionListResponse {
-	fn next_page(&self) -> Option<Url> {
-		self.links.next()
-	}
-}
-
-impl HasValue for RegionListResponse {
-	type Value = Vec<Region>;
-
-	fn value(self) -> Vec<Region> {
-		self.regions
-	}
-}
+list_response!(RegionListResponse, Region, regions);
@@ -0,0 +1,153 @@
+use super::{Action, Droplet};
+use super::{HasResponse, HasValue};
+use crate::method::{Create, Delete, Get, List, Method};
+use crate::request::Request;
+use crate::request::ReservedIpv6Request;
+use crate::validation;
+use crate::{ROOT_URL, STATIC_URL_ERROR};
+use chrono::{DateTime, Utc};
+use getset::{Getters, Setters};
+use serde::Serialize;
+use std::fmt::Display;
+use std::net::{IpAddr, Ipv6Addr};
+
+const RESERVED_IPV6_SEGMENT: &str = "reserved_ipv6";
+const ACTIONS_SEGMENT: &str = "actions";
+
+/// Reserved IPv6 addresses are publicly-accessible static IPv6 addresses
+/// that can be mapped to one of your Droplets.
+///
+/// Reserved IPv6 addresses are bound to a specific region.
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#tag/Reserved-IPv6)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct ReservedIpv6 {
+	/// The public IPv6 address. It also serves as its identifier.
+	ip: Ipv6Addr,
+
+	/// The slug of the region the address is reserved to.
+	region_slug: String,
+
+	/// When the address was reserved.
+	reserved_at: DateTime<Utc>,
+
+	/// The Droplet that the address has been assigned to, if any.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	droplet: Option<Droplet>
+}
+
+impl ReservedIpv6 {
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPv6s_list)
+	pub fn list() -> ReservedIpv6Request<List, Vec<ReservedIpv6>> {
+		let mut url = ROOT_URL.clone();
+		url.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(RESERVED_IPV6_SEGMENT);
+
+		Request::new(url)
+	}
+
+	/// `region` must be a region slug.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPv6s_create)
+	pub fn create<S>(region: S) -> ReservedIpv6Request<Create, ReservedIpv6>
+		where S: AsRef<str> + Display + Serialize {
+		let mut url = ROOT_URL.clone();
+		url.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(RESERVED_IPV6_SEGMENT);
+
+		let mut req = Request::new(url);
+		req.check("region_slug", validation::region_slug(region.as_ref()));
+		req.set_body(json!({
+			"region_slug": region,
+		}));
+		req.require(&["region_slug"]);
+		req
+	}
+
+	/// `ip` must be an IPv6 address.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPv6s_get)
+	pub fn get<I: Into<IpAddr>>(ip: I) -> ReservedIpv6Request<Get, ReservedIpv6> {
+		at(ip.into())
+	}
+
+	/// `ip` must be an IPv6 address.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPv6s_delete)
+	pub fn delete<I: Into<IpAddr>>(ip: I) -> ReservedIpv6Request<Delete, ()> {
+		at(ip.into())
+	}
+}
+
+/// A request for a single reserved IPv6 address. IPv4 addresses are rejected
+/// rather than sent, since they can never name a reserved IPv6 address.
+fn at<M: Method, V>(ip: IpAddr) -> ReservedIpv6Request<M, V> {
+	let mut url = ROOT_URL.clone();
+	url.path_segments_mut()
+		.expect(STATIC_URL_ERROR)
+		.push(RESERVED_IPV6_SEGMENT)
+		.push(&ip.to_string());
+
+	let mut req = Request::new(url);
+	req.path_param("ip");
+	if ip.is_ipv4() {
+		req.invalidate("ip", "must be an IPv6 address");
+	}
+	req
+}
+
+impl ReservedIpv6Request<Get, ReservedIpv6> {
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPv6Actions_post)
+	pub fn assign(mut self, droplet_id: usize) -> ReservedIpv6Request<Create, Action> {
+		self.url_mut()
+			.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(ACTIONS_SEGMENT);
+
+		self.set_body(json!({
+			"type": "assign",
+			"droplet_id": droplet_id,
+		}));
+		self.require(&["type", "droplet_id"]);
+
+		self.transmute()
+	}
+
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPv6Actions_post)
+	pub fn unassign(mut self) -> ReservedIpv6Request<Create, Action> {
+		self.url_mut()
+			.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(ACTIONS_SEGMENT);
+
+		self.set_body(json!({
+			"type": "unassign",
+		}));
+		self.require(&["type"]);
+
+		self.transmute()
+	}
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ReservedIpv6Response {
+	reserved_ipv6: ReservedIpv6
+}
+
+impl HasResponse for ReservedIpv6 {
+	type Response = ReservedIpv6Response;
+}
+
+impl HasValue for ReservedIpv6Response {
+	type Value = ReservedIpv6;
+
+	fn value(self) -> ReservedIpv6 {
+		self.reserved_ipv6
+	}
+}
+
+list_response!(ReservedIpv6ListResponse, ReservedIpv6, reserved_ipv6s);
@@ -1,11 +1,9 @@
-use super::{ApiLinks, ApiMeta};
-use super::{HasPagination, HasResponse, HasValue};
+use self::size_fields::GpuInfo;
 use crate::method::List;
 use crate::request::Request;
 use crate::request::SizeRequest;
 use crate::{ROOT_URL, STATIC_URL_ERROR};
 use getset::{Getters, Setters};
-use url::Url;
 
 const SIZES_SEGMENT: &str = "sizes";
 
@@ -18,6 +16,9 @@ const SIZES_SEGMENT: &str = "sizes";
 /// disk space, and transfer. The size object also includes the pricing
 /// details and the regions that the size is available in.
 ///
+/// Fields which only some sizes have, such as `gpu_info`, are optional, and
+/// fields this crate does not model are ignored.
+///
 /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#sizes)
 #[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
 #[get = "pub"]
@@ -56,7 +57,42 @@ pub struct Size {
 
 	/// An array containing the region slugs where this size is available for
     /// Droplet creates.
-	regions: Vec<String>
+	regions: Vec<String>,
+
+	/// A short description of the size, such as "Basic" or "GPU".
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	description: Option<String>,
+
+	/// The GPUs attached to Droplets of this size, for GPU sizes only.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	gpu_info: Option<GpuInfo>
+}
+
+/// Fields which exists inside sizes.
+pub mod size_fields {
+	/// This exists in the `gpu_info` field of a size.
+	#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+	#[serde(default)]
+	pub struct GpuInfo {
+		/// The number of GPUs.
+		pub count: usize,
+
+		/// The model of the GPUs, such as "nvidia_h100".
+		pub model: String,
+
+		/// The memory of each GPU.
+		pub vram: Option<Vram>
+	}
+
+	/// This exists in the `vram` field of a size's GPU info.
+	#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+	#[serde(default)]
+	pub struct Vram {
+		pub amount: usize,
+
+		/// The unit of `amount`, such as "gib".
+		pub unit: String
+	}
 }
 
 impl Size {
@@ -73,28 +109,4 @@ impl Size {
 
 // There is no signular size return.
 
-/// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct SizeListResponse {
-	sizes: Vec<Size>,
-	links: ApiLinks,
-	meta: ApiMeta
-}
-
-impl HasResponse for Vec<Size> {
-	type Response = SizeListResponse;
-}
-
-impl HasPagination for SizeListResponse {
-	fn next_page(&self) -> Option<Url> {
-		self.links.next()
-	}
-}
-
-impl HasValue for SizeListResponse {
-	type Value = Vec<Size>;
-
-	fn value(self) -> Vec<Size> {
-		self.sizes
-	}
-}
+list_response!(SizeListResponse, Size, sizes);
@@ -1,12 +1,10 @@
-use super::{ApiLinks, ApiMeta};
-use super::{HasPagination, HasResponse, HasValue};
+use super::{HasResponse, HasValue};
 use crate::method::{Delete, Get, List};
 use crate::request::Request;
 use crate::request::SnapshotRequest;
 use crate::{ROOT_URL, STATIC_URL_ERROR};
 use chrono::{DateTime, Utc};
 use getset::{Getters, Setters};
-use url::Url;
 
 const SNAPSHOT_SEGMENT: &str = "snapshots";
 
@@ -92,7 +90,9 @@ impl Snapshot {
 			.push(SNAPSHOT_SEGMENT)
 			.push(&id.to_string());
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
 	}
 
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#delete-a-snapshot)
@@ -103,35 +103,13 @@ impl Snapshot {
 			.push(SNAPSHOT_SEGMENT)
 			.push(&id.to_string());
 
-		Request::new(url)
-	}
-}
-
-/// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct SnapshotListResponse {
-	snapshots: Vec<Snapshot>,
-	links: ApiLinks,
-	meta: ApiMeta
-}
-
-impl HasResponse for Vec<Snapshot> {
-	type Response = SnapshotListResponse;
-}
-
-impl HasPagination for SnapshotListResponse {
-	fn next_page(&self) -> Option<Url> {
-		self.links.next()
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
 	}
 }
 
-impl HasValue for SnapshotListResponse {
-	type Value = Vec<Snapshot>;
-
-	fn value(self) -> Vec<Snapshot> {
-		self.snapshots
-	}
-}
+list_response!(SnapshotListResponse, Snapshot, snapshots);
 
 /// Response type returned from Digital Ocean.
 #[derive(Deserialize, Serialize, Debug, Clone)]
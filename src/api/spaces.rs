@@ -0,0 +1,160 @@
+use super::{HasResponse, HasValue};
+use crate::error::Error;
+use crate::method::{Get, List};
+use crate::request::Request;
+use crate::request::SpacesRequest;
+use crate::validation;
+use crate::{ROOT_URL, STATIC_URL_ERROR};
+use chrono::{DateTime, Utc};
+use getset::{Getters, Setters};
+use url::Url;
+
+const CDN_SEGMENT: &str = "cdn";
+const ENDPOINTS_SEGMENT: &str = "endpoints";
+const SPACES_SEGMENT: &str = "spaces";
+const KEYS_SEGMENT: &str = "keys";
+const SPACES_DOMAIN: &str = "digitaloceanspaces.com";
+
+/// The S3-compatible endpoint serving Spaces buckets in `region`, such as
+/// `https://nyc3.digitaloceanspaces.com` for `nyc3`.
+///
+/// Object operations are not part of the Digital Ocean API, so this only
+/// tells an S3 client where to connect. `region` must be a region slug.
+pub fn spaces_endpoint(region: &str) -> Result<Url, Error> {
+	validation::region_slug(region).map_err(|reason| Error::InvalidArgument {
+		field: "region".to_string(),
+		reason
+	})?;
+
+	Ok(Url::parse(&format!("https://{}.{}", region, SPACES_DOMAIN)).expect(STATIC_URL_ERROR))
+}
+
+/// A CDN endpoint caches the contents of a Space at edge locations.
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#tag/CDN-Endpoints)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct CdnEndpoint {
+	/// A unique ID that can be used to identify and reference a CDN endpoint.
+	id: String,
+
+	/// The fully qualified domain name of the Space the endpoint serves, such
+	/// as `static-images.nyc3.digitaloceanspaces.com`.
+	origin: String,
+
+	/// The fully qualified domain name from which the cached content is
+	/// served.
+	endpoint: String,
+
+	/// How long, in seconds, content is cached at the edge.
+	ttl: usize,
+
+	/// The ID of the certificate used with a custom domain, if any.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	certificate_id: Option<String>,
+
+	/// A custom domain the cached content is also served from, if any.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	custom_domain: Option<String>,
+
+	/// When the endpoint was created.
+	created_at: DateTime<Utc>
+}
+
+impl CdnEndpoint {
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/cdn_list_endpoints)
+	pub fn list() -> SpacesRequest<List, Vec<CdnEndpoint>> {
+		let mut url = ROOT_URL.clone();
+		url.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(CDN_SEGMENT)
+			.push(ENDPOINTS_SEGMENT);
+
+		Request::new(url)
+	}
+
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/cdn_get_endpoint)
+	pub fn get<S: AsRef<str>>(id: S) -> SpacesRequest<Get, CdnEndpoint> {
+		let mut url = ROOT_URL.clone();
+		url.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(CDN_SEGMENT)
+			.push(ENDPOINTS_SEGMENT)
+			.push(id.as_ref());
+
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
+	}
+}
+
+/// An access key which S3 clients use to authenticate against Spaces.
+///
+/// The secret key is only ever returned when a key is created, so it is not
+/// part of listings.
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#tag/Spaces-Keys)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct SpacesKey {
+	/// The name given to the key.
+	name: String,
+
+	/// The access key ID, which also serves as its identifier.
+	access_key: String,
+
+	/// The buckets the key may access, and how.
+	#[serde(default)]
+	grants: Vec<SpacesGrant>,
+
+	/// When the key was created.
+	created_at: DateTime<Utc>
+}
+
+/// A permission held by a [`SpacesKey`](struct.SpacesKey.html).
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct SpacesGrant {
+	/// The bucket the grant applies to. Empty for grants covering every
+	/// bucket.
+	#[serde(default)]
+	bucket: String,
+
+	/// The permission granted, such as `read`, `readwrite`, or `fullaccess`.
+	permission: String
+}
+
+impl SpacesKey {
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/spacesKey_list)
+	pub fn list() -> SpacesRequest<List, Vec<SpacesKey>> {
+		let mut url = ROOT_URL.clone();
+		url.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(SPACES_SEGMENT)
+			.push(KEYS_SEGMENT);
+
+		Request::new(url)
+	}
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CdnEndpointResponse {
+	endpoint: CdnEndpoint
+}
+
+impl HasResponse for CdnEndpoint {
+	type Response = CdnEndpointResponse;
+}
+
+impl HasValue for CdnEndpointResponse {
+	type Value = CdnEndpoint;
+
+	fn value(self) -> CdnEndpoint {
+		self.endpoint
+	}
+}
+
+list_response!(CdnEndpointListResponse, CdnEndpoint, endpoints);
+
+list_response!(SpacesKeyListResponse, SpacesKey, keys);
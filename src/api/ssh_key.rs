@@ -1,13 +1,12 @@
-use super::{ApiLinks, ApiMeta};
-use super::{HasPagination, HasResponse, HasValue};
+use super::{HasResponse, HasValue};
 use crate::method::{Create, Delete, Get, List, Update};
 use crate::request::Request;
 use crate::request::SshKeyRequest;
+use crate::validation;
 use crate::{ROOT_URL, STATIC_URL_ERROR};
 use getset::{Getters, Setters};
 use serde::Serialize;
 use std::fmt::Display;
-use url::Url;
 
 const ACCOUNT_SEGMENT: &str = "account";
 const KEYS_SEGMENT: &str = "keys";
@@ -43,7 +42,70 @@ pub struct SshKey {
 	name: String,
 }
 
+/// Fields which exists inside SSH keys.
+pub mod ssh_key_fields {
+	use std::convert::TryFrom;
+	use std::fmt;
+
+	/// An SSH key as given when creating a Droplet: either its id or its
+	/// fingerprint. Ids are sent as numbers and fingerprints as strings, and
+	/// the two can be mixed in one list.
+	#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+	#[serde(untagged)]
+	pub enum SshKeyIdent {
+		Id(usize),
+		Fingerprint(String)
+	}
+
+	impl<'a> From<&'a str> for SshKeyIdent {
+		fn from(fingerprint: &'a str) -> Self {
+			SshKeyIdent::Fingerprint(fingerprint.to_string())
+		}
+	}
+
+	impl From<String> for SshKeyIdent {
+		fn from(fingerprint: String) -> Self {
+			SshKeyIdent::Fingerprint(fingerprint)
+		}
+	}
+
+	impl From<usize> for SshKeyIdent {
+		fn from(id: usize) -> Self {
+			SshKeyIdent::Id(id)
+		}
+	}
+
+	impl From<u32> for SshKeyIdent {
+		fn from(id: u32) -> Self {
+			SshKeyIdent::Id(id as usize)
+		}
+	}
+
+	/// So that integer literals, which default to `i32`, can be given as
+	/// ids. A negative id is kept as written and rejected when the request is
+	/// validated.
+	impl From<i32> for SshKeyIdent {
+		fn from(id: i32) -> Self {
+			match usize::try_from(id) {
+				Ok(id) => SshKeyIdent::Id(id),
+				Err(_) => SshKeyIdent::Fingerprint(id.to_string())
+			}
+		}
+	}
+
+	impl fmt::Display for SshKeyIdent {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			match *self {
+				SshKeyIdent::Id(id) => write!(f, "{}", id),
+				SshKeyIdent::Fingerprint(ref fingerprint) => write!(f, "{}", fingerprint)
+			}
+		}
+	}
+}
+
 impl SshKey {
+	/// `name` and `public_key` must not be empty.
+	///
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-key)
 	pub fn create<N>(name: N, public_key: N) -> SshKeyRequest<Create, SshKey>
 		where N: AsRef<str> + Serialize + Display, {
@@ -54,10 +116,13 @@ impl SshKey {
 			.push(KEYS_SEGMENT);
 
 		let mut req = Request::new(url);
+		req.check("name", validation::non_empty(name.as_ref()));
+		req.check("public_key", validation::non_empty(public_key.as_ref()));
 		req.set_body(json!({
             "name": name,
             "public_key": public_key,
         }));
+		req.require(&["name", "public_key"]);
 		req
 	}
 
@@ -81,7 +146,9 @@ impl SshKey {
 			.push(KEYS_SEGMENT)
 			.push(&format!("{}", id));
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
 	}
 
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#retrieve-an-existing-key)
@@ -93,7 +160,9 @@ impl SshKey {
 			.push(KEYS_SEGMENT)
 			.push(&format!("{}", id));
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
 	}
 
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#delete-a-domain)
@@ -105,7 +174,9 @@ impl SshKey {
 			.push(KEYS_SEGMENT)
 			.push(&format!("{}", id));
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
 	}
 }
 
@@ -119,31 +190,7 @@ impl SshKeyRequest<Update, SshKey> {
 	}
 }
 
-/// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct SshKeyListResponse {
-	ssh_keys: Vec<SshKey>,
-	links: ApiLinks,
-	meta: ApiMeta
-}
-
-impl HasResponse for Vec<SshKey> {
-	type Response = SshKeyListResponse;
-}
-
-impl HasPagination for SshKeyListResponse {
-	fn next_page(&self) -> Option<Url> {
-		self.links.next()
-	}
-}
-
-impl HasValue for SshKeyListResponse {
-	type Value = Vec<SshKey>;
-
-	fn value(self) -> Vec<SshKey> {
-		self.ssh_keys
-	}
-}
+list_response!(SshKeyListResponse, SshKey, ssh_keys);
 
 /// Response type returned from Digital Ocean.
 #[derive(Deserialize, Serialize, Debug, Clone)]
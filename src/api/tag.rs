@@ -1,14 +1,13 @@
-use super::{ApiLinks, ApiMeta};
-use super::{HasPagination, HasResponse, HasValue};
+use super::{HasResponse, HasValue};
 use crate::method::{Create, Delete, Get, List};
 use crate::request::Request;
 use crate::request::TagRequest;
+use crate::validation;
 use crate::{ROOT_URL, STATIC_URL_ERROR};
 use getset::{Getters, Setters};
 use serde::Serialize;
 use serde_json::Value;
 use std::fmt::Display;
-use url::Url;
 
 const TAG_SEGMENT: &str = "tags";
 const RESOURCES_SEGMENT: &str = "resources";
@@ -34,6 +33,8 @@ pub struct Tag {
 }
 
 impl Tag {
+	/// `name` must be a valid tag name.
+	///
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-tag)
 	pub fn create<S: AsRef<str> + Serialize + Display>(name: S) -> TagRequest<Create, Tag> {
 		let mut url = ROOT_URL.clone();
@@ -42,9 +43,11 @@ impl Tag {
 			.push(TAG_SEGMENT);
 
 		let mut req = Request::new(url);
+		req.check("name", validation::tag_name(name.as_ref()));
 		req.set_body(json!({
 			"name": name,
 		}));
+		req.require(&["name"]);
 		req
 	}
 
@@ -56,7 +59,9 @@ impl Tag {
 			.push(TAG_SEGMENT)
 			.push(name.as_ref());
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("name");
+		req
 	}
 
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#retrieve-a-tag)
@@ -77,7 +82,9 @@ impl Tag {
 			.push(TAG_SEGMENT)
 			.push(name.as_ref());
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("name");
+		req
 	}
 }
 
@@ -105,6 +112,7 @@ impl TagRequest<Get, Tag> {
 		self.set_body(json!({
 			"resources": resources,
 		}));
+		self.require(&["resources"]);
 
 		self.transmute()
 	}
@@ -132,6 +140,7 @@ impl TagRequest<Get, Tag> {
 		self.set_body(json!({
 			"resources": resources,
 		}));
+		self.require(&["resources"]);
 
 		self.transmute()
 	}
@@ -155,28 +164,4 @@ impl HasResponse for Tag {
 	type Response = TagResponse;
 }
 
-/// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct TagListResponse {
-	tags: Vec<Tag>,
-	links: ApiLinks,
-	meta: ApiMeta
-}
-
-impl HasResponse for Vec<Tag> {
-	type Response = TagListResponse;
-}
-
-impl HasPagination for TagListResponse {
-	fn next_page(&self) -> Option<Url> {
-		self.links.next()
-	}
-}
-
-impl HasValue for TagListResponse {
-	type Value = Vec<Tag>;
-
-	fn value(self) -> Vec<Tag> {
-		self.tags
-	}
-}
+list_response!(TagListResponse, Tag, tags);
@@ -0,0 +1,435 @@
+use self::uptime_fields::{CheckKind, Notifications, Outage, RegionState};
+use super::{HasResponse, HasValue};
+use crate::method::{Create, Delete, Get, List, Update};
+use crate::request::Request;
+use crate::request::UptimeRequest;
+use crate::validation;
+use crate::{ROOT_URL, STATIC_URL_ERROR};
+use getset::{Getters, Setters};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+const UPTIME_SEGMENT: &str = "uptime";
+const CHECKS_SEGMENT: &str = "checks";
+const STATE_SEGMENT: &str = "state";
+const ALERTS_SEGMENT: &str = "alerts";
+
+/// Uptime checks periodically probe a target (a host or URL) from one or
+/// more regions and record whether it is reachable.
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#tag/Uptime)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct UptimeCheck {
+	/// A unique ID that can be used to identify and reference the check.
+	id: String,
+
+	/// A human-friendly display name.
+	name: String,
+
+	/// The type of health check to perform.
+	///
+	/// *Note:* Since `type` is a keyword in Rust `kind` is used instead.
+	#[serde(rename = "type")]
+	kind: CheckKind,
+
+	/// The endpoint to perform healthchecks on.
+	target: String,
+
+	/// The regions the check is performed from, such as "us_east".
+	regions: Vec<String>,
+
+	/// Whether the check is enabled.
+	enabled: bool
+}
+
+/// The current state of an uptime check.
+///
+/// Requests with this output this type are accessed via [`UptimeCheck::get(..).state()`](../request/type.UptimeRequest.html#method.state).
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/uptime_get_checkState)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct CheckState {
+	/// The state of the check in each region it is performed from.
+	regions: HashMap<String, RegionState>,
+
+	/// The most recent outage, if there has been one.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	previous_outage: Option<Outage>
+}
+
+/// An alert which notifies when an uptime check fails some condition.
+///
+/// Requests with this output this type are accessed via [`UptimeCheck::get(..).alerts()`](../request/type.UptimeRequest.html#method.alerts).
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/uptime_list_alerts)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct UptimeAlert {
+	/// A unique ID that can be used to identify and reference the alert.
+	id: String,
+
+	/// A human-friendly display name.
+	name: String,
+
+	/// The type of alert, such as "latency", "down", "down_global" or
+	/// "ssl_expiry".
+	///
+	/// *Note:* Since `type` is a keyword in Rust `kind` is used instead.
+	#[serde(rename = "type")]
+	kind: String,
+
+	/// The threshold at which the alert will enter a trigger state. The
+	/// specific threshold is dependent on the alert type.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	threshold: Option<usize>,
+
+	/// The comparison operator used against the threshold, either
+	/// "greater_than" or "less_than".
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	comparison: Option<String>,
+
+	/// Where notifications are sent.
+	notifications: Notifications,
+
+	/// The period of time the threshold must be exceeded to trigger the
+	/// alert, such as "2m".
+	period: String
+}
+
+/// Fields which exists inside uptime checks and alerts.
+pub mod uptime_fields {
+	use chrono::{DateTime, Utc};
+	use std::fmt;
+
+	/// The type of health check an uptime check performs.
+	#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+	#[serde(rename_all = "lowercase")]
+	pub enum CheckKind {
+		Ping,
+		Http,
+		Https
+	}
+
+	impl fmt::Display for CheckKind {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			match *self {
+				CheckKind::Ping => write!(f, "ping"),
+				CheckKind::Http => write!(f, "http"),
+				CheckKind::Https => write!(f, "https")
+			}
+		}
+	}
+
+	/// This exists in the `regions` field of a check state.
+	#[derive(Deserialize, Serialize, Debug, Clone)]
+	pub struct RegionState {
+		/// Either "UP", "DOWN" or "UNKNOWN".
+		pub status: String,
+		pub status_changed_at: DateTime<Utc>,
+		pub thirty_day_uptime_percentage: f64
+	}
+
+	/// This exists in the `previous_outage` field of a check state.
+	#[derive(Deserialize, Serialize, Debug, Clone)]
+	pub struct Outage {
+		pub region: String,
+		pub started_at: DateTime<Utc>,
+		pub ended_at: Option<DateTime<Utc>>,
+		pub duration_seconds: usize
+	}
+
+	/// This exists in the `notifications` field of an alert.
+	#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+	pub struct Notifications {
+		/// Email addresses to notify.
+		#[serde(default)]
+		pub email: Vec<String>,
+
+		/// Slack channels to notify.
+		#[serde(default)]
+		pub slack: Vec<SlackDetails>
+	}
+
+	/// This exists in the `slack` field of an alert's notifications.
+	#[derive(Deserialize, Serialize, Debug, Clone)]
+	pub struct SlackDetails {
+		pub channel: String,
+		pub url: String
+	}
+}
+
+impl UptimeCheck {
+	/// `name` and `target` must not be empty.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/uptime_create_check)
+	pub fn create<S>(
+		name: S,
+		kind: CheckKind,
+		target: S,
+		regions: Vec<S>,
+		enabled: bool,
+	) -> UptimeRequest<Create, UptimeCheck>
+		where S: AsRef<str> + Serialize + Display {
+		let mut url = ROOT_URL.clone();
+		url.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(UPTIME_SEGMENT)
+			.push(CHECKS_SEGMENT);
+
+		let mut req = Request::new(url);
+		req.check("name", validation::non_empty(name.as_ref()));
+		req.check("target", validation::non_empty(target.as_ref()));
+		req.set_body(json!({
+			"name": name,
+			"type": kind,
+			"target": target,
+			"regions": regions,
+			"enabled": enabled,
+		}));
+		req.require(&["name", "type", "target", "regions", "enabled"]);
+		req
+	}
+
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/uptime_get_check)
+	pub fn get<S: AsRef<str> + Display>(id: S) -> UptimeRequest<Get, UptimeCheck> {
+		let mut url = ROOT_URL.clone();
+		url.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(UPTIME_SEGMENT)
+			.push(CHECKS_SEGMENT)
+			.push(id.as_ref());
+
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
+	}
+
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/uptime_list_checks)
+	pub fn list() -> UptimeRequest<List, Vec<UptimeCheck>> {
+		let mut url = ROOT_URL.clone();
+		url.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(UPTIME_SEGMENT)
+			.push(CHECKS_SEGMENT);
+
+		Request::new(url)
+	}
+
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/uptime_update_check)
+	pub fn update<S: AsRef<str> + Display>(id: S) -> UptimeRequest<Update, UptimeCheck> {
+		let mut url = ROOT_URL.clone();
+		url.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(UPTIME_SEGMENT)
+			.push(CHECKS_SEGMENT)
+			.push(id.as_ref());
+
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
+	}
+
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/uptime_delete_check)
+	pub fn delete<S: AsRef<str> + Display>(id: S) -> UptimeRequest<Delete, ()> {
+		let mut url = ROOT_URL.clone();
+		url.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(UPTIME_SEGMENT)
+			.push(CHECKS_SEGMENT)
+			.push(id.as_ref());
+
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
+	}
+}
+
+impl UptimeRequest<Update, UptimeCheck> {
+	/// A human-friendly display name.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/uptime_update_check)
+	pub fn name<S: AsRef<str> + Display + Serialize>(mut self, val: S) -> Self {
+		self.body_mut()["name"] = json!(val);
+		self
+	}
+
+	/// The type of health check to perform.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/uptime_update_check)
+	pub fn kind(mut self, val: CheckKind) -> Self {
+		self.body_mut()["type"] = json!(val);
+		self
+	}
+
+	/// The endpoint to perform healthchecks on.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/uptime_update_check)
+	pub fn target<S: AsRef<str> + Display + Serialize>(mut self, val: S) -> Self {
+		self.body_mut()["target"] = json!(val);
+		self
+	}
+
+	/// The regions the check is performed from.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/uptime_update_check)
+	pub fn regions<S: AsRef<str> + Display + Serialize>(mut self, val: Vec<S>) -> Self {
+		self.body_mut()["regions"] = json!(val);
+		self
+	}
+
+	/// Whether the check is enabled.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/uptime_update_check)
+	pub fn enabled(mut self, val: bool) -> Self {
+		self.body_mut()["enabled"] = json!(val);
+		self
+	}
+}
+
+impl UptimeRequest<Get, UptimeCheck> {
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/uptime_get_checkState)
+	pub fn state(mut self) -> UptimeRequest<Get, CheckState> {
+		self.url_mut()
+			.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(STATE_SEGMENT);
+
+		self.transmute()
+	}
+
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/uptime_list_alerts)
+	pub fn alerts(mut self) -> UptimeRequest<List, Vec<UptimeAlert>> {
+		self.url_mut()
+			.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(ALERTS_SEGMENT);
+
+		self.transmute()
+	}
+}
+
+impl UptimeRequest<List, Vec<UptimeAlert>> {
+	/// `name` must not be empty.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/uptime_create_alert)
+	pub fn create<S: AsRef<str> + Display + Serialize>(
+		mut self,
+		name: S,
+		kind: S,
+		notifications: Notifications,
+		period: S,
+	) -> UptimeRequest<Create, UptimeAlert> {
+		self.check("name", validation::non_empty(name.as_ref()));
+		self.set_body(json!({
+			"name": name,
+			"type": kind,
+			"notifications": notifications,
+			"period": period,
+		}));
+		self.require(&["name", "type", "notifications", "period"]);
+
+		self.transmute()
+	}
+
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/uptime_get_alert)
+	pub fn get<S: AsRef<str> + Display>(mut self, id: S) -> UptimeRequest<Get, UptimeAlert> {
+		self.url_mut()
+			.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(id.as_ref());
+
+		self.path_param("alert_id");
+		self.transmute()
+	}
+
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/uptime_delete_alert)
+	pub fn delete<S: AsRef<str> + Display>(mut self, id: S) -> UptimeRequest<Delete, ()> {
+		self.url_mut()
+			.path_segments_mut()
+			.expect(STATIC_URL_ERROR)
+			.push(id.as_ref());
+
+		self.path_param("alert_id");
+		self.transmute()
+	}
+}
+
+impl UptimeRequest<Create, UptimeAlert> {
+	/// The threshold at which the alert will enter a trigger state.
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/uptime_create_alert)
+	pub fn threshold(mut self, val: usize) -> Self {
+		self.body_mut()["threshold"] = json!(val);
+		self
+	}
+
+	/// The comparison operator used against the threshold, either
+	/// "greater_than" or "less_than".
+	///
+	/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/uptime_create_alert)
+	pub fn comparison<S: AsRef<str> + Display + Serialize>(mut self, val: S) -> Self {
+		self.body_mut()["comparison"] = json!(val);
+		self
+	}
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct UptimeCheckResponse {
+	check: UptimeCheck
+}
+
+impl HasResponse for UptimeCheck {
+	type Response = UptimeCheckResponse;
+}
+
+impl HasValue for UptimeCheckResponse {
+	type Value = UptimeCheck;
+
+	fn value(self) -> UptimeCheck {
+		self.check
+	}
+}
+
+list_response!(UptimeCheckListResponse, UptimeCheck, checks);
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CheckStateResponse {
+	state: CheckState
+}
+
+impl HasResponse for CheckState {
+	type Response = CheckStateResponse;
+}
+
+impl HasValue for CheckStateResponse {
+	type Value = CheckState;
+
+	fn value(self) -> CheckState {
+		self.state
+	}
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct UptimeAlertResponse {
+	alert: UptimeAlert
+}
+
+impl HasResponse for UptimeAlert {
+	type Response = UptimeAlertResponse;
+}
+
+impl HasValue for UptimeAlertResponse {
+	type Value = UptimeAlert;
+
+	fn value(self) -> UptimeAlert {
+		self.alert
+	}
+}
+
+list_response!(UptimeAlertListResponse, UptimeAlert, alerts);
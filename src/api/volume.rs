@@ -1,16 +1,15 @@
 use super::region::Region;
 use super::snapshot::Snapshot;
-use super::{ApiLinks, ApiMeta};
-use super::{HasPagination, HasResponse, HasValue};
+use super::{HasResponse, HasValue};
 use crate::method::{Create, Delete, Get, List};
 use crate::request::Request;
 use crate::request::{SnapshotRequest, VolumeRequest};
+use crate::validation;
 use crate::{ROOT_URL, STATIC_URL_ERROR};
 use chrono::{DateTime, Utc};
 use getset::{Getters, Setters};
 use serde::Serialize;
 use std::fmt::Display;
-use url::Url;
 
 const VOLUME_SEGMENT: &str = "volumes";
 const SNAPSHOTS_SEGMENT: &str = "snapshots";
@@ -66,6 +65,8 @@ impl Volume {
 		Request::new(url)
 	}
 
+	/// `name` must not be empty.
+	///
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-block-storage-volume)
 	pub fn create<S>(name: S, size_gigabytes: usize) -> VolumeRequest<Create, Volume>
 		where S: AsRef<str> + Serialize + Display {
@@ -75,10 +76,12 @@ impl Volume {
 			.push(VOLUME_SEGMENT);
 
 		let mut req = Request::new(url);
+		req.check("name", validation::non_empty(name.as_ref()));
 		req.set_body(json!({
 			"name": name,
 			"size_gigabytes": size_gigabytes,
 		}));
+		req.require(&["name", "size_gigabytes"]);
 		req
 	}
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#retrieve-an-existing-block-storage-volume)
@@ -89,11 +92,13 @@ impl Volume {
 			.push(VOLUME_SEGMENT)
 			.push(id.as_ref());
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
 	}
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#retrieve-an-existing-block-storage-volume-by-name)
-	pub fn get_by_name<S>(name: S, region: S) -> VolumeRequest<Get, Volume>
-		where S: AsRef<str> + Serialize + Display {
+	pub fn get_by_name<S, R>(name: S, region: R) -> VolumeRequest<Get, Volume>
+		where S: AsRef<str> + Serialize + Display, R: AsRef<str> + Serialize + Display {
 		let mut url = ROOT_URL.clone();
 		url.path_segments_mut()
 			.expect(STATIC_URL_ERROR)
@@ -113,11 +118,13 @@ impl Volume {
 			.push(VOLUME_SEGMENT)
 			.push(id.as_ref());
 
-		Request::new(url)
+		let mut req = Request::new(url);
+		req.path_param("id");
+		req
 	}
 	/// [Digital Ocean Documentation.](hhttps://developers.digitalocean.com/documentation/v2/#delete-a-block-storage-volume-by-name)
-	pub fn delete_by_name<S>(name: S, region: S) -> VolumeRequest<Delete, ()>
-		where S: AsRef<str> + Serialize + Display {
+	pub fn delete_by_name<S, R>(name: S, region: R) -> VolumeRequest<Delete, ()>
+		where S: AsRef<str> + Serialize + Display, R: AsRef<str> + Serialize + Display {
 		let mut url = ROOT_URL.clone();
 		url.path_segments_mut()
 			.expect(STATIC_URL_ERROR)
@@ -183,8 +190,11 @@ impl VolumeRequest<Create, Volume> {
 	///
 	/// **Note:** Should not be specified with a `snapshot_id`.
 	///
+	/// `val` must be a region slug.
+	///
 	/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#block-storage)
 	pub fn region<S: AsRef<str> + Serialize + Display>(mut self, val: S) -> Self {
+		self.check("region", validation::region_slug(val.as_ref()));
 		self.body_mut()["region"] = json!(val);
 		self
 	}
@@ -201,31 +211,7 @@ impl VolumeRequest<Create, Volume> {
 	}
 }
 
-/// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct VolumeListResponse {
-	volumes: Vec<Volume>,
-	links: ApiLinks,
-	meta: ApiMeta
-}
-
-impl HasResponse for Vec<Volume> {
-	type Response = VolumeListResponse;
-}
-
-impl HasPagination for VolumeListResponse {
-	fn next_page(&self) -> Option<Url> {
-		self.links.next()
-	}
-}
-
-impl HasValue for VolumeListResponse {
-	type Value = Vec<Volume>;
-
-	fn value(self) -> Vec<Volume> {
-		self.volumes
-	}
-}
+list_response!(VolumeListResponse, Volume, volumes);
 
 /// Response type returned from Digital Ocean.
 #[derive(Deserialize, Serialize, Debug, Clone)]
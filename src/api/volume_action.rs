@@ -25,6 +25,7 @@ impl Volume {
 			"volume_name": volume_name,
 			"droplet_id": droplet,
 		}));
+		req.require(&["type", "volume_name", "droplet_id"]);
 		req
 	}
 
@@ -42,6 +43,7 @@ impl Volume {
 			"volume_name": volume_name,
 			"droplet_id": droplet,
 		}));
+		req.require(&["type", "volume_name", "droplet_id"]);
 		req
 	}
 }
@@ -58,6 +60,7 @@ impl VolumeRequest<Get, Volume> {
 			"type": "attach",
 			"droplet_id": droplet,
 		}));
+		self.require(&["type", "droplet_id"]);
 
 		self.transmute()
 	}
@@ -73,6 +76,7 @@ impl VolumeRequest<Get, Volume> {
 			"type": "detach",
 			"droplet_id": droplet,
 		}));
+		self.require(&["type", "droplet_id"]);
 
 		self.transmute()
 	}
@@ -88,6 +92,7 @@ impl VolumeRequest<Get, Volume> {
 			"type": "resize",
 			"size_gigabytes": size,
 		}));
+		self.require(&["type", "size_gigabytes"]);
 
 		self.transmute()
 	}
@@ -110,6 +115,7 @@ impl VolumeRequest<Get, Volume> {
 			.push(VOLUME_ACTIONS_SEGMENT)
 			.push(&id.to_string());
 
+		self.path_param("action_id");
 		self.transmute()
 	}
 }
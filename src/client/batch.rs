@@ -0,0 +1,60 @@
+use crate::api::HasResponse;
+use crate::error::Error;
+use crate::method::Method;
+use crate::request::{Executable, Request};
+use crate::DigitalOcean;
+use std::sync::Mutex;
+use std::thread;
+
+impl DigitalOcean {
+	/// Execute many independent requests, running up to `concurrency` of
+	/// them at once. A `concurrency` of zero is treated as one.
+	///
+	/// The results are in the same order as `requests`. A request which
+	/// fails does not stop the others.
+	///
+	/// ```rust,no_run
+	/// # use digitalocean::prelude::*;
+	/// # let client = DigitalOcean::new("token").unwrap();
+	/// let droplets = client.execute_batch(
+	///     vec![Droplet::get(1), Droplet::get(2), Droplet::get(3)],
+	///     2,
+	/// );
+	/// ```
+	pub fn execute_batch<A, V>(
+		&self,
+		requests: Vec<Request<A, V>>,
+		concurrency: usize,
+	) -> Vec<Result<V, Error>>
+		where
+			A: Method + Send,
+			V: HasResponse + Send,
+			Request<A, V>: Executable<V> {
+		let count = requests.len();
+		let workers = concurrency.max(1).min(count);
+		let queue = Mutex::new(requests.into_iter().enumerate());
+		let results = Mutex::new((0..count).map(|_| None).collect::<Vec<_>>());
+
+		thread::scope(|scope| {
+			for _ in 0..workers {
+				scope.spawn(|| loop {
+					let next = queue.lock().expect("Batch queue poisoned.").next();
+					let (index, request) = match next {
+						Some(v) => v,
+						None => break
+					};
+
+					let result = request.execute(self);
+					results.lock().expect("Batch results poisoned.")[index] = Some(result);
+				});
+			}
+		});
+
+		results
+			.into_inner()
+			.expect("Batch results poisoned.")
+			.into_iter()
+			.map(|result| result.expect("Every request in a batch is executed."))
+			.collect()
+	}
+}
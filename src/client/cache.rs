@@ -0,0 +1,101 @@
+use super::coalesce::Shared;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Remembers the `ETag` and body of list pages so that unchanged pages can
+/// be answered with `304 Not Modified` instead of being sent again.
+///
+/// Bodies are kept raw rather than deserialized so that one cache can serve
+/// every type of list. Entries are keyed like the coalescer's, so that
+/// clones with their own key or requests with their own headers never see
+/// each other's pages. The oldest entry is evicted once `capacity` is reached.
+#[derive(Debug)]
+pub struct EtagCache {
+	capacity: usize,
+	entries: Mutex<VecDeque<Entry>>
+}
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+	pub key: String,
+	pub etag: String,
+	pub body: Vec<u8>
+}
+
+impl EtagCache {
+	pub fn new(capacity: usize) -> Self {
+		EtagCache {
+			capacity,
+			entries: Mutex::new(VecDeque::with_capacity(capacity)),
+		}
+	}
+
+	pub fn capacity(&self) -> usize {
+		self.capacity
+	}
+
+	pub fn get(&self, key: &str) -> Option<Entry> {
+		let entries = self.entries.lock().expect("ETag cache poisoned.");
+		entries.iter().find(|entry| entry.key == key).cloned()
+	}
+
+	pub fn insert(&self, entry: Entry) {
+		let mut entries = self.entries.lock().expect("ETag cache poisoned.");
+		entries.retain(|existing| existing.key != entry.key);
+
+		while !entries.is_empty() && entries.len() >= self.capacity {
+			entries.pop_front();
+		}
+		if self.capacity > 0 {
+			entries.push_back(entry);
+		}
+	}
+}
+
+/// Remembers successful responses for `ttl`, so that repeating a request
+/// within it is answered without sending anything.
+///
+/// Like `EtagCache`, bodies are kept raw and the oldest entry is evicted
+/// once `capacity` is reached. Expired entries are dropped when found.
+#[derive(Debug)]
+pub struct TtlCache {
+	ttl: Duration,
+	capacity: usize,
+	entries: Mutex<VecDeque<(String, Instant, Shared)>>
+}
+
+impl TtlCache {
+	pub fn new(ttl: Duration, capacity: usize) -> Self {
+		TtlCache {
+			ttl,
+			capacity,
+			entries: Mutex::new(VecDeque::with_capacity(capacity)),
+		}
+	}
+
+	pub fn ttl(&self) -> Duration {
+		self.ttl
+	}
+
+	pub fn get(&self, key: &str) -> Option<Shared> {
+		let mut entries = self.entries.lock().expect("TTL cache poisoned.");
+		let ttl = self.ttl;
+		entries.retain(|(_, stored, _)| stored.elapsed() < ttl);
+		entries.iter()
+			.find(|(existing, _, _)| existing == key)
+			.map(|(_, _, response)| response.clone())
+	}
+
+	pub fn insert(&self, key: String, response: Shared) {
+		let mut entries = self.entries.lock().expect("TTL cache poisoned.");
+		entries.retain(|(existing, _, _)| *existing != key);
+
+		while !entries.is_empty() && entries.len() >= self.capacity {
+			entries.pop_front();
+		}
+		if self.capacity > 0 {
+			entries.push_back((key, Instant::now(), response));
+		}
+	}
+}
@@ -0,0 +1,99 @@
+use crate::error::Error;
+use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex};
+
+/// A response which was read once and handed to every caller waiting on it.
+#[derive(Debug, Clone)]
+pub struct Shared {
+	pub status: StatusCode,
+	pub path: String,
+	pub body: Arc<Vec<u8>>
+}
+
+/// Lets concurrent identical `GET`s share one request. The first caller for a
+/// key sends the request and the rest wait for its response.
+///
+/// Responses are shared whatever their status. If the first caller fails
+/// without a response, such as when the connection drops, each waiter sends
+/// the request itself, since errors cannot be shared.
+#[derive(Debug, Default)]
+pub struct Coalescer {
+	flights: Mutex<HashMap<String, Arc<Flight>>>
+}
+
+#[derive(Debug, Default)]
+struct Flight {
+	// `None` while in flight, then `Some(None)` if no response was read.
+	outcome: Mutex<Option<Option<Shared>>>,
+	done: Condvar
+}
+
+/// Finishes a flight when the first caller is done with it, even if it
+/// panicked, so that waiters are never left hanging.
+struct Leader<'a> {
+	coalescer: &'a Coalescer,
+	key: String,
+	flight: Arc<Flight>,
+	outcome: Option<Shared>
+}
+
+impl<'a> Drop for Leader<'a> {
+	fn drop(&mut self) {
+		let mut flights = self.coalescer.flights.lock().unwrap_or_else(|e| e.into_inner());
+		flights.remove(&self.key);
+		drop(flights);
+
+		let mut outcome = self.flight.outcome.lock().unwrap_or_else(|e| e.into_inner());
+		*outcome = Some(self.outcome.take());
+		self.flight.done.notify_all();
+	}
+}
+
+impl Coalescer {
+	pub fn new() -> Self {
+		Coalescer::default()
+	}
+
+	/// Call `fetch`, unless a call with the same key is already in flight, in
+	/// which case wait for its response instead.
+	pub fn run<F>(&self, key: String, fetch: F) -> Result<Shared, Error>
+		where F: FnOnce() -> Result<Shared, Error> {
+		let (flight, leading) = {
+			let mut flights = self.flights.lock().unwrap_or_else(|e| e.into_inner());
+
+			match flights.get(&key) {
+				Some(flight) => (flight.clone(), false),
+				None => {
+					let flight = Arc::new(Flight::default());
+					flights.insert(key.clone(), flight.clone());
+					(flight, true)
+				}
+			}
+		};
+
+		if !leading {
+			let mut outcome = flight.outcome.lock().unwrap_or_else(|e| e.into_inner());
+			while outcome.is_none() {
+				outcome = flight.done.wait(outcome).unwrap_or_else(|e| e.into_inner());
+			}
+
+			if let Some(Some(ref shared)) = *outcome {
+				return Ok(shared.clone());
+			}
+			drop(outcome);
+
+			return fetch();
+		}
+
+		let mut leader = Leader {
+			coalescer: self,
+			key,
+			flight,
+			outcome: None,
+		};
+		let shared = fetch()?;
+		leader.outcome = Some(shared.clone());
+		Ok(shared)
+	}
+}
@@ -0,0 +1,21 @@
+use crate::api::Account;
+use crate::error::Error;
+use crate::DigitalOcean;
+use reqwest::StatusCode;
+
+impl DigitalOcean {
+	/// Check that the API is reachable and the key is accepted, such as for a
+	/// readiness probe. This fetches the account without reading it.
+	///
+	/// Fails with `Error::Unauthorized` if the key is rejected, or
+	/// `Error::ReqwestError` if the API cannot be reached.
+	pub fn health_check(&self) -> Result<(), Error> {
+		let response = self.send_raw(Account::get())?;
+
+		match response.status() {
+			s if s.is_success() => Ok(()),
+			StatusCode::UNAUTHORIZED => Err(Error::Unauthorized),
+			e => Err(Error::UnexpectedStatus(e))
+		}
+	}
+}
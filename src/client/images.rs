@@ -0,0 +1,84 @@
+use crate::api::Image;
+use crate::error::Error;
+use crate::request::Executable;
+use crate::DigitalOcean;
+use std::sync::{Arc, Mutex};
+
+// Slugs further than this from the one given are not suggested.
+const MAX_SUGGESTION_DISTANCE: usize = 3;
+
+/// The slugs of the distribution images, fetched the first time a slug is
+/// checked and kept for the life of the client.
+#[derive(Debug, Default)]
+pub struct ImageSlugs {
+	slugs: Mutex<Option<Arc<Vec<String>>>>
+}
+
+impl DigitalOcean {
+	/// Check that `slug` names a distribution image, suggesting the closest
+	/// slugs if it does not.
+	pub(crate) fn check_image_slug(&self, cache: &ImageSlugs, slug: &str) -> Result<(), Error> {
+		let slugs = {
+			let mut slugs = cache.slugs.lock().unwrap_or_else(|e| e.into_inner());
+
+			match *slugs {
+				Some(ref slugs) => slugs.clone(),
+				None => {
+					client_log!(self, debug, "Fetching distribution image slugs.");
+					let fetched: Vec<String> = Image::distributions()
+						.execute(self)?
+						.into_iter()
+						.filter_map(|image| image.slug().clone())
+						.collect();
+
+					let fetched = Arc::new(fetched);
+					*slugs = Some(fetched.clone());
+					fetched
+				}
+			}
+		};
+
+		if slugs.iter().any(|s| s == slug) {
+			return Ok(());
+		}
+
+		let mut near: Vec<(usize, &String)> = slugs.iter()
+			.map(|s| (edit_distance(slug, s), s))
+			.filter(|&(distance, _)| distance <= MAX_SUGGESTION_DISTANCE)
+			.collect();
+		near.sort();
+
+		let reason = match near.len() {
+			0 => format!("`{}` is not a distribution image slug", slug),
+			_ => format!(
+				"`{}` is not a distribution image slug; did you mean {}?",
+				slug,
+				near.iter().take(3).map(|(_, s)| format!("`{}`", s)).collect::<Vec<_>>().join(", ")
+			)
+		};
+
+		Err(Error::InvalidArgument {
+			field: "image".to_string(),
+			reason,
+		})
+	}
+}
+
+/// The number of single character insertions, deletions or substitutions
+/// needed to turn `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+	let b: Vec<char> = b.chars().collect();
+	let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+	for (i, ca) in a.chars().enumerate() {
+		let mut current = vec![i + 1];
+
+		for (j, cb) in b.iter().enumerate() {
+			let substitution = previous[j] + if ca == *cb { 0 } else { 1 };
+			current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+		}
+		previous = current;
+	}
+
+	previous[b.len()]
+}
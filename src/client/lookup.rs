@@ -0,0 +1,34 @@
+use crate::api::droplet_fields::ListFilter;
+use crate::api::Droplet;
+use crate::error::Error;
+use crate::request::Executable;
+use crate::DigitalOcean;
+
+impl DigitalOcean {
+	/// Find the Droplet with exactly the given name, if there is one.
+	///
+	/// DigitalOcean has no endpoint for this, so it lists Droplets filtered by
+	/// name (following every page) and keeps exact matches. If more than one
+	/// Droplet has the name this fails with `Error::AmbiguousName`.
+	pub fn droplet_by_name<S: AsRef<str>>(&self, name: S) -> Result<Option<Droplet>, Error> {
+		let name = name.as_ref();
+		let filter = ListFilter {
+			tag: None,
+			name: Some(name.to_string()),
+		};
+
+		let mut matches: Vec<Droplet> = Droplet::list_filtered(filter)
+			.execute(self)?
+			.into_iter()
+			.filter(|droplet| droplet.name() == name)
+			.collect();
+
+		match matches.len() {
+			0 | 1 => Ok(matches.pop()),
+			count => Err(Error::AmbiguousName {
+				name: name.to_string(),
+				count,
+			})
+		}
+	}
+}
@@ -0,0 +1,57 @@
+use crate::{ClientMetrics, EndpointMetrics};
+use reqwest::StatusCode;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Counts what a client and its clones have sent, for
+/// [`DigitalOcean::metrics()`](../struct.DigitalOcean.html#method.metrics).
+#[derive(Debug, Default)]
+pub struct Counters {
+	requests: AtomicU64,
+	rate_limited: AtomicU64,
+	endpoints: Mutex<BTreeMap<String, EndpointMetrics>>
+}
+
+impl Counters {
+	/// Count a request which got a response with `status`, and against
+	/// `endpoint` if it is given.
+	pub fn record(&self, status: StatusCode, endpoint: Option<String>) {
+		let rate_limited = status == StatusCode::TOO_MANY_REQUESTS;
+		self.requests.fetch_add(1, Ordering::Relaxed);
+
+		if rate_limited {
+			self.rate_limited.fetch_add(1, Ordering::Relaxed);
+		}
+		if let Some(endpoint) = endpoint {
+			self.record_endpoint(endpoint, rate_limited);
+		}
+	}
+
+	/// Count a request which failed without a response.
+	pub fn record_failure(&self, endpoint: Option<String>) {
+		self.requests.fetch_add(1, Ordering::Relaxed);
+
+		if let Some(endpoint) = endpoint {
+			self.record_endpoint(endpoint, false);
+		}
+	}
+
+	fn record_endpoint(&self, endpoint: String, rate_limited: bool) {
+		let mut endpoints = self.endpoints.lock().unwrap_or_else(|e| e.into_inner());
+		let counts = endpoints.entry(endpoint).or_default();
+		counts.requests += 1;
+
+		if rate_limited {
+			counts.rate_limited += 1;
+		}
+	}
+
+	pub fn snapshot(&self) -> ClientMetrics {
+		ClientMetrics {
+			requests: self.requests.load(Ordering::Relaxed),
+			rate_limited: self.rate_limited.load(Ordering::Relaxed),
+			endpoints: self.endpoints.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+		}
+	}
+}
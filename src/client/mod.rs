@@ -1,4 +1,17 @@
+mod batch;
+mod cache;
+mod coalesce;
+mod health;
+mod images;
+mod lookup;
+mod metrics;
 /// Later we can make a different client and implement it as a feature.
 mod reqwest;
+mod usage;
+mod wait;
 
-pub use self::reqwest::Client;
+pub use self::cache::{EtagCache, TtlCache};
+pub use self::coalesce::Coalescer;
+pub use self::images::ImageSlugs;
+pub use self::metrics::Counters;
+pub use self::reqwest::{build, Client};
@@ -1,32 +1,86 @@
 pub use reqwest::Client;
 
-use crate::api::{HasPagination, HasResponse, HasValue, MAX_PER_PAGE};
+use crate::api::{HasPagination, HasResponse, HasValue, Page, MAX_PER_PAGE};
 use crate::error::Error;
 use crate::method::{Create, Delete, Get, List, Update};
-use crate::request::Request;
-use crate::DigitalOcean;
-use log::info;
-use reqwest::StatusCode;
+use crate::request::{Precondition, Request};
+use crate::{DigitalOcean, ROOT_URL};
+use super::cache::Entry;
+use super::coalesce::Shared;
+use reqwest::header::{HeaderValue, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, ETAG, IF_NONE_MATCH};
+use reqwest::{Method, StatusCode};
 use reqwest::{RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io::Read;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use url::percent_encoding::percent_decode;
+use url::Url;
+
+/// Build the underlying HTTP client.
+pub fn build(gzip: bool) -> Result<Client, Error> {
+	Ok(Client::builder().gzip(gzip).build()?)
+}
 
 impl DigitalOcean {
 	pub(crate) fn get<V>(&self, request: Request<Get, V>) -> Result<V, Error>
 		where V: HasResponse {
-		info!("GET {:?}", request.url());
-		let req = self.client.get(request.url().clone());
+		client_log!(self, debug, "GET {:?}", request.url());
+		let limit = request.response_limit(self);
 
-		let mut response = self.fetch(req)?;
+		let fetch = || -> Result<Shared, Error> {
+			let req = self.prepare(Method::GET, request.url().clone(), &request);
+			let response = self.fetch(req, &request)?;
+			let status = response.status();
+			let path = response.url().path().to_string();
 
-		match response.status() {
+			let body = match status {
+				StatusCode::OK => read_body(response, limit)?,
+				StatusCode::NOT_FOUND => Vec::new(),
+				_ => error_page(response)
+			};
+			Ok(Shared { status, path, body: Arc::new(body) })
+		};
+
+		let key = self.cache_key(request.url(), &request);
+		let metrics_cache = self.metrics_cache.as_ref()
+			.filter(|_| request.url().path().contains("/monitoring/metrics/"));
+		let cached = metrics_cache.and_then(|cache| cache.get(&key));
+
+		let shared = match (cached, &self.coalescer) {
+			(Some(shared), _) => {
+				client_log!(self, trace, "Answered from the metrics cache.");
+				shared
+			},
+			(None, Some(coalescer)) => coalescer.run(key.clone(), fetch)?,
+			(None, None) => fetch()?
+		};
+
+		if let (Some(cache), StatusCode::OK) = (metrics_cache, shared.status) {
+			cache.insert(key, shared.clone());
+		}
+
+		match shared.status {
 			// Successes
 			StatusCode::OK => (),
 			// Not Found
-			StatusCode::NOT_FOUND => Err(Error::NotFound)?,
+			StatusCode::NOT_FOUND => Err(not_found(&request, true))?,
 			// Errors
-			e => Err(Error::UnexpectedStatus(e))?
+			e => Err(status_error(e, &shared.body))?
 		};
 
-		let deserialized: V::Response = response.json()?;
+		// The body may have been read for a caller with a higher limit.
+		if let Some(limit) = limit {
+			if shared.body.len() > limit {
+				return Err(Error::ResponseTooLarge { limit });
+			}
+		}
+
+		let deserialized: V::Response = parse(shared.status, shared.path, &shared.body)?;
 		Ok(deserialized.value())
 	}
 
@@ -34,37 +88,21 @@ impl DigitalOcean {
 		where
 			Vec<V>: HasResponse,
 			<Vec<V> as HasResponse>::Response: HasPagination {
-		info!("LIST {:?}", request.url());
+		client_log!(self, debug, "LIST {:?}", request.url());
+		let limit = request.method().0;
+		let body_limit = request.response_limit(self);
 		// This may be a paginated response. We need to buffer.
 		let mut buffer = Vec::new();
 		let mut current_url = request.url().clone();
 
-		match request.method().0 {
-			Some(limit) if limit < MAX_PER_PAGE => {
-				current_url
-					.query_pairs_mut()
-					.append_pair("per_page", &limit.to_string());
-			}
-			_ => {
-				current_url
-					.query_pairs_mut()
-					.append_pair("per_page", &MAX_PER_PAGE.to_string());
-			}
+		match limit {
+			Some(limit) if limit < MAX_PER_PAGE => set_per_page(&mut current_url, limit),
+			_ => set_per_page(&mut current_url, MAX_PER_PAGE)
 		};
 
 		loop {
-			let req = self.client.get(current_url.clone());
-			let mut response = self.fetch(req)?;
-
-			match response.status() {
-				StatusCode::OK => (),
-				// Not Found
-				StatusCode::NOT_FOUND => Err(Error::NotFound)?,
-				// Errors
-				e => Err(Error::UnexpectedStatus(e))?
-			};
-
-			let deserialized: <Vec<V> as HasResponse>::Response = response.json()?;
+			let deserialized: <Vec<V> as HasResponse>::Response =
+				self.fetch_page(current_url, &request, body_limit)?;
 
 			let next_page = deserialized.next_page();
 			buffer.extend(deserialized.value());
@@ -74,94 +112,561 @@ impl DigitalOcean {
 				None => break
 			};
 
-			if let Some(limit) = request.method().0 {
+			if let Some(limit) = limit {
 				let buffer_size = buffer.len();
-				let remaining = limit - buffer_size;
 
 				if buffer_size >= limit {
 					break;
-				} else if remaining < MAX_PER_PAGE {
-					current_url
-						.query_pairs_mut()
-						.append_pair("per_page", &remaining.to_string());
+				} else if limit - buffer_size < MAX_PER_PAGE {
+					set_per_page(&mut current_url, limit - buffer_size);
 				}
 			}
-			info!("Fetching next page...")
+			self.pause_between_pages();
+			client_log!(self, trace, "Fetching next page...");
 		}
 
 		Ok(buffer)
 	}
 
-	// Delete requests do not return content.
+	/// Fetch a single page of a listing and return the response as-is, so
+	/// that its [`cursor()`](api/trait.HasPagination.html#method.cursor) can
+	/// be saved and the listing continued later with
+	/// [`resume_from()`](request/struct.Request.html#method.resume_from).
+	pub fn list_page<V>(
+		&self,
+		request: Request<List, Vec<V>>,
+	) -> Result<<Vec<V> as HasResponse>::Response, Error>
+		where
+			Vec<V>: HasResponse,
+			<Vec<V> as HasResponse>::Response: HasPagination {
+		request.run(|request| {
+			client_log!(self, debug, "LIST PAGE {:?}", request.url());
+			let mut url = request.url().clone();
+
+			match request.method().0 {
+				Some(limit) if limit < MAX_PER_PAGE => set_per_page(&mut url, limit),
+				_ => set_per_page(&mut url, MAX_PER_PAGE)
+			};
+
+			self.fetch_page(url, &request, request.response_limit(self))
+		})
+	}
+
+	/// Fetch a single page of a listing, along with the total number of
+	/// values and the links to the pages either side of it, such as for
+	/// showing "20 of 340" in a UI.
+	///
+	/// ```rust,no_run
+	/// # use digitalocean::prelude::*;
+	/// # let client = DigitalOcean::new("token").unwrap();
+	/// let page = client.execute_page(Droplet::list().limit(Some(20))).unwrap();
+	/// println!("Showing {} of {:?}", page.items.len(), page.total);
+	/// ```
+	pub fn execute_page<V>(&self, request: Request<List, Vec<V>>) -> Result<Page<V>, Error>
+		where
+			Vec<V>: HasResponse,
+			<Vec<V> as HasResponse>::Response: HasPagination {
+		let response = self.list_page(request)?;
+
+		Ok(Page {
+			total: response.total(),
+			next: response.next_page(),
+			prev: response.prev_page(),
+			items: response.value(),
+		})
+	}
+
+	fn fetch_page<R, V>(
+		&self,
+		url: Url,
+		request: &Request<List, V>,
+		limit: Option<usize>,
+	) -> Result<R, Error>
+		where R: DeserializeOwned {
+		let key = self.cache_key(&url, request);
+		let cached = self.etag_cache.as_ref().and_then(|cache| cache.get(&key));
+		let mut req = self.prepare(Method::GET, url.clone(), request);
+
+		if let Some(ref entry) = cached {
+			req = req.header(IF_NONE_MATCH, entry.etag.as_str());
+		}
+
+		let response = self.fetch(req, request)?;
+
+		match response.status() {
+			StatusCode::OK => (),
+			// Unchanged since it was cached
+			StatusCode::NOT_MODIFIED if cached.is_some() => {
+				let entry = cached.expect("Only cached pages are requested conditionally.");
+				let path = response.url().path().to_string();
+				return parse(StatusCode::OK, path, &entry.body);
+			}
+			// Not Found
+			StatusCode::NOT_FOUND => Err(not_found(request, false))?,
+			// Errors
+			e => return Err(status_error(e, &error_page(response)))
+		};
+
+		let etag = response.headers().get(ETAG).and_then(|v| v.to_str().ok()).map(String::from);
+		match (&self.etag_cache, etag) {
+			(Some(cache), Some(etag)) => {
+				let status = response.status();
+				let path = response.url().path().to_string();
+				let body = read_body(response, limit)?;
+				let parsed = parse(status, path, &body)?;
+
+				cache.insert(Entry { key, etag, body });
+				Ok(parsed)
+			},
+			_ => deserialize(response, limit)
+		}
+	}
+
+	// Delete requests do not return content, but some take a body.
 	pub(crate) fn delete<V>(&self, request: Request<Delete, V>) -> Result<(), Error> {
-		info!("DELETE {:?}", request.url());
-		let req = self.client.delete(request.url().clone());
+		client_log!(self, debug, "DELETE {:?}", request.url());
+		if !request.preconditions().is_empty() {
+			self.check_preconditions(&request)?;
+		}
+
+		let mut req = self.prepare(Method::DELETE, request.url().clone(), &request);
 
-		let response = self.fetch(req)?;
+		if !request.body().is_null() {
+			req = req.json(request.body());
+		}
+
+		let response = self.fetch(req, &request)?;
 
 		match response.status() {
 			// Successes
 			StatusCode::NO_CONTENT => (), // Delete success
+			s if s.is_success() => (),
 			// Errors
-			e => Err(Error::UnexpectedStatus(e))?
+			e => return Err(status_error(e, &error_page(response)))
 		};
 
 		Ok(())
 	}
 
+	/// Fetch the resource a delete request points at and make sure it meets
+	/// the request's preconditions.
+	fn check_preconditions<V>(&self, request: &Request<Delete, V>) -> Result<(), Error> {
+		client_log!(self, trace, "Checking preconditions of {:?}", request.url());
+		let req = self.prepare(Method::GET, request.url().clone(), request);
+		let response = self.fetch(req, request)?;
+
+		match response.status() {
+			StatusCode::OK => (),
+			StatusCode::NOT_FOUND => return Err(Error::Precondition {
+				reason: format!("{} no longer exists", request.url().path()),
+			}),
+			e => return Err(status_error(e, &error_page(response)))
+		};
+
+		// Resources are wrapped under a single key, such as `{"domain": {...}}`.
+		let body: Value = deserialize(response, request.response_limit(self))?;
+		let resource = match body {
+			Value::Object(ref fields) if fields.len() == 1 => fields.values().next(),
+			_ => None
+		};
+
+		for precondition in request.preconditions() {
+			if let Precondition::Matches(ref field, ref expected) = *precondition {
+				let actual = resource.and_then(|r| r.get(field)).unwrap_or(&Value::Null);
+
+				if actual != expected {
+					return Err(Error::Precondition {
+						reason: format!("`{}` is {}, expected {}", field, actual, expected),
+					});
+				}
+			}
+		}
+		Ok(())
+	}
+
 	pub(crate) fn post<V>(&self, request: Request<Create, V>) -> Result<V, Error>
 		where V: HasResponse {
-		info!("POST {:?}", request.url());
-		let req = self
-			.client
-			.post(request.url().clone())
-			.json(&request.body().clone());
+		client_log!(self, debug, "POST {:?}", request.url());
+		if let Some(ref slugs) = self.image_slugs {
+			let creates_droplet = request.url().path().trim_end_matches('/').ends_with("/droplets");
+			if let (true, Some(image)) = (creates_droplet, request.body()["image"].as_str()) {
+				self.check_image_slug(slugs, image)?;
+			}
+		}
+		let req = self.prepare(Method::POST, request.url().clone(), &request);
+		let req = with_body(req, request.body());
 
-		let mut response = self.fetch(req)?;
+		let response = self.fetch(req, &request)?;
+		let limit = request.response_limit(self);
 
 		match response.status() {
 			// Successes
-			StatusCode::CREATED => (),  // Post Success
-			StatusCode::ACCEPTED => (), // Post Success (async)
+			StatusCode::CREATED => (),    // Post Success
+			StatusCode::ACCEPTED => (),   // Post Success (async)
+			StatusCode::OK => (),         // Post Success (no resource created)
+			StatusCode::NO_CONTENT => (), // Post Success (no content)
 			// Errors
-			StatusCode::UNPROCESSABLE_ENTITY => {
-				Err(Error::UnprocessableEntity(response.json()?))?
+			StatusCode::UNPROCESSABLE_ENTITY if is_json(&response) => {
+				return Err(Error::UnprocessableEntity(deserialize(response, limit)?));
 			}
-			e => Err(Error::UnexpectedStatus(e))?
+			e => return Err(status_error(e, &error_page(response)))
 		};
 
-		let deserialized: V::Response = response.json()?;
+		let deserialized: V::Response = deserialize(response, limit)?;
 		Ok(deserialized.value())
 	}
 
 	pub(crate) fn put<V>(&self, request: Request<Update, V>) -> Result<V, Error>
 		where V: HasResponse {
-		info!("PUT {:?}", request.url());
-		let req = self
-			.client
-			.put(request.url().clone())
-			.json(&request.body().clone());
+		client_log!(self, debug, "PUT {:?}", request.url());
+		let req = self.prepare(Method::PUT, request.url().clone(), &request);
+		let req = with_body(req, request.body());
 
-		let mut response = self.fetch(req)?;
+		let response = self.fetch(req, &request)?;
+		let limit = request.response_limit(self);
 
 		match response.status() {
 			// Successes
-			StatusCode::OK => (), // Update success
+			StatusCode::OK => (),         // Update success
+			StatusCode::NO_CONTENT => (), // Update success (no content)
 			// Errors
-			StatusCode::UNPROCESSABLE_ENTITY => {
-				Err(Error::UnprocessableEntity(response.json()?))?
+			StatusCode::UNPROCESSABLE_ENTITY if is_json(&response) => {
+				return Err(Error::UnprocessableEntity(deserialize(response, limit)?));
 			}
-			e => Err(Error::UnexpectedStatus(e))?
+			e => return Err(status_error(e, &error_page(response)))
 		};
 
-		let deserialized: V::Response = response.json()?;
+		let deserialized: V::Response = deserialize(response, limit)?;
 		Ok(deserialized.value())
 	}
 
-	fn fetch(&self, dispatch: RequestBuilder) -> Result<Response, Error> {
-		let response = dispatch.bearer_auth(self.token.clone()).send()?;
+	/// Wait for the configured page delay, plus a random part of the jitter.
+	fn pause_between_pages(&self) {
+		let (delay, jitter) = self.page_delay;
+
+		let jitter = if jitter > Duration::from_secs(0) {
+			// Hashers are randomly keyed, which is random enough to spread
+			// out clients without depending on a random number crate.
+			let random = RandomState::new().build_hasher().finish();
+			let nanos = jitter.as_nanos() as u64;
+			Duration::from_nanos(random % nanos.saturating_add(1))
+		} else {
+			jitter
+		};
+
+		let pause = delay + jitter;
+		if pause > Duration::from_secs(0) {
+			client_log!(self, trace, "Pausing {:?} before the next page", pause);
+			thread::sleep(pause);
+		}
+	}
+
+	/// Send a request and hand back the response without checking its status
+	/// or reading its body, so that it can be streamed or inspected directly.
+	///
+	/// The request is validated, authorized and sent to the client's root
+	/// like any other; only the handling of the response is skipped.
+	pub fn send_raw<M, V>(&self, request: Request<M, V>) -> Result<Response, Error>
+		where M: crate::method::Method {
+		request.run(|request| {
+			client_log!(self, debug, "{} {:?} (raw)", M::VERB, request.url());
+
+			let method = Method::from_bytes(M::VERB.as_bytes())
+				.expect("HTTP verbs are valid methods.");
+			// As in `post()` and `put()`, so that a body-less create or
+			// update still sends `Content-Length: 0`.
+			let sends_body = method == Method::POST || method == Method::PUT;
+			let mut req = self.prepare(method, request.url().clone(), &request);
+
+			if sends_body {
+				req = with_body(req, request.body());
+			} else if !request.body().is_null() {
+				req = req.json(request.body());
+			}
+
+			self.fetch(req, &request)
+		})
+	}
+
+	/// The key a response to `url` is shared or cached under. Clones with
+	/// their own key must not see each other's responses, and requests with
+	/// different headers, such as correlation ids, must each send their own.
+	fn cache_key<M, V>(&self, url: &Url, request: &Request<M, V>) -> String
+		where M: crate::method::Method {
+		let mut headers = request.headers().clone();
+		headers.sort();
+		format!("{:p} {} {} {:?}", Arc::as_ptr(&self.token), self.root, url, headers)
+	}
+
+	/// Start building a call to `url` on behalf of `request`, carrying over
+	/// the headers set on it. Anything the client sets afterwards takes
+	/// precedence over them.
+	fn prepare<M, V>(&self, method: Method, url: Url, request: &Request<M, V>) -> RequestBuilder
+		where M: crate::method::Method {
+		let url = if request.is_absolute() { url } else { self.rebase(url) };
+		let mut req = self.client.request(method, url);
+
+		for (name, value) in request.headers() {
+			req = req.header(name.as_str(), value.as_str());
+		}
+		req
+	}
+
+	/// Point a URL under the default API root at the client's root instead.
+	/// URLs elsewhere are left alone.
+	fn rebase(&self, url: Url) -> Url {
+		if self.root == *ROOT_URL {
+			return url;
+		}
+
+		let rest = match url.as_str().strip_prefix(ROOT_URL.as_str()) {
+			Some(rest) if rest.is_empty() || rest.starts_with('/') || rest.starts_with('?') => rest,
+			_ => return url
+		};
+
+		let rebased = format!("{}{}", self.root.as_str().trim_end_matches('/'), rest);
+		Url::parse(&rebased).unwrap_or(url)
+	}
+
+	/// Send a call with the API key attached. The key is only sent to the
+	/// API host or the client's root, so a request for any other host, such
+	/// as an absolute URL or a tampered cursor, fails without being sent.
+	fn fetch<M, V>(&self, dispatch: RequestBuilder, request: &Request<M, V>) -> Result<Response, Error>
+		where M: crate::method::Method {
+		let mut req = dispatch.build()?;
+
+		let origin = req.url().origin();
+		if origin != ROOT_URL.origin() && origin != self.root.origin() {
+			return Err(Error::InvalidArgument {
+				field: "url".to_string(),
+				reason: format!(
+					"`{}` is not on the API host or the client's root, so the key is not sent to it",
+					origin.ascii_serialization()
+				),
+			});
+		}
+
+		let authorization = HeaderValue::from_str(&format!("Bearer {}", self.token.token()?))
+			.map_err(|_| Error::InvalidArgument {
+				field: "key".to_string(),
+				reason: "is not a valid header value".to_string(),
+			})?;
+		req.headers_mut().insert(AUTHORIZATION, authorization);
+
+		let endpoint = match self.endpoint_metrics {
+			true => Some(format!("{} {}", req.method(), request.describe().path)),
+			false => None
+		};
+		let response = match self.client.execute(req) {
+			Ok(response) => response,
+			Err(e) => {
+				self.counters.record_failure(endpoint);
+				return Err(e.into());
+			}
+		};
 
-		info!("Response status: {:?}", response.status());
+		self.counters.record(response.status(), endpoint);
+		client_log!(self, trace, "Response status: {:?}", response.status());
 		Ok(response)
 	}
 }
+
+/// Deserialize the body of a successful response. A response without content,
+/// such as a `204 No Content`, is read as `null` (which is what `()` expects)
+/// rather than handed to the JSON parser as an empty document.
+///
+/// Failures are reported with the path, status and a redacted snippet of the
+/// body, since serde's own errors do not say what was being read.
+fn deserialize<R: DeserializeOwned>(response: Response, limit: Option<usize>) -> Result<R, Error> {
+	let status = response.status();
+	let path = response.url().path().to_string();
+	let body = read_body(response, limit)?;
+
+	parse(status, path, &body)
+}
+
+/// Deserialize a body which has already been read.
+fn parse<R: DeserializeOwned>(status: StatusCode, path: String, body: &[u8]) -> Result<R, Error> {
+	let result = if status == StatusCode::NO_CONTENT || body.iter().all(u8::is_ascii_whitespace) {
+		serde_json::from_value(Value::Null)
+	} else {
+		serde_json::from_slice(body)
+	};
+
+	result.map_err(|source| Error::Deserialize {
+		path,
+		status,
+		snippet: snippet(body),
+		source,
+	})
+}
+
+/// Whether a response says its body is JSON. One which does not say is
+/// assumed to be.
+fn is_json(response: &Response) -> bool {
+	match response.headers().get(CONTENT_TYPE).and_then(|v| v.to_str().ok()) {
+		Some(content_type) => content_type.to_lowercase().contains("json"),
+		None => true
+	}
+}
+
+/// The start of the body of an error response which is not JSON, such as an
+/// HTML page from a proxy in front of the API, or nothing for JSON bodies.
+fn error_page(response: Response) -> Vec<u8> {
+	let mut body = Vec::new();
+
+	if !is_json(&response) {
+		// One byte past the snippet, so that it is marked as truncated.
+		let _ = response.take(SNIPPET_BYTES as u64 + 1).read_to_end(&mut body);
+	}
+	body
+}
+
+/// Name what was not found from the path of the request. A single
+/// resource's path ends with its kind and id, such as `droplets/3164444`,
+/// unless it is a singular resource such as `account`. A listing's path ends
+/// with its kind.
+fn not_found<M: crate::method::Method, V>(request: &Request<M, V>, single: bool) -> Error {
+	let mut path = request.url().path();
+	if !request.is_absolute() {
+		path = path.strip_prefix(ROOT_URL.path()).unwrap_or(path);
+	}
+	let mut segments = path.split('/')
+		.filter(|segment| !segment.is_empty())
+		.map(|segment| percent_decode(segment.as_bytes()).decode_utf8_lossy().into_owned());
+
+	let last = segments.next_back().unwrap_or_default();
+	match segments.next_back() {
+		Some(resource) if single => Error::NotFound { resource, id: Some(last) },
+		_ => Error::NotFound { resource: last, id: None }
+	}
+}
+
+/// The error for an unexpected status, with the text of any error page as
+/// its message.
+fn status_error(status: StatusCode, page: &[u8]) -> Error {
+	if page.iter().all(u8::is_ascii_whitespace) {
+		Error::UnexpectedStatus(status)
+	} else {
+		Error::ErrorPage {
+			status,
+			message: snippet(page),
+		}
+	}
+}
+
+// Enough of a body to recognise it without flooding logs.
+const SNIPPET_BYTES: usize = 512;
+
+// Keys whose string values are replaced in snippets.
+const SECRET_KEYS: &[&str] = &["password", "secret", "token", "private_key", "user_data"];
+
+/// The start of a body for error messages, with secrets redacted first so
+/// that truncation can never reveal part of one.
+fn snippet(body: &[u8]) -> String {
+	let mut snippet = redact(&String::from_utf8_lossy(body));
+
+	if snippet.len() > SNIPPET_BYTES {
+		let mut end = SNIPPET_BYTES;
+		while !snippet.is_char_boundary(end) {
+			end -= 1;
+		}
+		snippet.truncate(end);
+		snippet.push_str("...");
+	}
+	snippet
+}
+
+/// Replace the string value of any JSON key which looks like it holds a
+/// secret. This works on malformed JSON too, which is when it is needed.
+fn redact(body: &str) -> String {
+	let mut redacted = String::with_capacity(body.len());
+	let mut rest = body;
+	let mut secret = false;
+
+	while let Some(start) = rest.find('"') {
+		let (before, string) = rest.split_at(start);
+		let (literal, after) = string.split_at(string_end(string));
+		redacted.push_str(before);
+
+		if secret && before.trim() == ":" {
+			redacted.push_str("\"[REDACTED]\"");
+		} else {
+			redacted.push_str(literal);
+		}
+
+		let key = literal.to_lowercase();
+		secret = after.trim_start().starts_with(':') && SECRET_KEYS.iter().any(|k| key.contains(k));
+		rest = after;
+	}
+
+	redacted.push_str(rest);
+	redacted
+}
+
+/// The length of the JSON string literal at the start of `s`, including its
+/// quotes, or all of `s` if the literal is unterminated.
+fn string_end(s: &str) -> usize {
+	let mut escaped = false;
+
+	for (i, c) in s.char_indices().skip(1) {
+		match c {
+			_ if escaped => escaped = false,
+			'\\' => escaped = true,
+			'"' => return i + 1,
+			_ => ()
+		}
+	}
+	s.len()
+}
+
+/// Read the whole body of a response, giving up as soon as it grows past
+/// `limit` bytes rather than buffering all of it.
+fn read_body(mut response: Response, limit: Option<usize>) -> Result<Vec<u8>, Error> {
+	let mut body = Vec::new();
+
+	match limit {
+		Some(limit) => {
+			if let Some(length) = response.content_length() {
+				if length > limit as u64 {
+					return Err(Error::ResponseTooLarge { limit });
+				}
+			}
+
+			(&mut response).take(limit as u64 + 1).read_to_end(&mut body)?;
+			if body.len() > limit {
+				return Err(Error::ResponseTooLarge { limit });
+			}
+		},
+		None => {
+			response.copy_to(&mut body)?;
+		}
+	}
+
+	Ok(body)
+}
+
+/// Attach `body` as JSON. Requests without a body, such as most actions,
+/// are sent empty with `Content-Length: 0` rather than as a JSON `null`.
+fn with_body(req: RequestBuilder, body: &Value) -> RequestBuilder {
+	if body.is_null() {
+		req.header(CONTENT_LENGTH, 0).body(Vec::new())
+	} else {
+		req.json(body)
+	}
+}
+
+/// Set the `per_page` query parameter, replacing any value already present
+/// (such as in a `next` link returned by the API).
+fn set_per_page(url: &mut Url, per_page: usize) {
+	let pairs: Vec<(String, String)> = url
+		.query_pairs()
+		.filter(|(key, _)| key != "per_page")
+		.map(|(key, value)| (key.into_owned(), value.into_owned()))
+		.collect();
+
+	url.query_pairs_mut()
+		.clear()
+		.extend_pairs(pairs)
+		.append_pair("per_page", &per_page.to_string());
+}
@@ -0,0 +1,47 @@
+use crate::api::monitoring_fields::{Direction, Interface, Transfer};
+use crate::api::Metrics;
+use crate::error::Error;
+use crate::request::Executable;
+use crate::DigitalOcean;
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+
+impl DigitalOcean {
+	/// Total the public outbound traffic of a Droplet between `start` and
+	/// `end`, which is what counts towards its transfer allowance. Monitoring
+	/// must be enabled on the Droplet.
+	///
+	/// Fails with `Error::InvalidArgument` if `end` is not after `start`.
+	pub fn droplet_transfer(
+		&self,
+		droplet_id: usize,
+		start: DateTime<Utc>,
+		end: DateTime<Utc>,
+	) -> Result<Transfer, Error> {
+		let metrics = Metrics::droplet_bandwidth(
+			droplet_id,
+			Interface::Public,
+			Direction::Outbound,
+			start,
+			end,
+		).execute(self)?;
+		let (bytes, samples) = metrics.transfer_bytes();
+
+		Ok(Transfer {
+			start,
+			end,
+			bytes,
+			samples,
+		})
+	}
+
+	/// Total the public outbound traffic of a Droplet since the start of the
+	/// current billing month, which is the calendar month in UTC.
+	pub fn droplet_transfer_this_month(&self, droplet_id: usize) -> Result<Transfer, Error> {
+		let end = Utc::now();
+		let start = Utc.with_ymd_and_hms(end.year(), end.month(), 1, 0, 0, 0)
+			.single()
+			.expect("the first of the month is a valid date");
+
+		self.droplet_transfer(droplet_id, start, end)
+	}
+}
@@ -0,0 +1,198 @@
+use crate::api::{Action, Certificate, Droplet, FloatingIp, HasValue, Raw, Snapshot};
+use crate::error::Error;
+use crate::method::Create;
+use crate::request::{DropletRequest, Executable};
+use crate::DigitalOcean;
+use serde::Serialize;
+use std::fmt::Display;
+use std::iter;
+use std::net::IpAddr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+impl DigitalOcean {
+	/// Poll an action until it completes, checking every
+	/// [`poll_interval()`](struct.DigitalOcean.html#method.poll_interval).
+	///
+	/// Fails with `Error::ActionErrored` if the action errors, or
+	/// `Error::Timeout` if it has not finished within `timeout`.
+	pub fn wait_for_action(&self, id: usize, timeout: Duration) -> Result<Action, Error> {
+		let deadline = Instant::now() + timeout;
+
+		loop {
+			let action = Action::get(id).execute(self)?;
+			client_log!(self, debug, "Action {} is {}", id, action.status());
+
+			match action.status().as_str() {
+				"completed" => return Ok(action),
+				"errored" => return Err(Error::ActionErrored { id }),
+				_ => ()
+			}
+
+			let now = Instant::now();
+			if now >= deadline {
+				return Err(Error::Timeout);
+			}
+			thread::sleep(self.poll_interval().min(deadline - now));
+		}
+	}
+
+	/// Create a Droplet and wait for it to finish being created, then fetch it
+	/// again so that it is returned `active` and with its networks populated.
+	///
+	/// ```rust,no_run
+	/// # use digitalocean::prelude::*;
+	/// # use std::time::Duration;
+	/// # let client = DigitalOcean::new("token").unwrap();
+	/// let droplet = client.create_droplet_and_wait(
+	///     Droplet::create("bear", "tor1", "s-1vcpu-1gb", "ubuntu-18-04-x64"),
+	///     Duration::from_secs(300),
+	/// );
+	/// ```
+	pub fn create_droplet_and_wait(
+		&self,
+		request: DropletRequest<Create, Droplet>,
+		timeout: Duration,
+	) -> Result<Droplet, Error> {
+		let deadline = Instant::now() + timeout;
+		let Raw(response) = request.full_response().execute(self)?;
+
+		let action = response.create_action_id();
+		let id = *response.value().id();
+
+		match action {
+			Some(action) => {
+				self.wait_for_action(action, deadline.saturating_duration_since(Instant::now()))?;
+				Droplet::get(id).execute(self)
+			},
+			// Without a create action to follow, watch the Droplet itself.
+			None => loop {
+				let droplet = Droplet::get(id).execute(self)?;
+				if !droplet.status().is_new() {
+					return Ok(droplet);
+				}
+
+				let now = Instant::now();
+				if now >= deadline {
+					return Err(Error::Timeout);
+				}
+				thread::sleep(self.poll_interval().min(deadline - now));
+			}
+		}
+	}
+
+	/// Assign a Floating IP to a Droplet and wait for the assignment to take
+	/// effect, then fetch the Floating IP again so that its `droplet` is
+	/// populated.
+	///
+	/// Fails with `Error::ActionErrored` if the assignment errors, or
+	/// `Error::Timeout` if it has not finished within `timeout`.
+	pub fn assign_floating_ip_and_wait<I: Into<IpAddr>>(
+		&self,
+		ip: I,
+		droplet_id: usize,
+		timeout: Duration,
+	) -> Result<FloatingIp, Error> {
+		let ip = ip.into();
+		let action = FloatingIp::get(ip).assign(droplet_id).execute(self)?;
+
+		self.wait_for_action(*action.id(), timeout)?;
+		FloatingIp::get(ip).execute(self)
+	}
+
+	/// Snapshot a Droplet and wait for the snapshot action to finish, then
+	/// find the new snapshot among the Droplet's snapshots. If several share
+	/// `name`, the most recently created one is returned.
+	///
+	/// Fails with `Error::ActionErrored` if the snapshot action errors,
+	/// `Error::Timeout` if it has not finished within `timeout`, or
+	/// `Error::NotFound` if no snapshot named `name` exists afterwards.
+	///
+	/// ```rust,no_run
+	/// # use digitalocean::prelude::*;
+	/// # use std::time::Duration;
+	/// # let client = DigitalOcean::new("token").unwrap();
+	/// let snapshot = client.snapshot_droplet_and_wait(
+	///     3164444,
+	///     "before-upgrade",
+	///     Duration::from_secs(600),
+	/// );
+	/// ```
+	pub fn snapshot_droplet_and_wait<S>(
+		&self,
+		droplet_id: usize,
+		name: S,
+		timeout: Duration,
+	) -> Result<Snapshot, Error>
+		where
+			S: AsRef<str> + Serialize + Display,
+	{
+		let action = Droplet::get(droplet_id).snapshot(&name).execute(self)?;
+		self.wait_for_action(*action.id(), timeout)?;
+
+		Droplet::get(droplet_id)
+			.snapshots()
+			.execute(self)?
+			.into_iter()
+			.filter(|snapshot| snapshot.name() == name.as_ref())
+			.max_by_key(|snapshot| *snapshot.created_at())
+			.ok_or_else(|| Error::NotFound {
+				resource: "snapshots".to_string(),
+				id: Some(name.to_string()),
+			})
+	}
+
+	/// Have Let's Encrypt issue a certificate for `domain` and each of its
+	/// `subdomains`, such as `["www"]` for `www.example.com`, then wait for it
+	/// to be issued. `domain` must be managed by DigitalOcean DNS.
+	///
+	/// Fails with `Error::CertificateErrored` if it cannot be issued, or
+	/// `Error::Timeout` if it is still pending after `timeout`.
+	///
+	/// ```rust,no_run
+	/// # use digitalocean::prelude::*;
+	/// # use std::time::Duration;
+	/// # let client = DigitalOcean::new("token").unwrap();
+	/// let certificate = client.lets_encrypt_and_wait(
+	///     "web",
+	///     "example.com",
+	///     &["www"],
+	///     Duration::from_secs(600),
+	/// );
+	/// ```
+	pub fn lets_encrypt_and_wait<S: AsRef<str>>(
+		&self,
+		name: &str,
+		domain: &str,
+		subdomains: &[S],
+		timeout: Duration,
+	) -> Result<Certificate, Error> {
+		let deadline = Instant::now() + timeout;
+		let dns_names = iter::once(domain.to_string())
+			.chain(subdomains.iter().map(|sub| format!("{}.{}", sub.as_ref(), domain)))
+			.collect();
+
+		let mut certificate = Certificate::create_lets_encrypt(name, dns_names).execute(self)?;
+
+		loop {
+			client_log!(self, debug, "Certificate {} is {}", certificate.id(), certificate.state());
+
+			match certificate.state().as_str() {
+				"verified" => return Ok(certificate),
+				"error" => return Err(Error::CertificateErrored {
+					id: certificate.id().clone(),
+					dns_names: certificate.dns_names().clone(),
+				}),
+				_ => ()
+			}
+
+			let now = Instant::now();
+			if now >= deadline {
+				return Err(Error::Timeout);
+			}
+			thread::sleep(self.poll_interval().min(deadline - now));
+
+			certificate = Certificate::get(certificate.id()).execute(self)?;
+		}
+	}
+}
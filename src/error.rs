@@ -11,9 +11,15 @@ use serde_json;
 /// Errors which have crate specific meanings.
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-	/// The item does not exist or otherwise cannot be found.
-	#[error("Not Found")]
-	NotFound,
+	/// The item does not exist or otherwise cannot be found. `resource` is
+	/// the kind of item, as it appears in the path, such as `droplets`, and
+	/// `id` is the item's id, name or IP address. `id` is `None` when a whole
+	/// listing or a singular resource such as `account` was not found.
+	#[error("Not Found: {resource}{}", .id.as_ref().map_or(String::new(), |id| format!(" `{}`", id)))]
+	NotFound {
+		resource: String,
+		id: Option<String>
+	},
 
 	/// The reqest's API key is invalid or not authorized to view this resource.
 	#[error("Unauthorized")]
@@ -23,13 +29,139 @@ pub enum Error {
 	#[error("Unexpected status code: {0}")]
 	UnexpectedStatus(reqwest::StatusCode),
 
+	/// An unexpected status code was returned along with a body which is not
+	/// JSON, such as an HTML error page from a proxy in front of the API.
+	/// `message` is the start of the body.
+	#[error("Unexpected status code: {status}: {message}")]
+	ErrorPage {
+		status: reqwest::StatusCode,
+		message: String
+	},
+
 	/// The item exists (possibly on another account), the limit on this item has been reached,
 	/// or this request is otherwise unprocessable.
 	#[error("Unprocessable entity: {0}")]
 	UnprocessableEntity(serde_json::Value),
 
+	/// An argument given while building the request is invalid. This is caught
+	/// before the request is sent. The functions which validate their
+	/// arguments say so in their documentation.
+	#[error("Invalid argument `{field}`: {reason}")]
+	InvalidArgument {
+		field: String,
+		reason: String
+	},
+
+	/// An action finished with the status "errored".
+	#[error("Action {id} errored")]
+	ActionErrored {
+		id: usize
+	},
+
+	/// Let's Encrypt could not issue a certificate, usually because one of
+	/// its domains is not managed by DigitalOcean DNS.
+	#[error("Certificate {id} for {dns_names:?} could not be issued")]
+	CertificateErrored {
+		id: String,
+		dns_names: Vec<String>
+	},
+
+	/// More than one resource has the name being looked up.
+	#[error("{count} resources are named `{name}`")]
+	AmbiguousName {
+		name: String,
+		count: usize
+	},
+
+	/// A check made before deleting a resource failed, so it was not deleted.
+	#[error("Precondition failed: {reason}")]
+	Precondition {
+		reason: String
+	},
+
+	/// Waiting for an action to finish took longer than allowed.
+	#[error("Timed out waiting for an action to finish")]
+	Timeout,
+
+	/// The response body was larger than the configured limit. See
+	/// [`set_max_response_bytes()`](../struct.DigitalOcean.html#method.set_max_response_bytes).
+	#[error("Response body exceeded the limit of {limit} bytes")]
+	ResponseTooLarge {
+		limit: usize
+	},
+
+	/// The response body could not be read.
+	#[error("{0}")]
+	IoError(#[from] std::io::Error),
+
+	/// A response body could not be deserialized. `snippet` is the start of
+	/// the body, with anything that looks like a secret redacted.
+	#[error("Could not deserialize response from {path} ({status}): {source}; body: {snippet}")]
+	Deserialize {
+		path: String,
+		status: reqwest::StatusCode,
+		snippet: String,
+		#[source]
+		source: serde_json::Error
+	},
+
+	/// A value could not be converted to or from JSON.
+	#[error("{0}")]
+	JsonError(#[from] serde_json::Error),
+
+	/// An error from a request which was given a
+	/// [`correlation_id()`](../request/struct.Request.html#method.correlation_id).
+	#[error("{source} (correlation id {correlation_id})")]
+	Correlated {
+		correlation_id: String,
+		#[source]
+		source: Box<Error>
+	},
+
 	/// There was a miscellaneous error processing the request. Please look at the documentation of
 	/// `reqwest` to learn more about how to handle these errors.
 	#[error("{0}")]
 	ReqwestError(#[from] reqwest::Error),
 }
+
+impl Error {
+	/// Whether sending the same request again might succeed. This is true for
+	/// server errors, `429 Too Many Requests` and network failures, and false
+	/// for other errors, such as a `404` or a response which could not be
+	/// deserialized.
+	pub fn is_retryable(&self) -> bool {
+		match *self {
+			Error::UnexpectedStatus(status) | Error::ErrorPage { status, .. } => {
+				status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+			},
+			Error::ReqwestError(ref e) => match e.status() {
+				Some(status) => status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS,
+				// Connection failures surface as HTTP errors without a status.
+				None => e.is_timeout() || e.is_http()
+			},
+			Error::IoError(_) => true,
+			Error::Correlated { ref source, .. } => source.is_retryable(),
+			_ => false
+		}
+	}
+
+	/// The correlation id of the request which failed, if it was given one.
+	pub fn correlation_id(&self) -> Option<&str> {
+		match *self {
+			Error::Correlated { ref correlation_id, .. } => Some(correlation_id),
+			_ => None
+		}
+	}
+
+	/// Wrap the error with the correlation id of the request which caused
+	/// it, if there is one.
+	pub(crate) fn correlated(self, correlation_id: Option<String>) -> Self {
+		match correlation_id {
+			Some(correlation_id) => Error::Correlated {
+				correlation_id,
+				source: Box::new(self),
+			},
+			None => self
+		}
+	}
+}
@@ -65,7 +65,9 @@ The crate is founded on a few design considerations:
 * Use static dispatch as much as possible.
 * Only the bare minimum amount of information should be carried around.
 * Allow for easy construction of separate clients (`hyper`, etc.)
-* No caching (yet). (DigitalOcean does not have [ETags](https://en.wikipedia.org/wiki/HTTP_ETag))
+* No caching unless asked for. List pages can be cached by their
+  [ETag](https://en.wikipedia.org/wiki/HTTP_ETag) with
+  [`set_etag_cache()`](struct.DigitalOcean.html#method.set_etag_cache).
 
 ## Debugging
 
@@ -75,6 +77,11 @@ This crate uses the [`log`](https://doc.rust-lang.org/log/log/index.html) crate.
 RUST_LOG=digitalocean=debug cargo run
 ```
 
+Requests are logged at `debug` and routine bookkeeping at `trace`. Only
+methods, URLs and statuses are logged, never tokens or request bodies. A
+client's logs can be silenced entirely with
+[`set_logging(false)`](struct.DigitalOcean.html#method.set_logging).
+
 ## Development Status
 
 This crate is in a prototype state.
@@ -90,26 +97,53 @@ Please just open an issue or PR!
 */
 
 use lazy_static::lazy_static;
-use log::info;
 
 #[macro_use]
 extern crate serde_derive;
 #[macro_use]
 extern crate serde_json;
 
+/// Log through `log` unless the client has been silenced with
+/// `set_logging(false)`.
+macro_rules! client_log {
+	($client:expr, $level:ident, $($arg:tt)+) => {
+		if $client.logging() {
+			log::$level!($($arg)+);
+		}
+	};
+}
+
 pub mod api;
 mod client;
 pub mod error;
 pub mod method;
 pub mod prelude;
 pub mod request;
+pub mod token;
+mod validation;
 
-use crate::api::HasResponse;
+use crate::api::{HasResponse, Raw};
 use crate::method::Method;
 use crate::request::{Executable, Request};
 use crate::error::Error;
+use crate::token::TokenProvider;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
 use url::Url;
 
+/// The default limit on the size of a response body, 16 MiB.
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 16 * 1024 * 1024;
+
+/// The default interval between polls while waiting for an action, 5 seconds.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The most metric queries remembered by the cache enabled with
+/// [`DigitalOcean::set_metrics_cache()`](struct.DigitalOcean.html#method.set_metrics_cache).
+pub const METRICS_CACHE_CAPACITY: usize = 64;
+
 const STATIC_URL_ERROR: &str = "Staticly constructed DigitalOcean URL is malformed.";
 
 lazy_static! {
@@ -118,24 +152,423 @@ lazy_static! {
 }
 
 /// A DigitalOcean Client that holds an API key.
+///
+/// Cloning a client is cheap. Clones share the underlying connection pool
+/// but keep their own settings, so a clone can be given a different key with
+/// [`set_key()`](#method.set_key) without affecting the original.
 #[derive(Clone)]
 pub struct DigitalOcean {
 	client: client::Client,
-	token: String
+	token: Arc<dyn TokenProvider>,
+	root: Url,
+	gzip: bool,
+	max_response_bytes: Option<usize>,
+	poll_interval: Duration,
+	page_delay: (Duration, Duration),
+	etag_cache: Option<Arc<client::EtagCache>>,
+	coalescer: Option<Arc<client::Coalescer>>,
+	metrics_cache: Option<Arc<client::TtlCache>>,
+	counters: Arc<client::Counters>,
+	image_slugs: Option<Arc<client::ImageSlugs>>,
+	endpoint_metrics: bool,
+	logging: bool
 }
 
 impl DigitalOcean {
-	/// Create a DigitalOcean client with the given API key.
+	/// Create a DigitalOcean client with the given API key. Whitespace around
+	/// the key is trimmed, but the key is not otherwise checked. See
+	/// [`try_new()`](#method.try_new).
 	pub fn new<T: Into<String>>(token: T) -> Result<Self, Error> {
-		info!("Created.");
 		Ok(DigitalOcean {
-			client: client::Client::new(),
-			token: token.into(),
+			client: client::build(true)?,
+			token: Arc::new(token.into().trim().to_string()),
+			root: ROOT_URL.clone(),
+			gzip: true,
+			max_response_bytes: Some(DEFAULT_MAX_RESPONSE_BYTES),
+			poll_interval: DEFAULT_POLL_INTERVAL,
+			page_delay: (Duration::from_secs(0), Duration::from_secs(0)),
+			etag_cache: None,
+			coalescer: None,
+			metrics_cache: None,
+			counters: Arc::new(client::Counters::default()),
+			image_slugs: None,
+			endpoint_metrics: false,
+			logging: true,
 		})
 	}
 
+	/// Create a DigitalOcean client with the given API key, which must not be
+	/// empty or contain whitespace. A bad key is reported as
+	/// `Error::InvalidArgument` here rather than failing the first request.
+	pub fn try_new<T: Into<String>>(token: T) -> Result<Self, Error> {
+		let token = token.into();
+
+		if let Err(reason) = validation::api_key(&token) {
+			return Err(Error::InvalidArgument {
+				field: "key".to_string(),
+				reason,
+			});
+		}
+
+		DigitalOcean::new(token)
+	}
+
+	/// Use a different API key for requests made with this client.
+	/// Whitespace around the key is trimmed, as in [`new()`](#method.new).
+	pub fn set_key<T: Into<String>>(&mut self, token: T) {
+		self.token = Arc::new(token.into().trim().to_string());
+	}
+
+	/// Use a different API key for requests made with this client, checking
+	/// it as [`try_new()`](#method.try_new) does. A bad key is reported as
+	/// `Error::InvalidArgument` and the current key is kept.
+	pub fn try_set_key<T: Into<String>>(&mut self, token: T) -> Result<(), Error> {
+		let token = token.into();
+
+		if let Err(reason) = validation::api_key(&token) {
+			return Err(Error::InvalidArgument {
+				field: "key".to_string(),
+				reason,
+			});
+		}
+
+		self.set_key(token);
+		Ok(())
+	}
+
+	/// Create a DigitalOcean client which asks `provider` for the token to
+	/// use before each request, such as one which refreshes OAuth tokens.
+	pub fn with_token_provider<P: TokenProvider + 'static>(provider: P) -> Result<Self, Error> {
+		let mut client = DigitalOcean::new(String::new())?;
+		client.set_token_provider(provider);
+		Ok(client)
+	}
+
+	/// Ask `provider` for the token to use before each request made with
+	/// this client.
+	pub fn set_token_provider<P: TokenProvider + 'static>(&mut self, provider: P) {
+		self.token = Arc::new(provider);
+	}
+
+	/// Send requests to a different API root, such as a proxy or a mock
+	/// server, instead of `https://api.digitalocean.com/v2`.
+	///
+	/// Requests are still built against the default root and are rewritten
+	/// when they are sent, so this applies to every request executed with
+	/// this client, including pagination links returned by the API.
+	pub fn with_root(mut self, root: Url) -> Self {
+		self.root = root;
+		self
+	}
+
+	/// Send requests to a different API root, like
+	/// [`with_root()`](#method.with_root), but normalize it to end in exactly
+	/// one slash first so that `.../v2` and `.../v2/` behave the same.
+	///
+	/// Fails with `Error::InvalidArgument` if `root` has a query or fragment.
+	pub fn set_root(&mut self, mut root: Url) -> Result<(), Error> {
+		if let Err(reason) = validation::api_root(&root) {
+			return Err(Error::InvalidArgument {
+				field: "root".to_string(),
+				reason,
+			});
+		}
+
+		let path = format!("{}/", root.path().trim_end_matches('/'));
+		root.set_path(&path);
+		self.root = root;
+		Ok(())
+	}
+
+	/// The API root requests are sent to.
+	pub fn root(&self) -> &Url {
+		&self.root
+	}
+
+	/// Whether responses are requested with `Accept-Encoding: gzip` and
+	/// transparently decompressed. Enabled by default.
+	pub fn gzip(&self) -> bool {
+		self.gzip
+	}
+
+	/// Enable or disable gzip compression of responses. Disabling this can
+	/// help when running behind a proxy which mangles `Content-Encoding`.
+	///
+	/// Only gzip is negotiated. The HTTP client this crate uses cannot
+	/// decompress `deflate`, so it is never requested.
+	pub fn set_gzip(&mut self, enabled: bool) -> Result<(), Error> {
+		self.client = client::build(enabled)?;
+		self.gzip = enabled;
+		Ok(())
+	}
+
+	/// The largest response body which will be read, in bytes. `None` means
+	/// there is no limit. Defaults to [`DEFAULT_MAX_RESPONSE_BYTES`](constant.DEFAULT_MAX_RESPONSE_BYTES.html).
+	pub fn max_response_bytes(&self) -> Option<usize> {
+		self.max_response_bytes
+	}
+
+	/// Limit the size of response bodies. Reading a larger body fails with
+	/// `Error::ResponseTooLarge` instead of buffering it into memory.
+	///
+	/// Individual requests can override this with
+	/// [`Request::max_response_bytes()`](request/struct.Request.html#method.max_response_bytes).
+	pub fn set_max_response_bytes(&mut self, limit: Option<usize>) {
+		self.max_response_bytes = limit;
+	}
+
+	/// The pause between fetching pages of a listing, and the most random
+	/// extra time added to it. Both are zero by default.
+	pub fn page_delay(&self) -> (Duration, Duration) {
+		self.page_delay
+	}
+
+	/// Pause for `delay` plus up to `jitter` before fetching each page of a
+	/// listing after the first, to spread out requests for large listings.
+	pub fn set_page_delay(&mut self, delay: Duration, jitter: Duration) {
+		self.page_delay = (delay, jitter);
+	}
+
+	/// How many list pages are remembered for conditional requests, or
+	/// `None` if the ETag cache is disabled. Disabled by default.
+	pub fn etag_cache(&self) -> Option<usize> {
+		self.etag_cache.as_ref().map(|cache| cache.capacity())
+	}
+
+	/// Remember up to `capacity` list pages by their `ETag`. Fetching a page
+	/// again sends `If-None-Match`, and a `304 Not Modified` is answered from
+	/// the cache. `None` disables the cache.
+	///
+	/// Setting this starts a new, empty cache. It is shared with clones made
+	/// afterwards.
+	pub fn set_etag_cache(&mut self, capacity: Option<usize>) {
+		self.etag_cache = capacity.map(|capacity| Arc::new(client::EtagCache::new(capacity)));
+	}
+
+	/// Whether concurrent identical `GET`s share one request. Disabled by
+	/// default.
+	pub fn coalescing(&self) -> bool {
+		self.coalescer.is_some()
+	}
+
+	/// Let concurrent `GET`s of the same URL with the same key and headers
+	/// share one request and its response, rather than each sending their
+	/// own. Only requests for single resources are shared, not listings.
+	///
+	/// Enabling this is shared with clones made afterwards.
+	pub fn set_coalescing(&mut self, enabled: bool) {
+		self.coalescer = if enabled { Some(Arc::new(client::Coalescer::new())) } else { None };
+	}
+
+	/// How long monitoring metric queries are remembered, or `None` if the
+	/// metrics cache is disabled. Disabled by default.
+	pub fn metrics_cache(&self) -> Option<Duration> {
+		self.metrics_cache.as_ref().map(|cache| cache.ttl())
+	}
+
+	/// Remember the results of monitoring metric queries, such as
+	/// [`Metrics::droplet_bandwidth()`](api/struct.Metrics.html#method.droplet_bandwidth),
+	/// for `ttl`. Repeating a query for the same metric, host and time range,
+	/// with the same headers, within it is answered without sending a request. Up to
+	/// [`METRICS_CACHE_CAPACITY`](constant.METRICS_CACHE_CAPACITY.html)
+	/// queries are remembered. `None` disables the cache.
+	///
+	/// Setting this starts a new, empty cache. It is shared with clones made
+	/// afterwards.
+	pub fn set_metrics_cache(&mut self, ttl: Option<Duration>) {
+		self.metrics_cache = ttl.map(|ttl| Arc::new(client::TtlCache::new(ttl, METRICS_CACHE_CAPACITY)));
+	}
+
+	/// Whether Droplet image slugs are checked before creating Droplets.
+	/// Disabled by default.
+	pub fn image_slug_check(&self) -> bool {
+		self.image_slugs.is_some()
+	}
+
+	/// Check the image slug of each Droplet being created against the
+	/// distribution images before sending it, so that a typo such as
+	/// `ubuntu-22-04-x65` fails with `Error::InvalidArgument` suggesting the
+	/// nearest slugs rather than with a `422`.
+	///
+	/// The slugs are fetched the first time they are needed and kept until
+	/// this is set again. Images given by id are not checked, but application
+	/// and custom image slugs are rejected, so leave this disabled when
+	/// creating Droplets from those.
+	pub fn set_image_slug_check(&mut self, enabled: bool) {
+		self.image_slugs = if enabled { Some(Arc::new(client::ImageSlugs::default())) } else { None };
+	}
+
+	/// How long to wait between polls while waiting for an action to finish.
+	/// Defaults to [`DEFAULT_POLL_INTERVAL`](constant.DEFAULT_POLL_INTERVAL.html).
+	pub fn poll_interval(&self) -> Duration {
+		self.poll_interval
+	}
+
+	/// Change how long to wait between polls while waiting for an action.
+	pub fn set_poll_interval(&mut self, interval: Duration) {
+		self.poll_interval = interval;
+	}
+
+	/// Whether this client writes to the `log` crate. Enabled by default.
+	pub fn logging(&self) -> bool {
+		self.logging
+	}
+
+	/// Enable or disable this client's logs, for libraries which do not want
+	/// to add to their users' output. Token providers log on their own, such
+	/// as [`CachedToken`](token/struct.CachedToken.html) when it refreshes.
+	pub fn set_logging(&mut self, enabled: bool) {
+		self.logging = enabled;
+	}
+
+	/// Counts of the requests sent by this client and its clones so far,
+	/// such as for exporting to a monitoring system.
+	pub fn metrics(&self) -> ClientMetrics {
+		self.counters.snapshot()
+	}
+
+	/// Whether requests are also counted per endpoint in
+	/// [`metrics()`](#method.metrics). Disabled by default.
+	pub fn endpoint_metrics(&self) -> bool {
+		self.endpoint_metrics
+	}
+
+	/// Also count this client's requests per endpoint, keyed by the method
+	/// and path with ids left out, such as `GET /droplets/{id}`. The counts
+	/// are shared with clones, but each clone decides whether to add to them.
+	pub fn set_endpoint_metrics(&mut self, enabled: bool) {
+		self.endpoint_metrics = enabled;
+	}
+
+	/// Shut the client down, closing idle keep-alive connections so that a
+	/// short-lived program can exit promptly. The client cannot be used
+	/// afterwards.
+	///
+	/// Clones share the connection pool, so it is only closed once every
+	/// clone has been closed or dropped. Dropping the client does the same
+	/// thing; this just makes it explicit.
+	pub fn close(self) {
+		client_log!(self, trace, "Closing connection pool.");
+		drop(self);
+	}
+
+	/// A snapshot of this client's settings, such as for a bug report. The
+	/// key is never included.
+	pub fn config(&self) -> ClientConfig {
+		ClientConfig {
+			key: "***",
+			root: self.root.clone(),
+			gzip: self.gzip,
+			max_response_bytes: self.max_response_bytes,
+			poll_interval: self.poll_interval,
+			page_delay: self.page_delay.0,
+			page_delay_jitter: self.page_delay.1,
+			etag_cache: self.etag_cache(),
+			coalescing: self.coalescing(),
+			metrics_cache: self.metrics_cache(),
+			image_slug_check: self.image_slug_check(),
+			endpoint_metrics: self.endpoint_metrics,
+			logging: self.logging,
+		}
+	}
+
+	/// Build a request against a path under the API root which this crate
+	/// does not model yet, such as `"1-clicks"`. The response is
+	/// deserialized into any `T`.
+	///
+	/// Like every other request, it is sent to the root of whichever client
+	/// executes it. A path with `.` or `..` segments is invalid, so that it
+	/// cannot climb out of the root.
+	///
+	/// Only `Get`, `Create` and `Update` requests can be executed this way.
+	///
+	/// ```rust,no_run
+	/// # use digitalocean::prelude::*;
+	/// # use digitalocean::method::Get;
+	/// # use serde_json::Value;
+	/// # let client = DigitalOcean::new("token").unwrap();
+	/// let clicks = DigitalOcean::raw::<Get, Value>("1-clicks", None)
+	///     .execute(&client);
+	/// ```
+	pub fn raw<M, T>(path: &str, body: Option<Value>) -> Request<M, Raw<T>>
+		where M: Method, T: DeserializeOwned + Clone {
+		let joined = format!(
+			"{}/{}",
+			ROOT_URL.as_str().trim_end_matches('/'),
+			path.trim_start_matches('/')
+		);
+
+		let mut req = match Url::parse(&joined) {
+			Ok(url) => Request::new(url),
+			Err(e) => {
+				let mut req = Request::new(ROOT_URL.clone());
+				req.invalidate("path", e.to_string());
+				return req;
+			}
+		};
+		if let Err(reason) = validation::api_path(path) {
+			req.invalidate("path", reason);
+		} else if !req.url().path().starts_with(ROOT_URL.path()) {
+			req.invalidate("path", format!("`{}` is outside the API root", path));
+		}
+		req.set_body(body.unwrap_or(Value::Null));
+		req
+	}
+
 	pub fn execute<A: Method, V: HasResponse>(&self, request: Request<A, V>) -> Result<V, Error>
 		where Request<A, V>: Executable<V> {
 		request.execute(self)
 	}
 }
+
+/// The settings of a client, as returned by
+/// [`DigitalOcean::config()`](struct.DigitalOcean.html#method.config). Each
+/// field matches the getter of the same name.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ClientConfig {
+	/// Always `***`, so that the config can be shared safely.
+	pub key: &'static str,
+
+	#[serde(with = "url_serde")]
+	pub root: Url,
+
+	pub gzip: bool,
+	pub max_response_bytes: Option<usize>,
+	pub poll_interval: Duration,
+	pub page_delay: Duration,
+	pub page_delay_jitter: Duration,
+	pub etag_cache: Option<usize>,
+	pub coalescing: bool,
+	pub metrics_cache: Option<Duration>,
+	pub image_slug_check: bool,
+	pub endpoint_metrics: bool,
+	pub logging: bool
+}
+
+/// Counts of the requests a client has sent, as returned by
+/// [`DigitalOcean::metrics()`](struct.DigitalOcean.html#method.metrics).
+/// Clones of a client share their counts.
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientMetrics {
+	/// HTTP requests sent, including each page of a listing and requests
+	/// which failed without a response. Requests answered by another
+	/// caller's coalesced `GET` are not counted.
+	pub requests: u64,
+
+	/// Responses with the status `429 Too Many Requests`.
+	pub rate_limited: u64,
+
+	/// The same counts per endpoint, such as `GET /droplets/{id}`. Only
+	/// filled in by clients with
+	/// [`set_endpoint_metrics()`](struct.DigitalOcean.html#method.set_endpoint_metrics)
+	/// enabled.
+	pub endpoints: BTreeMap<String, EndpointMetrics>
+}
+
+/// Counts of the requests sent to one endpoint. See
+/// [`ClientMetrics::endpoints`](struct.ClientMetrics.html#structfield.endpoints).
+#[derive(Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EndpointMetrics {
+	pub requests: u64,
+	pub rate_limited: u64
+}
@@ -6,31 +6,44 @@ pub type Limit = usize;
 
 /// A marker trait used by [`Request`](../request/struct.Request.html)
 /// to signal which execution path should be taken.
-pub trait Method: Default + Debug + Clone + Copy {}
+pub trait Method: Default + Debug + Clone + Copy {
+	/// The HTTP method requests of this kind are sent with.
+	const VERB: &'static str;
+}
 
 /// A list method uses a GET request with pagination.
 ///
 /// This method enables the [`limit()`](../request/struct.Request.html#method.limit) call on requests.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct List(pub Option<Limit>);
-impl Method for List {}
+impl Method for List {
+	const VERB: &'static str = "GET";
+}
 
 /// A get method uses a GET request.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Get;
-impl Method for Get {}
+impl Method for Get {
+	const VERB: &'static str = "GET";
+}
 
 /// A create method uses a POST request.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Create;
-impl Method for Create {}
+impl Method for Create {
+	const VERB: &'static str = "POST";
+}
 
 /// An update method uses a PUT request.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Update;
-impl Method for Update {}
+impl Method for Update {
+	const VERB: &'static str = "PUT";
+}
 
 /// A delete method uses a DELETE request.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Delete;
-impl Method for Delete {}
+impl Method for Delete {
+	const VERB: &'static str = "DELETE";
+}
@@ -2,12 +2,12 @@
 //!
 //!
 
-use crate::api::{HasPagination, HasResponse};
+use crate::api::{HasPagination, HasResponse, PageCursor};
 use crate::method::{Create, Delete, Get, List, Method, Update};
-use crate::DigitalOcean;
+use crate::{DigitalOcean, ROOT_URL};
 use crate::error::Error;
 use getset::{Getters, MutGetters, Setters};
-use serde_json::Value;
+use serde_json::{Map, Value};
 use std::marker::PhantomData;
 use url::Url;
 use url_serde;
@@ -16,6 +16,8 @@ use url_serde;
 pub type AccountRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, Action>`](struct.Request.html) specific functions.
 pub type ActionRequest<M, V> = Request<M, V>;
+/// A type alias with [`Request<_, Invoice>`](struct.Request.html) specific functions.
+pub type BillingRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, Certificate>`](struct.Request.html) specific functions.
 pub type CertificateRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, DomainRecord>`](struct.Request.html) specific functions.
@@ -26,6 +28,8 @@ pub type DomainRequest<M, V> = Request<M, V>;
 pub type DropletActionRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, Droplet>`](struct.Request.html) specific functions.
 pub type DropletRequest<M, V> = Request<M, V>;
+/// A type alias with [`Request<_, Firewall>`](struct.Request.html) specific functions.
+pub type FirewallRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, FloatingIpAction>`](struct.Request.html) specific functions.
 pub type FloatingIpActionRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, FloatingIp>`](struct.Request.html) specific functions.
@@ -38,21 +42,31 @@ pub type ImageRequest<M, V> = Request<M, V>;
 pub type CustomImageRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, LoadBalancer>`](struct.Request.html) specific functions.
 pub type LoadBalancerRequest<M, V> = Request<M, V>;
+/// A type alias with [`Request<_, Metrics>`](struct.Request.html) specific functions.
+pub type MonitoringRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, Region>`](struct.Request.html) specific functions.
 pub type RegionRequest<M, V> = Request<M, V>;
+/// A type alias with [`Request<_, ReservedIpv6>`](struct.Request.html) specific functions.
+pub type ReservedIpv6Request<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, Size>`](struct.Request.html) specific functions.
 pub type SizeRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, Snapshot>`](struct.Request.html) specific functions.
 pub type SnapshotRequest<M, V> = Request<M, V>;
+/// A type alias with [`Request<_, SpacesKey>`](struct.Request.html) specific functions.
+pub type SpacesRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, SshKey>`](struct.Request.html) specific functions.
 pub type SshKeyRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, Tag>`](struct.Request.html) specific functions.
 pub type TagRequest<M, V> = Request<M, V>;
+/// A type alias with [`Request<_, UptimeCheck>`](struct.Request.html) specific functions.
+pub type UptimeRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, VolumeAction>`](struct.Request.html) specific functions.
 pub type VolumeActionRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, Volume>`](struct.Request.html) specific functions.
 pub type VolumeRequest<M, V> = Request<M, V>;
 
+const CORRELATION_ID_HEADER: &str = "X-Correlation-Id";
+
 /// A consuming builder which can be used to build up API calls.
 ///
 /// In general consumers of the crate should not need to use this type directly.
@@ -74,7 +88,61 @@ pub struct Request<A: Method, R> {
 	#[get = "pub"]
 	method: A,
 
-	value: PhantomData<R>
+	/// Extra headers sent along with the request.
+	#[get = "pub"]
+	#[serde(default)]
+	headers: Vec<(String, String)>,
+
+	value: PhantomData<R>,
+
+	/// Whether the url is used as-is instead of being moved under the
+	/// client's root.
+	#[serde(default)]
+	absolute: bool,
+
+	/// Overrides the client's limit on the size of the response body.
+	#[serde(skip)]
+	max_response_bytes: Option<Option<usize>>,
+
+	/// The problems found with arguments while building the request, in the
+	/// order they were found. Only the first is reported.
+	#[serde(skip)]
+	invalid: Vec<(String, String)>,
+
+	/// Checks made against the resource before it is deleted.
+	#[serde(skip)]
+	preconditions: Vec<Precondition>,
+
+	/// Sent as `X-Correlation-Id` and attached to any error.
+	#[serde(default)]
+	correlation_id: Option<String>,
+
+	/// Fields added with `extra_field()`, merged into the body when the
+	/// request is sent.
+	#[serde(default)]
+	extra: Map<String, Value>,
+
+	/// The fields of the body the endpoint requires, recorded by the builder
+	/// which creates the request. See [`describe()`](#method.describe).
+	#[serde(skip)]
+	required: Vec<String>,
+
+	/// The path segments which are parameters, by their index in the url's
+	/// path, and the name each is shown as in [`describe()`](#method.describe).
+	#[serde(skip)]
+	params: Vec<(usize, &'static str)>
+}
+
+/// A check made by fetching a resource before deleting it. See
+/// [`expect_exists()`](struct.Request.html#method.expect_exists) and
+/// [`if_match()`](struct.Request.html#method.if_match).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Precondition {
+	/// The resource must still exist.
+	Exists,
+
+	/// The resource's `field` must equal the value.
+	Matches(String, Value)
 }
 
 impl<A: Method, V> Request<A, V> {
@@ -85,64 +153,485 @@ impl<A: Method, V> Request<A, V> {
 			url,
 			body: Value::Null,
 			method: A::default(),
+			headers: Vec::new(),
 			value: PhantomData,
+			absolute: false,
+			max_response_bytes: None,
+			invalid: Vec::new(),
+			preconditions: Vec::new(),
+			correlation_id: None,
+			extra: Map::new(),
+			required: Vec::new(),
+			params: Vec::new(),
+		}
+	}
+
+	/// Create a request pointing at a URL which is sent exactly as given,
+	/// such as an endpoint under a beta or other version prefix. Unlike
+	/// [`new()`](#method.new), the URL is not moved under the client's
+	/// [`root()`](../struct.DigitalOcean.html#method.root).
+	///
+	/// The URL must still be on the API host or the client's root, since the
+	/// API key is sent with it. Executing a request for any other host fails
+	/// with `Error::InvalidArgument` without sending anything.
+	pub fn absolute(url: Url) -> Self {
+		let mut req = Request::new(url);
+		req.absolute = true;
+		req
+	}
+
+	/// Whether this request was created with [`absolute()`](#method.absolute).
+	pub fn is_absolute(&self) -> bool {
+		self.absolute
+	}
+
+	/// Record the body fields the endpoint requires.
+	pub(crate) fn require(&mut self, fields: &[&str]) {
+		self.required = fields.iter().map(|field| field.to_string()).collect();
+	}
+
+	/// Record the last segment of the url's path as the parameter `name`.
+	pub(crate) fn path_param(&mut self, name: &'static str) {
+		let index = self.url.path().split('/').count() - 1;
+		self.params.push((index, name));
+	}
+
+	/// Describe the request for tools which generate help or documentation
+	/// from this crate, such as a CLI.
+	///
+	/// The description is of the request as built so far: optional fields
+	/// are only listed once their builder method has been called. Path
+	/// parameters, such as ids, IP addresses and domain names, are shown by
+	/// name, such as `{id}`.
+	///
+	/// ```rust
+	/// # use digitalocean::prelude::*;
+	/// let description = Droplet::get(1234).describe();
+	/// assert_eq!(description.method, "GET");
+	/// assert_eq!(description.path, "/droplets/{id}");
+	/// assert_eq!(description.response, "Droplet");
+	/// ```
+	pub fn describe(&self) -> RequestDescriptor {
+		let root = match self.absolute {
+			true => 0,
+			false => ROOT_URL.path().split('/').filter(|segment| !segment.is_empty()).count()
+		};
+		let path = self.url.path().split('/')
+			.enumerate()
+			.map(|(index, segment)| match self.params.iter().find(|(param, _)| *param == index) {
+				Some((_, name)) => format!("{{{}}}", name),
+				None => segment.to_string()
+			})
+			.filter(|segment| !segment.is_empty())
+			.skip(root)
+			.fold(String::new(), |path, segment| path + "/" + &segment);
+
+		let mut fields: Vec<FieldDescriptor> = match self.body {
+			Value::Object(ref fields) => fields.iter()
+				.map(|(name, value)| FieldDescriptor {
+					name: name.clone(),
+					kind: json_kind(value),
+					required: self.required.contains(name),
+				})
+				.collect(),
+			_ => Vec::new()
+		};
+		for (name, value) in self.extra.iter() {
+			if !fields.iter().any(|field| field.name == *name) {
+				fields.push(FieldDescriptor {
+					name: name.clone(),
+					kind: json_kind(value),
+					required: false,
+				});
+			}
+		}
+		fields.sort_by(|a, b| a.name.cmp(&b.name));
+
+		RequestDescriptor {
+			method: A::VERB,
+			path,
+			fields,
+			response: short_type_name(std::any::type_name::<V>()),
+		}
+	}
+
+	/// Send an extra header with the request, such as a tracing header.
+	///
+	/// Headers set by the client itself (`Authorization` and `Content-Type`)
+	/// take precedence. Setting `Authorization` here is invalid.
+	pub fn header<N, S>(mut self, name: N, value: S) -> Self
+		where N: Into<String>, S: Into<String> {
+		let name = name.into();
+
+		if name.eq_ignore_ascii_case("authorization") {
+			self.invalidate("header", "`Authorization` is set by the client");
+		} else {
+			self.headers.push((name, value.into()));
+		}
+		self
+	}
+
+	/// Tie the request into the caller's tracing. The id is sent as the
+	/// `X-Correlation-Id` header, and any error from executing the request
+	/// is wrapped in `Error::Correlated` carrying it.
+	pub fn correlation_id<S: Into<String>>(mut self, id: S) -> Self {
+		let id = id.into();
+		self.headers.retain(|(name, _)| !name.eq_ignore_ascii_case(CORRELATION_ID_HEADER));
+		self.headers.push((CORRELATION_ID_HEADER.to_string(), id.clone()));
+		self.correlation_id = Some(id);
+		self
+	}
+
+	/// Override the client's limit on the size of the response body for this
+	/// request only. `None` disables the limit.
+	pub fn max_response_bytes(mut self, limit: Option<usize>) -> Self {
+		self.max_response_bytes = Some(limit);
+		self
+	}
+
+	/// The limit on the size of the response body, falling back to the
+	/// client's limit if the request does not override it.
+	pub(crate) fn response_limit(&self, instance: &DigitalOcean) -> Option<usize> {
+		self.max_response_bytes
+			.unwrap_or_else(|| instance.max_response_bytes())
+	}
+
+	/// Check for problems with arguments given while building the request.
+	/// This is done automatically before the request is executed.
+	pub fn validate(&self) -> Result<(), Error> {
+		if let Some((field, reason)) = self.invalid.first() {
+			return Err(Error::InvalidArgument {
+				field: field.clone(),
+				reason: reason.clone(),
+			});
+		}
+
+		match self.body {
+			Value::Object(ref fields) => match self.extra.keys().find(|key| fields.contains_key(*key)) {
+				Some(key) => Err(Error::InvalidArgument {
+					field: key.clone(),
+					reason: "is already set by this request".to_string(),
+				}),
+				None => Ok(())
+			},
+			Value::Null => Ok(()),
+			_ if self.extra.is_empty() => Ok(()),
+			_ => Err(Error::InvalidArgument {
+				field: self.extra.keys().next().cloned().unwrap_or_default(),
+				reason: "the body of this request is not an object".to_string(),
+			})
+		}
+	}
+
+	/// Fields added with `extra_field()`. They are kept apart from the
+	/// [`body()`](#method.body) until the request is sent.
+	pub fn extra_fields(&self) -> &Map<String, Value> {
+		&self.extra
+	}
+
+	/// Record a problem with an argument. Only the first problem is
+	/// reported, but the rest are kept in case it is taken back.
+	pub(crate) fn invalidate<F, S>(&mut self, field: F, reason: S)
+		where F: Into<String>, S: Into<String> {
+		self.invalid.push((field.into(), reason.into()));
+	}
+
+	/// Record a problem with `field` if `result` failed.
+	pub(crate) fn check<F: Into<String>>(&mut self, field: F, result: Result<(), String>) {
+		if let Err(reason) = result {
+			self.invalidate(field, reason);
+		}
+	}
+
+	/// Transform the value returned by this request once it has been
+	/// executed, such as sorting a list or picking out a field. `f` is only
+	/// called if the request succeeds.
+	///
+	/// ```rust,no_run
+	/// # use digitalocean::prelude::*;
+	/// # let client = DigitalOcean::new("token").unwrap();
+	/// let count = Droplet::list()
+	///     .map_response(|droplets| droplets.len())
+	///     .execute(&client);
+	/// ```
+	pub fn map_response<U, F>(self, f: F) -> MapResponse<Self, V, F>
+		where F: FnOnce(V) -> U {
+		MapResponse {
+			request: self,
+			f,
+			value: PhantomData
+		}
+	}
+
+	/// Keep `key` to be merged into the body when the request is sent. Used
+	/// by `extra_field()`. Clashes with the body are found by `validate()`,
+	/// whichever was set first.
+	fn insert_extra_field(&mut self, key: String, value: Value) {
+		if self.extra.contains_key(&key) {
+			self.invalidate(key, "is given more than once");
+		} else {
+			self.extra.insert(key, value);
+		}
+	}
+
+	/// Replace the body field `from` with `to`, keeping whether it is
+	/// required. Problems found with `from` are dropped along with it.
+	pub(crate) fn replace_field(&mut self, from: &str, to: &str, value: Value) {
+		self.invalid.retain(|(field, _)| field != from);
+		if let Value::Object(ref mut fields) = self.body {
+			fields.remove(from);
+			fields.insert(to.to_string(), value);
+		}
+		for field in self.required.iter_mut().filter(|field| *field == from) {
+			*field = to.to_string();
 		}
 	}
 
 	pub(crate) fn transmute<C: Method, D>(self) -> Request<C, D> {
 		let mut req = Request::new(self.url);
-		req.set_body(self.body);
+		req.body = self.body;
+		req.required = self.required;
+		req.params = self.params;
+		req.headers = self.headers;
+		req.absolute = self.absolute;
+		req.max_response_bytes = self.max_response_bytes;
+		req.invalid = self.invalid;
+		req.preconditions = self.preconditions;
+		req.correlation_id = self.correlation_id;
+		req.extra = self.extra;
 		req
 	}
+
+	/// Validate the request and hand it to `send`, attaching its
+	/// correlation id to any error.
+	pub(crate) fn run<T, F>(mut self, send: F) -> Result<T, Error>
+		where F: FnOnce(Self) -> Result<T, Error> {
+		let correlation_id = self.correlation_id.clone();
+
+		self.validate()
+			.and_then(|_| {
+				self.merge_extra_fields();
+				send(self)
+			})
+			.map_err(|e| e.correlated(correlation_id))
+	}
+
+	/// Move the fields added with `extra_field()` into the body. Only called
+	/// once the request is valid, so none of them clash with it.
+	fn merge_extra_fields(&mut self) {
+		if self.extra.is_empty() {
+			return;
+		}
+		if self.body.is_null() {
+			self.body = json!({});
+		}
+		if let Value::Object(ref mut fields) = self.body {
+			fields.append(&mut self.extra);
+		}
+	}
 }
 
 impl<V> Request<List, V> {
 	/// Impose a limit on the number of values which may be retrieved from a request.
+	///
+	/// A limit of zero is invalid.
 	pub fn limit(mut self, limit: Option<usize>) -> Self {
+		if limit == Some(0) {
+			self.invalidate("limit", "must be greater than zero");
+		}
 		self.method.0 = limit;
 		self
 	}
+
+	/// Continue a listing from a previously saved [`PageCursor`](../api/struct.PageCursor.html)
+	/// instead of from the first page. As with [`absolute()`](#method.absolute),
+	/// a cursor pointing at another host is refused rather than sent the key.
+	pub fn resume_from(mut self, cursor: PageCursor) -> Self {
+		self.url = cursor.url().clone();
+		self
+	}
+}
+
+impl<V> Request<Create, V> {
+	/// Send a field this crate does not model yet, such as one recently added
+	/// to the API. Extra fields are kept apart from the others and merged into
+	/// the body when the request is sent. A key which the request also sets,
+	/// before or after this is called, is invalid.
+	///
+	/// ```rust,no_run
+	/// # use digitalocean::prelude::*;
+	/// # use serde_json::json;
+	/// # let client = DigitalOcean::new("token").unwrap();
+	/// let droplet = Droplet::create("example", "nyc3", "s-1vcpu-1gb", "ubuntu-22-04-x64")
+	///     .extra_field("with_droplet_agent", json!(true))
+	///     .execute(&client);
+	/// ```
+	pub fn extra_field<S: Into<String>>(mut self, key: S, value: Value) -> Self {
+		self.insert_extra_field(key.into(), value);
+		self
+	}
+}
+
+impl<V> Request<Update, V> {
+	/// Send a field this crate does not model yet. See
+	/// [`Request<Create, V>::extra_field()`](#method.extra_field).
+	pub fn extra_field<S: Into<String>>(mut self, key: S, value: Value) -> Self {
+		self.insert_extra_field(key.into(), value);
+		self
+	}
+}
+
+impl<V> Request<Delete, V> {
+	/// Fetch the resource before deleting it, and fail with
+	/// `Error::Precondition` instead of deleting if it is already gone. This
+	/// costs an extra request.
+	///
+	/// This only makes sense for requests which delete a single resource at
+	/// the same URL it is fetched from, such as
+	/// [`Domain::delete()`](../api/struct.Domain.html#method.delete).
+	pub fn expect_exists(mut self) -> Self {
+		self.preconditions.push(Precondition::Exists);
+		self
+	}
+
+	/// Fetch the resource before deleting it, and fail with
+	/// `Error::Precondition` instead of deleting unless its `field` equals
+	/// `expected`. This implies [`expect_exists()`](#method.expect_exists).
+	///
+	/// ```rust,no_run
+	/// # use digitalocean::prelude::*;
+	/// # use serde_json::json;
+	/// # let client = DigitalOcean::new("token").unwrap();
+	/// Droplet::delete(1234)
+	///     .if_match("name", json!("staging-db"))
+	///     .execute(&client);
+	/// ```
+	pub fn if_match<S: Into<String>>(mut self, field: S, expected: Value) -> Self {
+		self.preconditions.push(Precondition::Matches(field.into(), expected));
+		self
+	}
+
+	/// The checks made before the resource is deleted.
+	pub fn preconditions(&self) -> &[Precondition] {
+		&self.preconditions
+	}
+}
+
+/// A description of a request, as returned by
+/// [`Request::describe()`](struct.Request.html#method.describe).
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct RequestDescriptor {
+	/// The HTTP method, such as `POST`.
+	pub method: &'static str,
+
+	/// The path under the API root, with parameters replaced by their name,
+	/// such as `/droplets/{id}/actions`.
+	pub path: String,
+
+	/// The fields of the body, in alphabetical order.
+	pub fields: Vec<FieldDescriptor>,
+
+	/// The name of the value returned when the request is executed, such as
+	/// `Vec<Droplet>`.
+	pub response: String
+}
+
+/// A field of a request's body. See [`RequestDescriptor`](struct.RequestDescriptor.html).
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct FieldDescriptor {
+	pub name: String,
+
+	/// The JSON type of the field: `string`, `number`, `boolean`, `array`,
+	/// `object` or `null`.
+	pub kind: &'static str,
+
+	/// Whether the endpoint requires the field, rather than it having been
+	/// added by a builder method.
+	pub required: bool
+}
+
+fn json_kind(value: &Value) -> &'static str {
+	match *value {
+		Value::Null => "null",
+		Value::Bool(_) => "boolean",
+		Value::Number(_) => "number",
+		Value::String(_) => "string",
+		Value::Array(_) => "array",
+		Value::Object(_) => "object"
+	}
+}
+
+/// Strip the module paths from a type name, so that
+/// `alloc::vec::Vec<digitalocean::api::droplet::Droplet>` becomes
+/// `Vec<Droplet>`.
+fn short_type_name(name: &str) -> String {
+	let mut short = String::new();
+	let mut path = String::new();
+
+	for c in name.chars() {
+		if c.is_alphanumeric() || c == '_' || c == ':' {
+			path.push(c);
+		} else {
+			short.push_str(path.rsplit("::").next().unwrap_or(""));
+			path.clear();
+			short.push(c);
+		}
+	}
+	short.push_str(path.rsplit("::").next().unwrap_or(""));
+	short
 }
 
 /// Describes an API call which can be executed.
-pub trait Executable<T: HasResponse>: Sized {
+pub trait Executable<T>: Sized {
 	/// Execute the corresponding call.
 	fn execute(self, instance: &DigitalOcean) -> Result<T, Error>;
 }
 
+/// A request whose value is transformed after it is executed. Built with
+/// [`map_response()`](struct.Request.html#method.map_response).
+pub struct MapResponse<R, V, F> {
+	request: R,
+	f: F,
+	value: PhantomData<V>
+}
+
+impl<R, V, U, F> Executable<U> for MapResponse<R, V, F>
+	where R: Executable<V>, F: FnOnce(V) -> U {
+	fn execute(self, instance: &DigitalOcean) -> Result<U, Error> {
+		let value = self.request.execute(instance)?;
+		Ok((self.f)(value))
+	}
+}
+
 impl<V> Executable<Vec<V>> for Request<List, Vec<V>>
 	where
 		Vec<V>: HasResponse,
 		<Vec<V> as HasResponse>::Response: HasPagination {
 	fn execute(self, instance: &DigitalOcean) -> Result<Vec<V>, Error> {
-		let response: Vec<V> = instance.list(self)?;
-		Ok(response)
+		self.run(|req| instance.list(req))
 	}
 }
 
 impl<V: HasResponse> Executable<V> for Request<Create, V> {
 	fn execute(self, instance: &DigitalOcean) -> Result<V, Error> {
-		let response = instance.post(self)?;
-		Ok(response)
+		self.run(|req| instance.post(req))
 	}
 }
 
 impl<V: HasResponse> Executable<V> for Request<Update, V> {
 	fn execute(self, instance: &DigitalOcean) -> Result<V, Error> {
-		let response = instance.put(self)?;
-		Ok(response)
+		self.run(|req| instance.put(req))
 	}
 }
 
 impl<V: HasResponse> Executable<V> for Request<Get, V> {
 	fn execute(self, instance: &DigitalOcean) -> Result<V, Error> {
-		let response = instance.get(self)?;
-		Ok(response)
+		self.run(|req| instance.get(req))
 	}
 }
 
 impl Executable<()> for Request<Delete, ()> {
 	fn execute(self, instance: &DigitalOcean) -> Result<(), Error> {
-		instance.delete(self)
+		self.run(|req| instance.delete(req))
 	}
 }
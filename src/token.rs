@@ -0,0 +1,65 @@
+//! Sources of the bearer token sent with each request.
+//!
+//! A plain API key never changes, but OAuth tokens expire and have to be
+//! refreshed. A [`TokenProvider`](trait.TokenProvider.html) is asked for the
+//! current token before every request, so it can refresh as needed.
+
+use crate::error::Error;
+use chrono::{DateTime, Duration, Utc};
+use log::debug;
+use std::sync::Mutex;
+
+/// Something which can produce the token to authenticate a request with.
+///
+/// It is called once per HTTP request, including each page of a listing, so
+/// implementations which talk to an authorization server should cache their
+/// token. [`CachedToken`](struct.CachedToken.html) does this for you.
+pub trait TokenProvider: Send + Sync {
+	fn token(&self) -> Result<String, Error>;
+}
+
+/// A static API key, such as a personal access token.
+impl TokenProvider for String {
+	fn token(&self) -> Result<String, Error> {
+		Ok(self.clone())
+	}
+}
+
+/// Caches the token returned by `refresh` until shortly before it expires.
+///
+/// `refresh` returns a new token along with the time it expires at. It is
+/// only called again once fewer than `margin` remain before that time.
+pub struct CachedToken<F> {
+	refresh: F,
+	margin: Duration,
+	current: Mutex<Option<(String, DateTime<Utc>)>>
+}
+
+impl<F> CachedToken<F>
+	where F: Fn() -> Result<(String, DateTime<Utc>), Error> + Send + Sync {
+	pub fn new(refresh: F, margin: Duration) -> Self {
+		CachedToken {
+			refresh,
+			margin,
+			current: Mutex::new(None)
+		}
+	}
+}
+
+impl<F> TokenProvider for CachedToken<F>
+	where F: Fn() -> Result<(String, DateTime<Utc>), Error> + Send + Sync {
+	fn token(&self) -> Result<String, Error> {
+		let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+
+		if let Some((ref token, expires_at)) = *current {
+			if Utc::now() + self.margin < expires_at {
+				return Ok(token.clone());
+			}
+		}
+
+		debug!("Refreshing token.");
+		let (token, expires_at) = (self.refresh)()?;
+		*current = Some((token.clone(), expires_at));
+		Ok(token)
+	}
+}
@@ -0,0 +1,113 @@
+//! Checks applied to arguments while requests are built, so that obviously
+//! bad input is reported before a round trip to the API.
+
+use url::percent_encoding::percent_decode;
+use url::Url;
+
+/// The value must contain at least one character.
+pub(crate) fn non_empty(value: &str) -> Result<(), String> {
+	if value.is_empty() {
+		Err("must not be empty".to_string())
+	} else {
+		Ok(())
+	}
+}
+
+/// The value must look like a region slug, such as `nyc3`.
+pub(crate) fn region_slug(value: &str) -> Result<(), String> {
+	let letters = value.trim_end_matches(|c: char| c.is_ascii_digit());
+
+	if letters.is_empty()
+		|| letters.len() == value.len()
+		|| !letters.chars().all(|c| c.is_ascii_lowercase()) {
+		Err(format!("`{}` is not a region slug such as `nyc3`", value))
+	} else {
+		Ok(())
+	}
+}
+
+/// The value must look like a size slug, such as `s-1vcpu-1gb`.
+pub(crate) fn size_slug(value: &str) -> Result<(), String> {
+	non_empty(value)?;
+
+	if !value.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-') {
+		Err(format!("`{}` is not a size slug such as `s-1vcpu-1gb`", value))
+	} else {
+		Ok(())
+	}
+}
+
+/// The value must be an image slug or a positive image id.
+pub(crate) fn image(value: &str) -> Result<(), String> {
+	non_empty(value)?;
+
+	match value.parse::<i64>() {
+		Ok(id) if id <= 0 => Err(format!("`{}` is not a positive image id", id)),
+		_ => Ok(())
+	}
+}
+
+/// The value must be an SSH key fingerprint or a positive key id.
+pub(crate) fn ssh_key(value: &str) -> Result<(), String> {
+	non_empty(value)?;
+
+	match value.parse::<i64>() {
+		Ok(id) if id <= 0 => Err(format!("`{}` is not a positive SSH key id", id)),
+		_ => Ok(())
+	}
+}
+
+/// Tags may contain letters, numbers, colons, dashes, and underscores, up to
+/// 255 characters.
+pub(crate) fn tag_name(value: &str) -> Result<(), String> {
+	non_empty(value)?;
+
+	if value.len() > 255 {
+		Err("must not exceed 255 characters".to_string())
+	} else if !value
+		.chars()
+		.all(|c| c.is_ascii_alphanumeric() || c == ':' || c == '-' || c == '_') {
+		Err("may only contain letters, numbers, colons, dashes, and underscores".to_string())
+	} else {
+		Ok(())
+	}
+}
+
+/// API keys are never empty and never contain whitespace, which usually
+/// means a line break or space was copied along with the key.
+pub(crate) fn api_key(value: &str) -> Result<(), String> {
+	non_empty(value)?;
+
+	if value.chars().any(char::is_whitespace) {
+		Err("must not contain whitespace or line breaks".to_string())
+	} else {
+		Ok(())
+	}
+}
+
+/// A path under the API root may not climb out of it with `.` or `..`
+/// segments, which would otherwise be resolved away when the URL is parsed.
+pub(crate) fn api_path(value: &str) -> Result<(), String> {
+	let path = value.split(['?', '#']).next().unwrap_or("");
+	let climbs = path.split('/')
+		.map(|segment| percent_decode(segment.as_bytes()).decode_utf8_lossy().to_lowercase())
+		.any(|segment| segment == "." || segment == "..");
+
+	if climbs {
+		Err(format!("`{}` must not contain `.` or `..` segments", value))
+	} else {
+		Ok(())
+	}
+}
+
+/// An API root is a base URL which paths are added to, so it cannot carry a
+/// query or fragment.
+pub(crate) fn api_root(value: &Url) -> Result<(), String> {
+	if value.cannot_be_a_base() {
+		Err(format!("`{}` cannot be used as a base URL", value))
+	} else if value.query().is_some() || value.fragment().is_some() {
+		Err(format!("`{}` must not have a query or fragment", value))
+	} else {
+		Ok(())
+	}
+}
@@ -0,0 +1,119 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+extern crate serde_json;
+extern crate url;
+extern crate url_serde;
+
+mod utils;
+
+use serde_json::Value;
+
+use digitalocean::api::Action;
+use digitalocean::method::{Get, List};
+use digitalocean::request::{Executable, Request};
+use digitalocean::DigitalOcean;
+
+use crate::utils::{action_json, before, json_response, Mock};
+
+#[test]
+fn list_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/actions";
+
+    let req: Request<List, Vec<Action>> = Action::list();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn get_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/actions/36804636";
+
+    let req: Request<Get, Action> = Action::get(36804636);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn list_deserializes_and_follows_pages() {
+    before();
+
+    let mock = Mock::new(vec![
+        json_response(
+            "200 OK",
+            &serde_json::json!({
+                "actions": [action_json(1, "completed"), action_json(2, "errored")],
+                "links": {
+                    "pages": {
+                        "next": "https://api.digitalocean.com/v2/actions?page=2&per_page=2",
+                        "last": "https://api.digitalocean.com/v2/actions?page=2&per_page=2"
+                    }
+                },
+                "meta": { "total": 3 }
+            }),
+        ),
+        json_response(
+            "200 OK",
+            &serde_json::json!({
+                "actions": [action_json(3, "in-progress")],
+                "links": {},
+                "meta": { "total": 3 }
+            }),
+        ),
+    ]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let actions = Action::list().execute(&client).unwrap();
+
+    assert_eq!(actions.len(), 3);
+    assert_eq!(*actions[0].id(), 1);
+    assert_eq!(actions[0].kind(), "create");
+    assert_eq!(actions[1].status(), "errored");
+    assert_eq!(actions[1].resource_type(), "droplet");
+    assert_eq!(*actions[1].resource_id(), 3164444);
+    assert_eq!(actions[1].region_slug().as_deref(), Some("nyc3"));
+    assert!(actions[2].completed_at().is_none());
+
+    let requests = mock.requests();
+    assert!(requests[0].starts_with("GET /v2/actions?per_page=200 "));
+    assert!(requests[1].starts_with("GET /v2/actions?page=2&per_page=2 "));
+}
+
+#[test]
+fn list_with_limit_sets_per_page() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "200 OK",
+        &serde_json::json!({
+            "actions": [action_json(1, "completed"), action_json(2, "completed")],
+            "links": {
+                "pages": {
+                    "next": "https://api.digitalocean.com/v2/actions?page=2&per_page=2"
+                }
+            },
+            "meta": { "total": 5 }
+        }),
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let actions = Action::list().limit(Some(2)).execute(&client).unwrap();
+
+    assert_eq!(actions.len(), 2);
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with("GET /v2/actions?per_page=2 "));
+}
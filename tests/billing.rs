@@ -0,0 +1,142 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+extern crate serde_json;
+extern crate url;
+extern crate url_serde;
+
+mod utils;
+
+use serde_json::Value;
+
+use digitalocean::api::{BillingHistory, Invoice};
+use digitalocean::method::List;
+use digitalocean::request::{Executable, Request};
+use digitalocean::DigitalOcean;
+
+use crate::utils::{before, json_response, Mock};
+
+fn invoice_json(uuid: &str, period: &str) -> Value {
+    serde_json::json!({
+        "invoice_uuid": uuid,
+        "invoice_id": "123",
+        "amount": "12.34",
+        "invoice_period": period,
+        "updated_at": "2020-01-01T00:00:00Z",
+    })
+}
+
+#[test]
+fn invoices_list_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/customers/my/invoices";
+
+    let req: Request<List, Vec<Invoice>> = Invoice::list();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn billing_history_list_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/customers/my/billing_history";
+
+    let req: Request<List, Vec<BillingHistory>> = BillingHistory::list();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn invoices_list_follows_pages() {
+    before();
+
+    let mock = Mock::new(vec![
+        json_response(
+            "200 OK",
+            &serde_json::json!({
+                "invoices": [invoice_json("22737452-c7ed-4ab5-8e28-bd0a6e7b2a46", "2019-12")],
+                "invoice_preview": invoice_json("1afe95e6-0958-4eb0-8d9a-9c5060d3ef03", "2020-01"),
+                "links": {
+                    "pages": {
+                        "next": "https://api.digitalocean.com/v2/customers/my/invoices?page=2&per_page=1",
+                        "last": "https://api.digitalocean.com/v2/customers/my/invoices?page=2&per_page=1"
+                    }
+                },
+                "meta": { "total": 2 }
+            }),
+        ),
+        json_response(
+            "200 OK",
+            &serde_json::json!({
+                "invoices": [invoice_json("fdabb512-6faf-443c-ba2e-665452332a9e", "2019-11")],
+                "invoice_preview": invoice_json("1afe95e6-0958-4eb0-8d9a-9c5060d3ef03", "2020-01"),
+                "links": {
+                    "pages": {
+                        "first": "https://api.digitalocean.com/v2/customers/my/invoices?page=1&per_page=1",
+                        "prev": "https://api.digitalocean.com/v2/customers/my/invoices?page=1&per_page=1"
+                    }
+                },
+                "meta": { "total": 2 }
+            }),
+        ),
+    ]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let invoices = Invoice::list().execute(&client).unwrap();
+
+    assert_eq!(invoices.len(), 2);
+    assert_eq!(invoices[0].invoice_period(), "2019-12");
+    assert_eq!(invoices[1].invoice_period(), "2019-11");
+
+    let requests = mock.requests();
+    assert!(requests[0].starts_with("GET /v2/customers/my/invoices?per_page=200 "));
+    assert!(requests[1].starts_with("GET /v2/customers/my/invoices?page=2&per_page=1 "));
+}
+
+#[test]
+fn billing_history_without_links_is_a_single_page() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "200 OK",
+        &serde_json::json!({
+            "billing_history": [
+                {
+                    "description": "Invoice for May 2018",
+                    "amount": "12.34",
+                    "invoice_id": "123",
+                    "invoice_uuid": "example-uuid",
+                    "date": "2018-06-01T08:44:38Z",
+                    "type": "Invoice"
+                },
+                {
+                    "description": "Payment (MC 2018)",
+                    "amount": "-12.34",
+                    "date": "2018-06-02T08:44:38Z",
+                    "type": "Payment"
+                }
+            ]
+        }),
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let history = BillingHistory::list().execute(&client).unwrap();
+
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].kind(), "Invoice");
+    assert_eq!(history[0].invoice_uuid().as_deref(), Some("example-uuid"));
+    assert_eq!(history[1].amount(), "-12.34");
+    assert!(history[1].invoice_id().is_none());
+
+    assert_eq!(mock.requests().len(), 1);
+}
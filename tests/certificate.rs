@@ -12,6 +12,7 @@ use serde_json::Value;
 
 use digitalocean::api::Certificate;
 use digitalocean::method::{Create, Delete, Get, List};
+use digitalocean::error::Error;
 use digitalocean::request::Request;
 
 use crate::utils::before;
@@ -91,3 +92,38 @@ fn delete_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[test]
+fn create_lets_encrypt_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/certificates";
+
+    let req: Request<Create, Certificate> =
+        Certificate::create_lets_encrypt("web", vec!["example.com", "www.example.com"]);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "name": "web",
+            "type": "lets_encrypt",
+            "dns_names": ["example.com", "www.example.com"],
+        })
+    );
+    assert!(req.validate().is_ok());
+}
+
+#[test]
+fn create_lets_encrypt_requires_dns_names() {
+    before();
+
+    let req: Request<Create, Certificate> =
+        Certificate::create_lets_encrypt("web", Vec::<String>::new());
+
+    match req.validate() {
+        Err(Error::InvalidArgument { field, .. }) => assert_eq!(field, "dns_names"),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
@@ -0,0 +1,2054 @@
+extern crate chrono;
+extern crate digitalocean;
+extern crate serde_json;
+extern crate url;
+
+use chrono::Utc;
+use std::io::Read;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+mod utils;
+
+use digitalocean::api::{
+    Account, Domain, Droplet, HasPagination, HasValue, Image, PageCursor, Raw, Region, Tag,
+    Wrapped, WrapperKey,
+};
+use digitalocean::method::{Create, Get};
+use digitalocean::error::Error;
+use digitalocean::request::{Executable, Request};
+use digitalocean::token::CachedToken;
+use digitalocean::{ClientMetrics, DigitalOcean, EndpointMetrics};
+
+use crate::utils::{action_json, before, droplet_json, json_response, response, Mock};
+
+// `{"account":{...}}` compressed with gzip.
+const GZIPPED_ACCOUNT: &[u8] = &[
+    0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0x35, 0xcc, 0xcb, 0x0a, 0x83, 0x30,
+    0x10, 0x85, 0xe1, 0x77, 0x99, 0xb5, 0x14, 0xac, 0x44, 0x13, 0x57, 0x7d, 0x13, 0x19, 0x27, 0x13,
+    0x19, 0x48, 0x8c, 0xe4, 0x22, 0x14, 0xf1, 0xdd, 0x9b, 0x45, 0xdd, 0x7e, 0x3f, 0xe7, 0x5c, 0x80,
+    0x44, 0xb1, 0xee, 0x05, 0xe6, 0x0b, 0x6c, 0x8a, 0x87, 0xe7, 0xb2, 0x78, 0x09, 0xd2, 0xe0, 0xad,
+    0x3a, 0x70, 0x3e, 0x62, 0x91, 0x7d, 0x5b, 0xe4, 0x78, 0xb8, 0x29, 0x07, 0x14, 0x0f, 0x33, 0x64,
+    0x0c, 0xe1, 0xfb, 0xb1, 0xb2, 0x49, 0x41, 0x1f, 0x89, 0x71, 0x7f, 0x51, 0x0c, 0xd0, 0x41, 0xad,
+    0x62, 0x5b, 0x5f, 0x47, 0x97, 0xb4, 0xb1, 0xab, 0x1b, 0xad, 0xe9, 0xd5, 0x48, 0x48, 0xac, 0xdc,
+    0x40, 0x93, 0xb6, 0x64, 0xb4, 0xea, 0xad, 0x51, 0xd3, 0xa0, 0x7b, 0x76, 0xf0, 0xbf, 0x5c, 0x4e,
+    0x4e, 0xe2, 0x84, 0xdb, 0xb6, 0xa4, 0xca, 0x1d, 0xe4, 0x82, 0xa5, 0xe6, 0xf6, 0x84, 0x54, 0xe4,
+    0x64, 0x78, 0x64, 0x09, 0x9c, 0x33, 0x6e, 0xdc, 0x0a, 0xdc, 0xf7, 0x0f, 0x2a, 0xc5, 0x59, 0x8c,
+    0xc5, 0x00, 0x00, 0x00,
+];
+
+#[test]
+fn gzip_response_is_decompressed() {
+    before();
+
+    let mock = Mock::new(vec![response(
+        "200 OK",
+        &[
+            ("Content-Type", "application/json"),
+            ("Content-Encoding", "gzip"),
+        ],
+        GZIPPED_ACCOUNT,
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let req = Account::get();
+    let account = req.execute(&client).unwrap();
+
+    assert_eq!(account.email(), "sammy@digitalocean.com");
+    assert_eq!(*account.droplet_limit(), 25);
+
+    let requests = mock.requests();
+    assert!(requests[0].to_lowercase().contains("accept-encoding: gzip"));
+}
+
+#[test]
+fn gzip_can_be_disabled() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "200 OK",
+        &serde_json::json!({
+            "account": {
+                "droplet_limit": 25,
+                "floating_ip_limit": 5,
+                "email": "sammy@digitalocean.com",
+                "uuid": "b6fr89dbf6d9156cace5f3c78dc9851d957381ef",
+                "email_verified": true,
+                "status": "active",
+                "status_message": "",
+            }
+        }),
+    )]);
+    let mut client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+    client.set_gzip(false).unwrap();
+    assert!(!client.gzip());
+
+    let req = Account::get();
+    req.execute(&client).unwrap();
+
+    let requests = mock.requests();
+    assert!(!requests[0].to_lowercase().contains("accept-encoding: gzip"));
+}
+
+#[test]
+fn page_cursor_resumes_listing() {
+    before();
+
+    let next = "https://api.digitalocean.com/v2/domains?page=2&per_page=1";
+    let mock = Mock::new(vec![
+        json_response(
+            "200 OK",
+            &serde_json::json!({
+                "domains": [{ "name": "first.com", "ttl": 1800, "zone_file": null }],
+                "links": { "pages": { "next": next, "last": next } },
+                "meta": { "total": 2 },
+            }),
+        ),
+        json_response(
+            "200 OK",
+            &serde_json::json!({
+                "domains": [{ "name": "second.com", "ttl": 1800, "zone_file": null }],
+                "links": {},
+                "meta": { "total": 2 },
+            }),
+        ),
+    ]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let req = Domain::list().limit(Some(1));
+    let first = client.list_page(req).unwrap();
+    let cursor = first.cursor().expect("First page should have a cursor.");
+
+    // Checkpoint the cursor as a long-running job would.
+    let saved = serde_json::to_string(&cursor).unwrap();
+    let cursor: PageCursor = serde_json::from_str(&saved).unwrap();
+
+    let req = Domain::list().limit(Some(1)).resume_from(cursor);
+    assert_eq!(req.url().as_str(), next);
+    let second = client.list_page(req).unwrap();
+    assert!(second.cursor().is_none());
+    assert_eq!(second.value()[0].name(), "second.com");
+
+    let requests = mock.requests();
+    assert!(requests[0].starts_with("GET /v2/domains?per_page=1 "));
+    assert!(requests[1].starts_with("GET /v2/domains?page=2&per_page=1 "));
+}
+
+#[test]
+fn no_content_delete_succeeds() {
+    before();
+
+    let mock = Mock::new(vec![
+        response("204 No Content", &[], b""),
+        // A 204 should never carry a body, but if one does it is ignored.
+        response("204 No Content", &[("Content-Type", "application/json")], b"{}"),
+    ]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    for _ in 0..2 {
+        let req = Droplet::delete(123);
+        assert!(req.execute(&client).is_ok());
+    }
+
+    let requests = mock.requests();
+    assert!(requests[0].starts_with("DELETE /v2/droplets/123 "));
+}
+
+#[test]
+fn no_content_create_succeeds() {
+    before();
+
+    let mock = Mock::new(vec![response("204 No Content", &[], b"")]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let req = Tag::get("bear").add_resources(vec![("123", "droplet")]);
+    assert!(req.execute(&client).is_ok());
+
+    let requests = mock.requests();
+    assert!(requests[0].starts_with("POST /v2/tags/bear/resources "));
+}
+
+#[test]
+fn custom_headers_are_sent() {
+    before();
+
+    let mock = Mock::new(vec![response("204 No Content", &[], b"")]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let req = Droplet::delete(123).header("X-Trace-Id", "abc123");
+    req.execute(&client).unwrap();
+
+    let requests = mock.requests();
+    let request = requests[0].to_lowercase();
+    assert!(request.contains("x-trace-id: abc123"));
+    assert!(request.contains("authorization: bearer token"));
+}
+
+#[test]
+fn authorization_header_cannot_be_overridden() {
+    before();
+
+    let req = Droplet::delete(123).header("authorization", "Bearer stolen");
+
+    assert!(req.headers().is_empty());
+    match req.validate() {
+        Err(Error::InvalidArgument { ref field, .. }) if field == "header" => (),
+        other => panic!("Expected header to be invalid, got {:?}", other),
+    }
+}
+
+#[test]
+fn raw_request_hits_arbitrary_path() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "200 OK",
+        &serde_json::json!({ "1_clicks": [{ "slug": "wordpress-18-04", "type": "droplet" }] }),
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let req = DigitalOcean::raw::<Get, serde_json::Value>("/1-clicks?type=droplet", None);
+    assert_eq!(
+        req.url().as_str(),
+        "https://api.digitalocean.com/v2/1-clicks?type=droplet"
+    );
+    let Raw(value) = req.execute(&client).unwrap();
+
+    assert_eq!(value["1_clicks"][0]["slug"], "wordpress-18-04");
+
+    let requests = mock.requests();
+    assert!(requests[0].starts_with("GET /v2/1-clicks?type=droplet "));
+    assert!(requests[0].to_lowercase().contains("authorization: bearer token"));
+}
+
+#[test]
+fn raw_request_sends_body() {
+    before();
+
+    let body = serde_json::json!({ "addon_slugs": ["kafka"], "cluster_uuid": "50a994b6" });
+    let req = DigitalOcean::raw::<Create, serde_json::Value>("1-clicks/kubernetes", Some(body.clone()));
+
+    assert_eq!(
+        req.url().as_str(),
+        "https://api.digitalocean.com/v2/1-clicks/kubernetes"
+    );
+    assert_eq!(*req.body(), body);
+}
+
+#[test]
+fn raw_request_rejects_paths_outside_the_root() {
+    before();
+
+    for path in &["../v1/account", "droplets/../../account", "%2e%2e/account", "droplets/./1"] {
+        let req = DigitalOcean::raw::<Get, serde_json::Value>(path, None);
+        match req.validate() {
+            Err(Error::InvalidArgument { field, .. }) => assert_eq!(field, "path"),
+            other => panic!("Unexpected result for {:?}: {:?}", path, other),
+        }
+    }
+}
+
+struct DropletKey;
+
+impl WrapperKey for DropletKey {
+    const KEY: &'static str = "droplet";
+}
+
+#[test]
+fn raw_request_unwraps_resource_key() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "200 OK",
+        &serde_json::json!({
+            "droplet": droplet_json(3164444, "active"),
+            "links": {}
+        }),
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let req = DigitalOcean::raw::<Get, Wrapped<DropletKey, Droplet>>("droplets/3164444", None);
+    let Raw(wrapped) = req.execute(&client).unwrap();
+    let droplet = wrapped.into_inner();
+
+    assert_eq!(*droplet.id(), 3164444);
+    assert!(droplet.status().is_running());
+
+    let missing: Result<Wrapped<DropletKey, Droplet>, _> =
+        serde_json::from_value(serde_json::json!({ "droplets": [] }));
+    assert!(missing.unwrap_err().to_string().contains("missing field `droplet`"));
+}
+
+/// An account response padded to `size` bytes, sent without a
+/// `Content-Length` so that it has to be streamed until the connection closes.
+fn streamed_account(size: usize) -> Vec<u8> {
+    let mut body = serde_json::json!({
+        "account": {
+            "droplet_limit": 25,
+            "floating_ip_limit": 5,
+            "email": "sammy@digitalocean.com",
+            "uuid": "b6fr89dbf6d9156cace5f3c78dc9851d957381ef",
+            "email_verified": true,
+            "status": "active",
+            "status_message": "",
+        }
+    })
+    .to_string()
+    .into_bytes();
+    body.resize(size, b' ');
+
+    let mut raw =
+        b"HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Type: application/json\r\n\r\n".to_vec();
+    raw.extend_from_slice(&body);
+    raw
+}
+
+#[test]
+fn oversized_response_is_rejected() {
+    before();
+
+    let mock = Mock::new(vec![streamed_account(64 * 1024)]);
+    let mut client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+    client.set_max_response_bytes(Some(1024));
+
+    let req = Account::get();
+
+    match req.execute(&client) {
+        Err(Error::ResponseTooLarge { limit }) => assert_eq!(limit, 1024),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn oversized_content_length_is_rejected() {
+    before();
+
+    let body = vec![b' '; 4096];
+    let mock = Mock::new(vec![response("200 OK", &[], &body)]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let req = Account::get().max_response_bytes(Some(1024));
+
+    match req.execute(&client) {
+        Err(Error::ResponseTooLarge { limit }) => assert_eq!(limit, 1024),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn response_limit_can_be_overridden_per_request() {
+    before();
+
+    let mock = Mock::new(vec![streamed_account(64 * 1024)]);
+    let mut client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+    client.set_max_response_bytes(Some(1024));
+
+    let req = Account::get().max_response_bytes(None);
+
+    let account = req.execute(&client).unwrap();
+    assert_eq!(account.email(), "sammy@digitalocean.com");
+}
+
+#[test]
+fn create_droplet_and_wait_returns_active_droplet() {
+    before();
+
+    let mock = Mock::new(vec![
+        json_response(
+            "202 Accepted",
+            &serde_json::json!({
+                "droplet": droplet_json(3164444, "new"),
+                "links": {
+                    "actions": [{
+                        "id": 36805096,
+                        "rel": "create",
+                        "href": "https://api.digitalocean.com/v2/actions/36805096",
+                    }]
+                }
+            }),
+        ),
+        json_response(
+            "200 OK",
+            &serde_json::json!({ "action": action_json(36805096, "in-progress") }),
+        ),
+        json_response(
+            "200 OK",
+            &serde_json::json!({ "action": action_json(36805096, "completed") }),
+        ),
+        json_response(
+            "200 OK",
+            &serde_json::json!({ "droplet": droplet_json(3164444, "active") }),
+        ),
+    ]);
+    let mut client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+    client.set_poll_interval(Duration::from_millis(1));
+
+    let droplet = client
+        .create_droplet_and_wait(
+            Droplet::create("example.com", "nyc3", "s-1vcpu-1gb", "ubuntu-20-04-x64"),
+            Duration::from_secs(10),
+        )
+        .unwrap();
+
+    assert_eq!(droplet.status(), "active");
+    assert_eq!(droplet.networks().v4.len(), 1);
+
+    let requests = mock.requests();
+    assert!(requests[0].starts_with("POST /v2/droplets "));
+    assert!(requests[1].starts_with("GET /v2/actions/36805096 "));
+    assert!(requests[2].starts_with("GET /v2/actions/36805096 "));
+    assert!(requests[3].starts_with("GET /v2/droplets/3164444 "));
+}
+
+#[test]
+fn create_droplet_and_wait_fails_on_errored_action() {
+    before();
+
+    let mock = Mock::new(vec![
+        json_response(
+            "202 Accepted",
+            &serde_json::json!({
+                "droplet": droplet_json(3164444, "new"),
+                "links": {
+                    "actions": [{
+                        "id": 36805096,
+                        "rel": "create",
+                        "href": "https://api.digitalocean.com/v2/actions/36805096",
+                    }]
+                }
+            }),
+        ),
+        json_response(
+            "200 OK",
+            &serde_json::json!({ "action": action_json(36805096, "errored") }),
+        ),
+    ]);
+    let mut client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+    client.set_poll_interval(Duration::from_millis(1));
+
+    let result = client.create_droplet_and_wait(
+        Droplet::create("example.com", "nyc3", "s-1vcpu-1gb", "ubuntu-20-04-x64"),
+        Duration::from_secs(10),
+    );
+
+    match result {
+        Err(Error::ActionErrored { id }) => assert_eq!(id, 36805096),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+    assert_eq!(mock.requests().len(), 2);
+}
+
+fn snapshot_json(id: &str, name: &str, created_at: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "name": name,
+        "created_at": created_at,
+        "regions": ["nyc3"],
+        "resource_id": "3164444",
+        "resource_type": "droplet",
+        "min_disk_size": 25,
+        "size_gigabytes": 2.34,
+    })
+}
+
+#[test]
+fn snapshot_droplet_and_wait_returns_new_snapshot() {
+    before();
+
+    let mock = Mock::new(vec![
+        json_response(
+            "201 Created",
+            &serde_json::json!({ "action": action_json(36805187, "in-progress") }),
+        ),
+        json_response(
+            "200 OK",
+            &serde_json::json!({ "action": action_json(36805187, "in-progress") }),
+        ),
+        json_response(
+            "200 OK",
+            &serde_json::json!({ "action": action_json(36805187, "completed") }),
+        ),
+        json_response(
+            "200 OK",
+            &serde_json::json!({
+                "snapshots": [
+                    snapshot_json("6372321", "nightly", "2020-07-20T18:37:44Z"),
+                    snapshot_json("6372581", "before-upgrade", "2020-07-19T18:37:44Z"),
+                    snapshot_json("6372909", "before-upgrade", "2020-07-21T18:40:02Z"),
+                ],
+                "links": {},
+                "meta": { "total": 3 }
+            }),
+        ),
+    ]);
+    let mut client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+    client.set_poll_interval(Duration::from_millis(1));
+
+    let snapshot = client
+        .snapshot_droplet_and_wait(3164444, "before-upgrade", Duration::from_secs(10))
+        .unwrap();
+
+    assert_eq!(snapshot.id(), "6372909");
+
+    let requests = mock.requests();
+    assert!(requests[0].starts_with("POST /v2/droplets/3164444/actions "));
+    assert!(requests[0].contains(r#""type":"snapshot""#));
+    assert!(requests[1].starts_with("GET /v2/actions/36805187 "));
+    assert!(requests[2].starts_with("GET /v2/actions/36805187 "));
+    assert!(requests[3].starts_with("GET /v2/droplets/3164444/snapshots"));
+}
+
+#[test]
+fn snapshot_droplet_and_wait_fails_on_errored_action() {
+    before();
+
+    let mock = Mock::new(vec![
+        json_response(
+            "201 Created",
+            &serde_json::json!({ "action": action_json(36805187, "in-progress") }),
+        ),
+        json_response(
+            "200 OK",
+            &serde_json::json!({ "action": action_json(36805187, "errored") }),
+        ),
+    ]);
+    let mut client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+    client.set_poll_interval(Duration::from_millis(1));
+
+    match client.snapshot_droplet_and_wait(3164444, "before-upgrade", Duration::from_secs(10)) {
+        Err(Error::ActionErrored { id }) => assert_eq!(id, 36805187),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+    assert_eq!(mock.requests().len(), 2);
+}
+
+#[test]
+fn wait_for_action_times_out() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "200 OK",
+        &serde_json::json!({ "action": action_json(36805096, "in-progress") }),
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    match client.wait_for_action(36805096, Duration::from_secs(0)) {
+        Err(Error::Timeout) => (),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn clones_can_use_their_own_key() {
+    before();
+
+    let account = serde_json::json!({
+        "account": {
+            "droplet_limit": 25,
+            "floating_ip_limit": 5,
+            "email": "sammy@digitalocean.com",
+            "uuid": "b6fr89dbf6d9156cace5f3c78dc9851d957381ef",
+            "email_verified": true,
+            "status": "active",
+            "status_message": "",
+        }
+    });
+    let mock = Mock::new(vec![
+        json_response("200 OK", &account),
+        json_response("200 OK", &account),
+    ]);
+    let client = DigitalOcean::new("first")
+        .unwrap()
+        .with_root(mock.url().clone());
+    let mut clone = client.clone();
+    clone.set_key("second");
+
+    Account::get().execute(&client).unwrap();
+    Account::get().execute(&clone).unwrap();
+
+    let requests = mock.requests();
+    assert!(requests[0].to_lowercase().contains("authorization: bearer first"));
+    assert!(requests[1].to_lowercase().contains("authorization: bearer second"));
+    assert_eq!(clone.root(), client.root());
+}
+
+#[test]
+fn absolute_request_bypasses_root() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "200 OK",
+        &serde_json::json!({ "enabled": true }),
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let url = mock.url().join("/beta/feature").unwrap();
+    let req: Request<Get, Raw<serde_json::Value>> = Request::absolute(url.clone());
+    assert!(req.is_absolute());
+    assert_eq!(*req.url(), url);
+
+    let Raw(value) = req.execute(&client).unwrap();
+    assert_eq!(value["enabled"], true);
+
+    let requests = mock.requests();
+    assert!(requests[0].starts_with("GET /beta/feature "));
+}
+
+#[test]
+fn malformed_response_reports_path_and_snippet() {
+    before();
+
+    let body = br#"{"account": {"email": "sammy@digitalocean.com", "token": "dop_v1_secret", "#;
+    let mock = Mock::new(vec![response(
+        "200 OK",
+        &[("Content-Type", "application/json")],
+        body,
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let req = Account::get();
+
+    match req.execute(&client) {
+        Err(Error::Deserialize {
+            path,
+            status,
+            snippet,
+            ..
+        }) => {
+            assert_eq!(path, "/v2/account");
+            assert_eq!(status.as_u16(), 200);
+            assert!(snippet.contains("sammy@digitalocean.com"));
+            assert!(snippet.contains(r#""token": "[REDACTED]""#));
+            assert!(!snippet.contains("dop_v1_secret"));
+        }
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn deserialize_snippet_is_truncated() {
+    before();
+
+    let body = format!("[{}", "1,".repeat(1000));
+    let mock = Mock::new(vec![response("200 OK", &[], body.as_bytes())]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let req = Account::get();
+
+    match req.execute(&client) {
+        Err(e @ Error::Deserialize { .. }) => {
+            let message = e.to_string();
+            assert!(message.contains("/v2/account"));
+            assert!(message.len() < 1024);
+        }
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn batch_preserves_order_and_isolates_errors() {
+    before();
+
+    let mock = Mock::with_handler(5, |request| {
+        let path = request.split_whitespace().nth(1).unwrap().to_string();
+        let name = path.rsplit('/').next().unwrap().to_string();
+
+        if name == "tag-3" {
+            json_response("404 Not Found", &serde_json::json!({ "id": "not_found" }))
+        } else {
+            json_response(
+                "200 OK",
+                &serde_json::json!({ "tag": { "name": name, "resources": {} } }),
+            )
+        }
+    });
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let requests = (0..5).map(|i| Tag::get(format!("tag-{}", i))).collect();
+    let results = client.execute_batch(requests, 2);
+
+    assert_eq!(results.len(), 5);
+    for (i, result) in results.iter().enumerate() {
+        match result {
+            Err(Error::NotFound { resource, id }) if i == 3 => {
+                assert_eq!(resource, "tags");
+                assert_eq!(id.as_deref(), Some("tag-3"));
+            }
+            Ok(tag) if i != 3 => assert_eq!(*tag.name(), format!("tag-{}", i)),
+            other => panic!("Unexpected result {}: {:?}", i, other),
+        }
+    }
+    assert_eq!(mock.requests().len(), 5);
+}
+
+#[test]
+fn send_raw_sends_empty_body_for_bodyless_create() {
+    before();
+
+    let mock = Mock::new(vec![json_response("200 OK", &serde_json::json!({}))]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let req: Request<Create, Raw<serde_json::Value>> = DigitalOcean::raw("1-clicks/kubernetes", None);
+    client.send_raw(req).unwrap();
+
+    let requests = mock.requests();
+    assert!(requests[0].starts_with("POST "));
+    assert!(requests[0].to_lowercase().contains("content-length: 0\r\n"));
+}
+
+#[test]
+fn send_raw_returns_response_untouched() {
+    before();
+
+    let mock = Mock::new(vec![response(
+        "418 I'm a teapot",
+        &[("Content-Type", "text/plain"), ("X-Custom", "kept")],
+        b"short and stout",
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let req = Droplet::get(123);
+    let mut response = client.send_raw(req).unwrap();
+
+    assert_eq!(response.status().as_u16(), 418);
+    assert_eq!(response.headers()["x-custom"], "kept");
+
+    let mut body = String::new();
+    response.read_to_string(&mut body).unwrap();
+    assert_eq!(body, "short and stout");
+
+    let requests = mock.requests();
+    assert!(requests[0].starts_with("GET /v2/droplets/123 "));
+    assert!(requests[0].to_lowercase().contains("authorization: bearer token"));
+}
+
+fn droplet_list(droplets: Vec<serde_json::Value>) -> Vec<u8> {
+    let total = droplets.len();
+    json_response(
+        "200 OK",
+        &serde_json::json!({
+            "droplets": droplets,
+            "links": {},
+            "meta": { "total": total },
+        }),
+    )
+}
+
+fn named(mut droplet: serde_json::Value, name: &str) -> serde_json::Value {
+    droplet["name"] = serde_json::json!(name);
+    droplet
+}
+
+#[test]
+fn droplet_by_name_finds_exact_match() {
+    before();
+
+    let mock = Mock::new(vec![droplet_list(vec![
+        named(droplet_json(1, "active"), "App-1"),
+        named(droplet_json(2, "active"), "app-1"),
+    ])]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let droplet = client.droplet_by_name("app-1").unwrap().unwrap();
+    assert_eq!(*droplet.id(), 2);
+
+    let requests = mock.requests();
+    assert!(requests[0].starts_with("GET /v2/droplets?name=app-1&per_page=200 "));
+}
+
+#[test]
+fn droplet_by_name_reports_missing_droplet() {
+    before();
+
+    let mock = Mock::new(vec![droplet_list(vec![])]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    assert!(client.droplet_by_name("app-1").unwrap().is_none());
+}
+
+#[test]
+fn droplet_by_name_rejects_ambiguous_name() {
+    before();
+
+    let mock = Mock::new(vec![droplet_list(vec![
+        named(droplet_json(1, "active"), "app-1"),
+        named(droplet_json(2, "active"), "app-1"),
+    ])]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    match client.droplet_by_name("app-1") {
+        Err(Error::AmbiguousName { name, count }) => {
+            assert_eq!(name, "app-1");
+            assert_eq!(count, 2);
+        }
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn etag_cache_answers_not_modified() {
+    before();
+
+    let mock = Mock::new(vec![
+        response(
+            "200 OK",
+            &[("Content-Type", "application/json"), ("ETag", "\"v1\"")],
+            serde_json::json!({
+                "regions": [{
+                    "name": "New York 3",
+                    "slug": "nyc3",
+                    "sizes": [],
+                    "available": true,
+                    "features": [],
+                }],
+                "links": {},
+                "meta": { "total": 1 },
+            })
+            .to_string()
+            .as_bytes(),
+        ),
+        response("304 Not Modified", &[("ETag", "\"v1\"")], b""),
+    ]);
+    let mut client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+    assert_eq!(client.etag_cache(), None);
+    client.set_etag_cache(Some(16));
+
+    let first = Region::list().execute(&client).unwrap();
+    let second = Region::list().execute(&client).unwrap();
+
+    assert_eq!(first.len(), 1);
+    assert_eq!(second[0].slug(), "nyc3");
+
+    let requests = mock.requests();
+    assert!(!requests[0].to_lowercase().contains("if-none-match"));
+    assert!(requests[1].to_lowercase().contains("if-none-match: \"v1\""));
+}
+
+#[test]
+fn etag_cache_is_kept_apart_per_key_and_headers() {
+    before();
+
+    let page = response(
+        "200 OK",
+        &[("Content-Type", "application/json"), ("ETag", "\"v1\"")],
+        serde_json::json!({ "regions": [], "links": {}, "meta": { "total": 0 } })
+            .to_string()
+            .as_bytes(),
+    );
+    let mock = Mock::new(vec![page.clone(), page.clone(), page]);
+    let mut client = DigitalOcean::new("first")
+        .unwrap()
+        .with_root(mock.url().clone());
+    client.set_etag_cache(Some(16));
+    let mut clone = client.clone();
+    clone.set_key("second");
+
+    Region::list().execute(&client).unwrap();
+    Region::list().execute(&clone).unwrap();
+    Region::list().header("X-Tenant", "other").execute(&client).unwrap();
+
+    let requests = mock.requests();
+    assert!(!requests[1].to_lowercase().contains("if-none-match"));
+    assert!(!requests[2].to_lowercase().contains("if-none-match"));
+}
+
+#[test]
+fn etag_cache_is_off_by_default() {
+    before();
+
+    let page = response(
+        "200 OK",
+        &[("Content-Type", "application/json"), ("ETag", "\"v1\"")],
+        serde_json::json!({ "regions": [], "links": {}, "meta": { "total": 0 } })
+            .to_string()
+            .as_bytes(),
+    );
+    let mock = Mock::new(vec![page.clone(), page]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    Region::list().execute(&client).unwrap();
+    Region::list().execute(&client).unwrap();
+
+    let requests = mock.requests();
+    assert!(!requests[1].to_lowercase().contains("if-none-match"));
+}
+
+#[test]
+fn page_delay_separates_page_fetches() {
+    before();
+
+    let region = serde_json::json!({
+        "name": "New York 3",
+        "slug": "nyc3",
+        "sizes": [],
+        "available": true,
+        "features": [],
+    });
+    let mock = Mock::new(vec![
+        json_response(
+            "200 OK",
+            &serde_json::json!({
+                "regions": [region.clone()],
+                "links": {
+                    "pages": { "next": "https://api.digitalocean.com/v2/regions?page=2" }
+                },
+                "meta": { "total": 2 },
+            }),
+        ),
+        json_response(
+            "200 OK",
+            &serde_json::json!({
+                "regions": [region],
+                "links": {},
+                "meta": { "total": 2 },
+            }),
+        ),
+    ]);
+    let mut client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+    assert_eq!(client.page_delay(), (Duration::from_secs(0), Duration::from_secs(0)));
+    client.set_page_delay(Duration::from_millis(200), Duration::from_millis(50));
+
+    let start = std::time::Instant::now();
+    let regions = Region::list().execute(&client).unwrap();
+
+    assert_eq!(regions.len(), 2);
+    assert!(start.elapsed() >= Duration::from_millis(200));
+    assert!(mock.requests()[1].starts_with("GET /v2/regions?page=2 "));
+}
+
+fn account_json() -> serde_json::Value {
+    serde_json::json!({
+        "account": {
+            "droplet_limit": 25,
+            "floating_ip_limit": 5,
+            "email": "sammy@digitalocean.com",
+            "uuid": "b6fr89dbf6d9156cace5f3c78dc9851d957381ef",
+            "email_verified": true,
+            "status": "active",
+            "status_message": "",
+        }
+    })
+}
+
+#[test]
+fn token_provider_is_asked_before_each_request() {
+    before();
+
+    let mock = Mock::new(vec![
+        json_response("200 OK", &account_json()),
+        json_response("200 OK", &account_json()),
+    ]);
+    let refreshes = Arc::new(AtomicUsize::new(0));
+    let counter = refreshes.clone();
+    // Every token has already expired, so each request refreshes it.
+    let provider = CachedToken::new(
+        move || {
+            let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok((format!("oauth-{}", n), Utc::now()))
+        },
+        chrono::Duration::seconds(60),
+    );
+    let client = DigitalOcean::with_token_provider(provider)
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    Account::get().execute(&client).unwrap();
+    Account::get().execute(&client).unwrap();
+
+    let requests = mock.requests();
+    assert!(requests[0].to_lowercase().contains("authorization: bearer oauth-1"));
+    assert!(requests[1].to_lowercase().contains("authorization: bearer oauth-2"));
+    assert_eq!(refreshes.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn cached_token_is_reused_until_near_expiry() {
+    before();
+
+    let mock = Mock::new(vec![
+        json_response("200 OK", &account_json()),
+        json_response("200 OK", &account_json()),
+    ]);
+    let refreshes = Arc::new(AtomicUsize::new(0));
+    let counter = refreshes.clone();
+    let provider = CachedToken::new(
+        move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(("oauth".to_string(), Utc::now() + chrono::Duration::hours(1)))
+        },
+        chrono::Duration::seconds(60),
+    );
+    let client = DigitalOcean::with_token_provider(provider)
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    Account::get().execute(&client).unwrap();
+    Account::get().execute(&client).unwrap();
+
+    let requests = mock.requests();
+    assert!(requests[1].to_lowercase().contains("authorization: bearer oauth"));
+    assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn bodyless_post_is_sent_empty() {
+    before();
+
+    let mock = Mock::new(vec![response("202 Accepted", &[], b"")]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    Droplet::get(123).retry_destroy().execute(&client).unwrap();
+
+    let requests = mock.requests();
+    let request = requests[0].to_lowercase();
+    assert!(request.starts_with("post /v2/droplets/123/destroy_with_associated_resources/retry "));
+    assert!(request.contains("content-length: 0"));
+    assert!(!request.contains("content-type"));
+    assert!(request.ends_with("\r\n\r\n"));
+}
+
+#[test]
+fn health_check_succeeds_with_valid_key() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "200 OK",
+        &serde_json::json!({ "account": { "email": "sammy@digitalocean.com" } }),
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    client.health_check().unwrap();
+
+    let requests = mock.requests();
+    assert!(requests[0].starts_with("GET /v2/account "));
+    assert!(requests[0].to_lowercase().contains("authorization: bearer token"));
+}
+
+#[test]
+fn health_check_reports_invalid_key() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "401 Unauthorized",
+        &serde_json::json!({ "id": "unauthorized", "message": "Unable to authenticate you" }),
+    )]);
+    let client = DigitalOcean::new("bad-token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    match client.health_check() {
+        Err(Error::Unauthorized) => (),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn health_check_reports_unreachable_api() {
+    before();
+
+    // Bind and release a port so that nothing is listening on it.
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let root = url::Url::parse(&format!("http://127.0.0.1:{}/v2", port)).unwrap();
+    let client = DigitalOcean::new("token").unwrap().with_root(root);
+
+    match client.health_check() {
+        Err(Error::ReqwestError(_)) => (),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn map_response_transforms_the_value() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "200 OK",
+        &serde_json::json!({
+            "droplets": [droplet_json(1, "active"), droplet_json(2, "off")],
+            "links": {},
+            "meta": { "total": 2 }
+        }),
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let count = Droplet::list()
+        .map_response(|droplets| droplets.len())
+        .execute(&client)
+        .unwrap();
+
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn map_response_is_skipped_on_error() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "200 OK",
+        &serde_json::json!({ "droplets": "not a list" }),
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let mut called = false;
+    let result = Droplet::list()
+        .map_response(|droplets| {
+            called = true;
+            droplets.len()
+        })
+        .execute(&client);
+
+    match result {
+        Err(Error::Deserialize { .. }) => (),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+    assert!(!called);
+}
+
+/// Send `count` identical `GET`s from separate threads at once, against a
+/// server which is slow to answer the first.
+fn concurrent_gets(count: usize, status: &'static str) -> (Vec<Result<Droplet, Error>>, usize) {
+    let mock = Mock::with_handler(1, move |_| {
+        std::thread::sleep(Duration::from_millis(300));
+        json_response(
+            status,
+            &serde_json::json!({ "droplet": droplet_json(3164444, "active") }),
+        )
+    });
+    let mut client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+    client.set_coalescing(true);
+
+    let barrier = Arc::new(std::sync::Barrier::new(count));
+    let handles: Vec<_> = (0..count)
+        .map(|_| {
+            let client = client.clone();
+            let barrier = barrier.clone();
+            std::thread::spawn(move || {
+                barrier.wait();
+                Droplet::get(3164444).execute(&client)
+            })
+        })
+        .collect();
+
+    let results = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    (results, mock.requests().len())
+}
+
+#[test]
+fn coalescing_keeps_requests_with_different_headers_apart() {
+    before();
+
+    let mock = Mock::with_handler(2, |_| {
+        std::thread::sleep(Duration::from_millis(300));
+        json_response(
+            "200 OK",
+            &serde_json::json!({ "droplet": droplet_json(3164444, "active") }),
+        )
+    });
+    let mut client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+    client.set_coalescing(true);
+
+    let barrier = Arc::new(std::sync::Barrier::new(2));
+    let handles: Vec<_> = ["trace-a", "trace-b"]
+        .iter()
+        .map(|id| {
+            let client = client.clone();
+            let barrier = barrier.clone();
+            std::thread::spawn(move || {
+                barrier.wait();
+                Droplet::get(3164444).correlation_id(*id).execute(&client)
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap().unwrap();
+    }
+
+    let requests: Vec<String> = mock.requests().iter().map(|r| r.to_lowercase()).collect();
+    assert_eq!(requests.len(), 2);
+    assert!(requests.iter().any(|r| r.contains("x-correlation-id: trace-a")));
+    assert!(requests.iter().any(|r| r.contains("x-correlation-id: trace-b")));
+}
+
+#[test]
+fn coalescing_shares_one_request() {
+    before();
+
+    let (results, requests) = concurrent_gets(5, "200 OK");
+
+    assert_eq!(requests, 1);
+    for result in results {
+        assert_eq!(*result.unwrap().id(), 3164444);
+    }
+}
+
+#[test]
+fn coalescing_shares_error_statuses() {
+    before();
+
+    let (results, requests) = concurrent_gets(5, "404 Not Found");
+
+    assert_eq!(requests, 1);
+    for result in results {
+        match result {
+            Err(Error::NotFound { .. }) => (),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+}
+
+fn error_for_status(status: &str) -> Error {
+    let mock = Mock::new(vec![json_response(status, &serde_json::json!({}))]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let req = Account::get();
+
+    req.execute(&client).unwrap_err()
+}
+
+#[test]
+fn server_errors_and_rate_limits_are_retryable() {
+    before();
+
+    assert!(error_for_status("500 Internal Server Error").is_retryable());
+    assert!(error_for_status("503 Service Unavailable").is_retryable());
+    assert!(error_for_status("429 Too Many Requests").is_retryable());
+}
+
+#[test]
+fn client_errors_are_not_retryable() {
+    before();
+
+    assert!(!error_for_status("400 Bad Request").is_retryable());
+    assert!(!error_for_status("404 Not Found").is_retryable());
+    assert!(!Error::Unauthorized.is_retryable());
+    assert!(!Error::InvalidArgument {
+        field: "name".into(),
+        reason: "must not be empty".into(),
+    }
+    .is_retryable());
+}
+
+#[test]
+fn deserialize_errors_are_not_retryable() {
+    before();
+
+    let mock = Mock::new(vec![response("200 OK", &[], b"{\"account\": ")]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let req = Account::get();
+
+    let error = req.execute(&client).unwrap_err();
+    match error {
+        Error::Deserialize { .. } => assert!(!error.is_retryable()),
+        other => panic!("Unexpected error: {:?}", other),
+    }
+}
+
+#[test]
+fn unreachable_api_is_retryable() {
+    before();
+
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let root = url::Url::parse(&format!("http://127.0.0.1:{}/v2", port)).unwrap();
+    let client = DigitalOcean::new("token").unwrap().with_root(root);
+
+    assert!(client.health_check().unwrap_err().is_retryable());
+}
+
+#[test]
+fn try_new_rejects_empty_key() {
+    before();
+
+    match DigitalOcean::try_new("") {
+        Err(Error::InvalidArgument { field, .. }) => assert_eq!(field, "key"),
+        other => panic!("Unexpected result: {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn try_new_rejects_key_with_whitespace() {
+    before();
+
+    for key in &["dop_v1_abc\n", " dop_v1_abc", "dop_v1 abc", "dop_v1_abc\r\n"] {
+        match DigitalOcean::try_new(*key) {
+            Err(Error::InvalidArgument { field, .. }) => assert_eq!(field, "key"),
+            other => panic!("Unexpected result for {:?}: {:?}", key, other.map(|_| ())),
+        }
+    }
+}
+
+#[test]
+fn set_key_trims_and_try_set_key_checks() {
+    before();
+
+    let mock = Mock::new(vec![
+        json_response("200 OK", &account_json()),
+        json_response("200 OK", &account_json()),
+    ]);
+    let mut client = DigitalOcean::new("first")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    client.set_key("second\n");
+    client.health_check().unwrap();
+
+    match client.try_set_key("dop_v1 abc") {
+        Err(Error::InvalidArgument { field, .. }) => assert_eq!(field, "key"),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+    client.health_check().unwrap();
+
+    let requests = mock.requests();
+    assert!(requests[0].to_lowercase().contains("authorization: bearer second\r\n"));
+    assert!(requests[1].to_lowercase().contains("authorization: bearer second\r\n"));
+}
+
+#[test]
+fn try_new_accepts_valid_key() {
+    before();
+
+    let mock = Mock::new(vec![json_response("200 OK", &account_json())]);
+    let client = DigitalOcean::try_new("dop_v1_abc")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    client.health_check().unwrap();
+
+    let requests = mock.requests();
+    assert!(requests[0].to_lowercase().contains("authorization: bearer dop_v1_abc\r\n"));
+}
+
+#[test]
+fn new_trims_key() {
+    before();
+
+    let mock = Mock::new(vec![json_response("200 OK", &account_json())]);
+    let client = DigitalOcean::new(" dop_v1_abc\n")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    client.health_check().unwrap();
+
+    let requests = mock.requests();
+    assert!(requests[0].to_lowercase().contains("authorization: bearer dop_v1_abc\r\n"));
+}
+
+#[test]
+fn extra_field_is_merged_into_body_when_sent() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "202 Accepted",
+        &serde_json::json!({ "droplet": droplet_json(3164444, "new") }),
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let req = Droplet::create("example", "nyc3", "s-1vcpu-1gb", "ubuntu-22-04-x64")
+        .extra_field("with_droplet_agent", serde_json::json!(true));
+
+    assert!(req.validate().is_ok());
+    assert_eq!(req.extra_fields()["with_droplet_agent"], serde_json::json!(true));
+    assert!(req.body().get("with_droplet_agent").is_none());
+    req.execute(&client).unwrap();
+
+    let requests = mock.requests();
+    let body = &requests[0][requests[0].find("\r\n\r\n").unwrap() + 4..];
+    let body: serde_json::Value = serde_json::from_str(body).unwrap();
+    assert_eq!(body["with_droplet_agent"], serde_json::json!(true));
+    assert_eq!(body["name"], "example");
+}
+
+#[test]
+fn extra_field_on_empty_update_body() {
+    before();
+
+    let mock = Mock::new(vec![json_response("200 OK", &serde_json::json!({}))]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let req = Image::update("123")
+        .extra_field("distribution", serde_json::json!("Ubuntu"));
+
+    assert!(req.validate().is_ok());
+    client.send_raw(req).unwrap();
+
+    let requests = mock.requests();
+    assert!(requests[0].ends_with("{\"distribution\":\"Ubuntu\"}"), "{}", requests[0]);
+}
+
+#[test]
+fn extra_field_rejects_typed_field() {
+    before();
+
+    let req = Droplet::create("example", "nyc3", "s-1vcpu-1gb", "ubuntu-22-04-x64")
+        .extra_field("name", serde_json::json!("other"));
+
+    assert_eq!(req.body()["name"], "example");
+    match req.validate() {
+        Err(Error::InvalidArgument { field, .. }) => assert_eq!(field, "name"),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn extra_field_rejects_typed_field_set_afterwards() {
+    before();
+
+    let req = Droplet::create("example", "nyc3", "s-1vcpu-1gb", "ubuntu-22-04-x64")
+        .extra_field("tags", serde_json::json!(["extra"]))
+        .tags(vec!["typed".to_string()]);
+
+    match req.validate() {
+        Err(Error::InvalidArgument { field, .. }) => assert_eq!(field, "tags"),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+const ERROR_PAGE: &[u8] =
+    b"<html><head><title>503 Service Temporarily Unavailable</title></head></html>";
+
+#[test]
+fn html_error_page_is_reported_as_text() {
+    before();
+
+    let mock = Mock::new(vec![response(
+        "503 Service Unavailable",
+        &[("Content-Type", "text/html")],
+        ERROR_PAGE,
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let error = Account::get().execute(&client).unwrap_err();
+    assert!(error.is_retryable());
+
+    match error {
+        Error::ErrorPage { status, message } => {
+            assert_eq!(status.as_u16(), 503);
+            assert_eq!(message.as_bytes(), ERROR_PAGE);
+        }
+        other => panic!("Unexpected error: {:?}", other),
+    }
+}
+
+#[test]
+fn html_error_page_on_create_is_not_deserialized() {
+    before();
+
+    let mock = Mock::new(vec![response(
+        "422 Unprocessable Entity",
+        &[("Content-Type", "text/html; charset=utf-8")],
+        ERROR_PAGE,
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    match Tag::create("example").execute(&client) {
+        Err(Error::ErrorPage { status, .. }) => assert_eq!(status.as_u16(), 422),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn html_error_page_is_truncated() {
+    before();
+
+    let page = format!("<html>{}</html>", "x".repeat(5000));
+    let mock = Mock::new(vec![response(
+        "502 Bad Gateway",
+        &[("Content-Type", "text/html")],
+        page.as_bytes(),
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    match Droplet::list().execute(&client) {
+        Err(Error::ErrorPage { message, .. }) => {
+            assert!(message.len() < 1000);
+            assert!(message.ends_with("..."));
+        }
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn json_server_error_is_unexpected_status() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "500 Internal Server Error",
+        &serde_json::json!({ "id": "server_error", "message": "Something went wrong" }),
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    match Account::get().execute(&client) {
+        Err(Error::UnexpectedStatus(status)) => assert_eq!(status.as_u16(), 500),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn config_reflects_settings() {
+    before();
+
+    let mut client = DigitalOcean::new("token").unwrap();
+    client.set_etag_cache(Some(16));
+    client.set_page_delay(Duration::from_millis(100), Duration::from_millis(50));
+
+    let config = client.config();
+    assert_eq!(config.root, *client.root());
+    assert_eq!(config.etag_cache, Some(16));
+    assert_eq!(config.page_delay, Duration::from_millis(100));
+    assert_eq!(config.page_delay_jitter, Duration::from_millis(50));
+    assert_eq!(config.max_response_bytes, client.max_response_bytes());
+    assert!(config.gzip);
+    assert!(!config.coalescing);
+}
+
+#[test]
+fn config_never_contains_key() {
+    before();
+
+    let key = "dop_v1_0123456789abcdef";
+    let client = DigitalOcean::new(key).unwrap();
+    let config = client.config();
+
+    assert_eq!(config.key, "***");
+    assert!(!format!("{:?}", config).contains(key));
+
+    let json = serde_json::to_string(&config).unwrap();
+    assert!(!json.contains(key));
+    assert!(json.contains(r#""key":"***""#));
+}
+
+#[test]
+fn set_root_normalizes_trailing_slash() {
+    before();
+
+    for root in &["http://localhost:8080/v2", "http://localhost:8080/v2/", "http://localhost:8080/v2//"] {
+        let mut client = DigitalOcean::new("token").unwrap();
+        client.set_root(url::Url::parse(root).unwrap()).unwrap();
+
+        assert_eq!(client.root().as_str(), "http://localhost:8080/v2/");
+    }
+}
+
+#[test]
+fn set_root_sends_requests_under_root() {
+    before();
+
+    let mock = Mock::new(vec![
+        json_response("200 OK", &account_json()),
+        json_response("200 OK", &account_json()),
+    ]);
+    let without_slash = mock.url().clone();
+    let with_slash = url::Url::parse(&format!("{}/", mock.url())).unwrap();
+
+    for root in [without_slash, with_slash] {
+        let mut client = DigitalOcean::new("token").unwrap();
+        client.set_root(root).unwrap();
+        Account::get().execute(&client).unwrap();
+    }
+
+    for request in mock.requests() {
+        assert!(request.starts_with("GET /v2/account "));
+    }
+}
+
+#[test]
+fn set_root_rejects_query_and_fragment() {
+    before();
+
+    let mut client = DigitalOcean::new("token").unwrap();
+
+    for root in &["http://localhost:8080/v2?x=1", "http://localhost:8080/v2#top"] {
+        match client.set_root(url::Url::parse(root).unwrap()) {
+            Err(Error::InvalidArgument { field, .. }) => assert_eq!(field, "root"),
+            other => panic!("Unexpected result for {}: {:?}", root, other),
+        }
+    }
+    assert_eq!(client.root().as_str(), "https://api.digitalocean.com/v2");
+}
+
+#[test]
+fn execute_page_exposes_total_and_links() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "200 OK",
+        &serde_json::json!({
+            "droplets": [droplet_json(1, "active"), droplet_json(2, "active")],
+            "links": {
+                "pages": {
+                    "first": "https://api.digitalocean.com/v2/droplets?page=1&per_page=2",
+                    "prev": "https://api.digitalocean.com/v2/droplets?page=1&per_page=2",
+                    "next": "https://api.digitalocean.com/v2/droplets?page=3&per_page=2",
+                    "last": "https://api.digitalocean.com/v2/droplets?page=170&per_page=2"
+                }
+            },
+            "meta": { "total": 340 }
+        }),
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let page = client.execute_page(Droplet::list().limit(Some(2))).unwrap();
+
+    assert_eq!(page.items.len(), 2);
+    assert_eq!(page.total, Some(340));
+    assert_eq!(
+        page.next.as_ref().map(url::Url::as_str),
+        Some("https://api.digitalocean.com/v2/droplets?page=3&per_page=2")
+    );
+    assert_eq!(
+        page.prev.as_ref().map(url::Url::as_str),
+        Some("https://api.digitalocean.com/v2/droplets?page=1&per_page=2")
+    );
+    assert_eq!(page.cursor().map(|c| c.url().clone()), page.next);
+}
+
+#[test]
+fn execute_page_without_meta_has_no_total() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "200 OK",
+        &serde_json::json!({ "droplets": [droplet_json(1, "active")], "links": {}, "meta": {} }),
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let page = client.execute_page(Droplet::list()).unwrap();
+
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.total, None);
+    assert_eq!(page.next, None);
+    assert_eq!(page.prev, None);
+}
+
+#[test]
+fn execute_page_exposes_total_and_links_of_macro_listings() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "200 OK",
+        &serde_json::json!({
+            "domains": [{ "name": "example.com", "ttl": 1800, "zone_file": "" }],
+            "links": {
+                "pages": {
+                    "prev": "https://api.digitalocean.com/v2/domains?page=1&per_page=1",
+                    "next": "https://api.digitalocean.com/v2/domains?page=3&per_page=1"
+                }
+            },
+            "meta": { "total": 3 }
+        }),
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let page = client.execute_page(Domain::list().limit(Some(1))).unwrap();
+
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.total, Some(3));
+    assert_eq!(
+        page.next.as_ref().map(url::Url::as_str),
+        Some("https://api.digitalocean.com/v2/domains?page=3&per_page=1")
+    );
+    assert_eq!(
+        page.prev.as_ref().map(url::Url::as_str),
+        Some("https://api.digitalocean.com/v2/domains?page=1&per_page=1")
+    );
+}
+
+#[test]
+fn page_and_raw_entry_points_validate_and_correlate() {
+    before();
+
+    // Nothing is listening: invalid requests must not be sent.
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(url::Url::parse("http://127.0.0.1:9/v2").unwrap());
+
+    let invalid = || Droplet::list().limit(Some(0)).correlation_id("trace-9");
+
+    for error in [
+        client.list_page(invalid()).unwrap_err(),
+        client.execute_page(invalid()).unwrap_err(),
+        client.send_raw(invalid()).unwrap_err(),
+    ] {
+        assert_eq!(error.correlation_id(), Some("trace-9"));
+        match error {
+            Error::Correlated { source, .. } => match *source {
+                Error::InvalidArgument { ref field, .. } => assert_eq!(field, "limit"),
+                other => panic!("Unexpected source: {:?}", other),
+            },
+            other => panic!("Unexpected error: {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn key_is_not_sent_to_other_hosts() {
+    before();
+
+    // The mock is the client's root, so a different port is another host.
+    let mock = Mock::new(vec![]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+    let elsewhere = url::Url::parse("http://127.0.0.1:9/v2/droplets").unwrap();
+
+    let absolute: Request<Get, Raw<serde_json::Value>> = Request::absolute(elsewhere.clone());
+    let cursor: PageCursor =
+        serde_json::from_value(serde_json::json!(elsewhere.as_str())).unwrap();
+
+    for error in [
+        absolute.execute(&client).unwrap_err(),
+        Droplet::list().resume_from(cursor).execute(&client).unwrap_err(),
+    ] {
+        match error {
+            Error::InvalidArgument { ref field, .. } => assert_eq!(field, "url"),
+            other => panic!("Unexpected error: {:?}", other),
+        }
+    }
+    assert!(mock.requests().is_empty());
+}
+
+#[test]
+fn close_releases_idle_connections() {
+    use std::io::Write;
+    use std::net::TcpListener;
+
+    before();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let root = url::Url::parse(&format!("http://{}/v2", listener.local_addr().unwrap())).unwrap();
+
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0; 1024];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+            let read = stream.read(&mut buf).unwrap();
+            request.extend_from_slice(&buf[..read]);
+        }
+
+        // Keep the connection alive so that it goes back to the pool.
+        let body = account_json().to_string();
+        let head = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+            body.len()
+        );
+        stream.write_all(head.as_bytes()).unwrap();
+        stream.write_all(body.as_bytes()).unwrap();
+
+        // The client closing its pool ends the connection.
+        stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        stream.read(&mut buf)
+    });
+
+    let client = DigitalOcean::new("token").unwrap().with_root(root);
+    Account::get().execute(&client).unwrap();
+    client.close();
+
+    assert_eq!(server.join().unwrap().unwrap(), 0);
+}
+
+#[test]
+fn metrics_count_requests_and_rate_limits() {
+    before();
+
+    let rate_limited = || {
+        json_response(
+            "429 Too Many Requests",
+            &serde_json::json!({ "id": "too_many_requests", "message": "API Rate limit exceeded." }),
+        )
+    };
+    let mock = Mock::new(vec![
+        json_response("200 OK", &account_json()),
+        rate_limited(),
+        json_response("200 OK", &account_json()),
+        rate_limited(),
+    ]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+    let clone = client.clone();
+
+    assert_eq!(client.metrics(), ClientMetrics::default());
+
+    Account::get().execute(&client).unwrap();
+    assert!(Account::get().execute(&client).is_err());
+    Account::get().execute(&clone).unwrap();
+    assert!(Account::get().execute(&clone).is_err());
+
+    let metrics = client.metrics();
+    assert_eq!(metrics.requests, 4);
+    assert_eq!(metrics.rate_limited, 2);
+    assert_eq!(clone.metrics(), metrics);
+}
+
+#[test]
+fn metrics_count_requests_per_endpoint_when_enabled() {
+    before();
+
+    let mock = Mock::new(vec![
+        json_response("200 OK", &account_json()),
+        json_response(
+            "429 Too Many Requests",
+            &serde_json::json!({ "id": "too_many_requests", "message": "API Rate limit exceeded." }),
+        ),
+        json_response("200 OK", &serde_json::json!({ "droplet": droplet_json(1234, "active") })),
+    ]);
+    let mut client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    Account::get().execute(&client).unwrap();
+    assert!(client.metrics().endpoints.is_empty());
+
+    client.set_endpoint_metrics(true);
+    assert!(Droplet::get(1234).execute(&client).is_err());
+    Droplet::get(1234).execute(&client).unwrap();
+
+    let metrics = client.metrics();
+    assert_eq!(metrics.requests, 3);
+    assert_eq!(
+        metrics.endpoints.get("GET /droplets/{id}"),
+        Some(&EndpointMetrics { requests: 2, rate_limited: 1 })
+    );
+    assert_eq!(metrics.endpoints.len(), 1);
+}
+
+#[test]
+fn metrics_count_failed_requests() {
+    before();
+
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let root = url::Url::parse(&format!("http://127.0.0.1:{}/v2", port)).unwrap();
+    let client = DigitalOcean::new("token").unwrap().with_root(root);
+
+    assert!(Account::get().execute(&client).is_err());
+    assert_eq!(client.metrics().requests, 1);
+    assert_eq!(client.metrics().rate_limited, 0);
+}
+
+fn certificate_json(state: &str) -> serde_json::Value {
+    serde_json::json!({
+        "certificate": {
+            "id": "ba9b9c18-6c59-46c2-99df-70da170a42ba",
+            "name": "web",
+            "not_after": "2017-02-22T00:23:00Z",
+            "sha1_fingerprint": if state == "verified" { "479c82b5c63cb6d3e6fac4624d58a33b267e166c" } else { "" },
+            "created_at": "2017-02-08T16:02:37Z",
+            "dns_names": ["example.com", "www.example.com"],
+            "state": state,
+            "type": "lets_encrypt"
+        }
+    })
+}
+
+#[test]
+fn lets_encrypt_and_wait_polls_until_verified() {
+    before();
+
+    let mock = Mock::new(vec![
+        json_response("201 Created", &certificate_json("pending")),
+        json_response("200 OK", &certificate_json("pending")),
+        json_response("200 OK", &certificate_json("verified")),
+    ]);
+    let mut client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+    client.set_poll_interval(Duration::from_millis(1));
+
+    let certificate = client
+        .lets_encrypt_and_wait("web", "example.com", &["www"], Duration::from_secs(10))
+        .unwrap();
+
+    assert_eq!(certificate.state(), "verified");
+    assert_eq!(certificate.kind(), "lets_encrypt");
+    assert!(!certificate.sha1_fingerprint().is_empty());
+
+    let requests = mock.requests();
+    assert!(requests[0].starts_with("POST /v2/certificates "));
+    assert!(requests[0].contains(r#""dns_names":["example.com","www.example.com"]"#));
+    assert!(requests[1].starts_with("GET /v2/certificates/ba9b9c18-6c59-46c2-99df-70da170a42ba "));
+    assert!(requests[2].starts_with("GET /v2/certificates/ba9b9c18-6c59-46c2-99df-70da170a42ba "));
+}
+
+#[test]
+fn lets_encrypt_and_wait_fails_on_error_state() {
+    before();
+
+    let mock = Mock::new(vec![
+        json_response("201 Created", &certificate_json("pending")),
+        json_response("200 OK", &certificate_json("error")),
+    ]);
+    let mut client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+    client.set_poll_interval(Duration::from_millis(1));
+
+    match client.lets_encrypt_and_wait("web", "example.com", &["www"], Duration::from_secs(10)) {
+        Err(Error::CertificateErrored { id, dns_names }) => {
+            assert_eq!(id, "ba9b9c18-6c59-46c2-99df-70da170a42ba");
+            assert_eq!(dns_names, vec!["example.com", "www.example.com"]);
+        }
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn correlation_id_is_sent_and_attached_to_errors() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "404 Not Found",
+        &serde_json::json!({ "id": "not_found", "message": "The resource you were accessing could not be found." }),
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let error = Droplet::get(123)
+        .correlation_id("trace-4f2a")
+        .execute(&client)
+        .unwrap_err();
+
+    assert_eq!(error.correlation_id(), Some("trace-4f2a"));
+    assert!(error.to_string().contains("trace-4f2a"));
+    match error {
+        Error::Correlated { source, .. } => match *source {
+            Error::NotFound { .. } => (),
+            other => panic!("Unexpected source: {:?}", other),
+        },
+        other => panic!("Unexpected error: {:?}", other),
+    }
+
+    let requests = mock.requests();
+    assert!(requests[0].to_lowercase().contains("x-correlation-id: trace-4f2a\r\n"));
+}
+
+#[test]
+fn correlation_id_is_attached_to_validation_errors() {
+    before();
+
+    let client = DigitalOcean::new("token").unwrap();
+    let error = Droplet::list()
+        .limit(Some(0))
+        .correlation_id("trace-1")
+        .correlation_id("trace-2")
+        .execute(&client)
+        .unwrap_err();
+
+    assert_eq!(error.correlation_id(), Some("trace-2"));
+}
+
+#[test]
+fn errors_without_correlation_id_are_not_wrapped() {
+    before();
+
+    let client = DigitalOcean::new("token").unwrap();
+
+    match Droplet::list().limit(Some(0)).execute(&client) {
+        Err(e @ Error::InvalidArgument { .. }) => assert_eq!(e.correlation_id(), None),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
@@ -12,11 +12,13 @@ use serde_json::Value;
 use std::net::IpAddr;
 use std::str::FromStr;
 
-use digitalocean::api::Domain;
+use digitalocean::api::{Domain, HasPagination, HasResponse, HasValue};
+use digitalocean::error::Error;
 use digitalocean::method::{Create, Delete, Get, List};
-use digitalocean::request::Request;
+use digitalocean::request::{Executable, Precondition, Request};
+use digitalocean::DigitalOcean;
 
-use crate::utils::before;
+use crate::utils::{before, json_response, response, Mock};
 
 #[test]
 fn list_produces_correct_request() {
@@ -79,3 +81,142 @@ fn delete_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[test]
+fn list_response_deserializes_and_paginates() {
+    before();
+
+    let body = r#"{
+        "domains": [
+            { "name": "example.com", "ttl": 1800, "zone_file": null },
+            { "name": "example.org", "ttl": 3600, "zone_file": null }
+        ],
+        "links": {
+            "pages": {
+                "next": "https://api.digitalocean.com/v2/domains?page=2&per_page=2",
+                "last": "https://api.digitalocean.com/v2/domains?page=3&per_page=2"
+            }
+        },
+        "meta": { "total": 6 }
+    }"#;
+
+    let response: <Vec<Domain> as HasResponse>::Response = serde_json::from_str(body).unwrap();
+
+    assert_eq!(
+        response.next_page().unwrap().as_str(),
+        "https://api.digitalocean.com/v2/domains?page=2&per_page=2"
+    );
+
+    let domains = response.value();
+    assert_eq!(domains.len(), 2);
+    assert_eq!(domains[1].name(), "example.org");
+}
+
+#[test]
+fn domain_round_trips_through_serde() {
+    before();
+
+    let full = json!({
+        "name": "example.com",
+        "ttl": 1800,
+        "zone_file": "$ORIGIN example.com.\n$TTL 1800\n",
+    });
+    let domain: Domain = serde_json::from_value(full.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&domain).unwrap(), full);
+
+    // A domain which was just created has no zone file yet.
+    let created = json!({ "name": "example.com", "ttl": null, "zone_file": null });
+    let domain: Domain = serde_json::from_value(created).unwrap();
+    let serialized = serde_json::to_value(&domain).unwrap();
+    assert_eq!(serialized, json!({ "name": "example.com" }));
+
+    let again: Domain = serde_json::from_value(serialized.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&again).unwrap(), serialized);
+}
+
+#[test]
+fn delete_records_preconditions() {
+    before();
+
+    let req = Domain::delete("example.com")
+        .expect_exists()
+        .if_match("ttl", json!(1800));
+
+    assert_eq!(
+        req.preconditions(),
+        &[
+            Precondition::Exists,
+            Precondition::Matches("ttl".into(), json!(1800)),
+        ][..]
+    );
+}
+
+#[test]
+fn delete_of_missing_domain_is_aborted() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "404 Not Found",
+        &json!({ "id": "not_found", "message": "The resource you were accessing could not be found." }),
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    match Domain::delete("example.com").expect_exists().execute(&client) {
+        Err(Error::Precondition { .. }) => (),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with("GET /v2/domains/example.com "));
+}
+
+fn domain_json() -> Value {
+    json!({
+        "domain": {
+            "name": "example.com",
+            "ttl": 1800,
+            "zone_file": null
+        }
+    })
+}
+
+#[test]
+fn delete_with_mismatched_field_is_aborted() {
+    before();
+
+    let mock = Mock::new(vec![json_response("200 OK", &domain_json())]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    match Domain::delete("example.com").if_match("ttl", json!(3600)).execute(&client) {
+        Err(Error::Precondition { reason }) => assert!(reason.contains("ttl")),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+    assert_eq!(mock.requests().len(), 1);
+}
+
+#[test]
+fn delete_with_matching_field_proceeds() {
+    before();
+
+    let mock = Mock::new(vec![
+        json_response("200 OK", &domain_json()),
+        response("204 No Content", &[], b""),
+    ]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    Domain::delete("example.com")
+        .if_match("name", json!("example.com"))
+        .execute(&client)
+        .unwrap();
+
+    let requests = mock.requests();
+    assert!(requests[0].starts_with("GET /v2/domains/example.com "));
+    assert!(requests[1].starts_with("DELETE /v2/domains/example.com "));
+}
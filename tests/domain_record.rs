@@ -122,3 +122,48 @@ fn delete_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[test]
+fn record_round_trips_through_serde() {
+    before();
+
+    let srv = json!({
+        "id": 3352896,
+        "type": "SRV",
+        "name": "_xmpp._tcp",
+        "data": "xmpp.example.com",
+        "priority": 10,
+        "port": 5222,
+        "ttl": 1800,
+        "weight": 5,
+    });
+    let record: DomainRecord = serde_json::from_value(srv.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&record).unwrap(), srv);
+
+    // Fields which do not apply to a record are left out rather than null.
+    let a = json!({
+        "id": 3352892,
+        "type": "A",
+        "name": "@",
+        "data": "1.2.3.4",
+        "priority": null,
+        "port": null,
+        "ttl": 1800,
+        "weight": null,
+    });
+    let record: DomainRecord = serde_json::from_value(a).unwrap();
+    let serialized = serde_json::to_value(&record).unwrap();
+    assert_eq!(
+        serialized,
+        json!({
+            "id": 3352892,
+            "type": "A",
+            "name": "@",
+            "data": "1.2.3.4",
+            "ttl": 1800,
+        })
+    );
+
+    let again: DomainRecord = serde_json::from_value(serialized.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&again).unwrap(), serialized);
+}
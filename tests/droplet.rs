@@ -10,11 +10,18 @@ mod utils;
 
 use serde_json::Value;
 
-use digitalocean::api::{Droplet, Snapshot};
+use digitalocean::api::droplet_fields::{DropletImage, DropletStatus, ListFilter};
+use digitalocean::api::ssh_key_fields::SshKeyIdent;
+use digitalocean::api::{
+    Domain, Droplet, DropletBackupPolicy, Firewall, DropletResponse, HasResponse, HasValue, Raw,
+    Snapshot, SupportedBackupPolicy, Tag,
+};
+use digitalocean::error::Error;
 use digitalocean::method::{Create, Delete, Get, List};
-use digitalocean::request::Request;
+use digitalocean::request::{Executable, FieldDescriptor, Request};
+use digitalocean::DigitalOcean;
 
-use crate::utils::before;
+use crate::utils::{before, droplet_json, json_response, response, Mock};
 
 #[test]
 fn create_produces_correct_request() {
@@ -45,6 +52,64 @@ fn create_produces_correct_request() {
     );
 }
 
+#[test]
+fn create_from_snapshot_id_sends_number() {
+    before();
+
+    let req: Request<Create, Droplet> =
+        Droplet::create("bear", "tor1", "5gb", DropletImage::Id(6372321));
+    info!("{:#?}", req);
+
+    assert_eq!(req.body()["image"], json!(6372321));
+
+    let snapshot_id: usize = 6372321;
+    let req: Request<Create, Droplet> = Droplet::create("bear", "tor1", "5gb", snapshot_id);
+
+    assert_eq!(req.body()["image"], json!(6372321));
+}
+
+#[test]
+fn create_accepts_integer_literals_as_ids() {
+    before();
+
+    let req: Request<Create, Droplet> = Droplet::create("bear", "tor1", "5gb", 6372321)
+        .ssh_keys(vec![123, 456]);
+    info!("{:#?}", req);
+
+    assert_eq!(req.body()["image"], json!(6372321));
+    assert_eq!(req.body()["ssh_keys"], json!([123, 456]));
+    assert!(req.validate().is_ok());
+}
+
+#[test]
+fn create_rejects_negative_ids() {
+    before();
+
+    let req: Request<Create, Droplet> = Droplet::create("bear", "tor1", "5gb", -1);
+    match req.validate() {
+        Err(Error::InvalidArgument { ref field, .. }) => assert_eq!(field, "image"),
+        other => panic!("expected an invalid image, got {:?}", other),
+    }
+
+    let req: Request<Create, Droplet> =
+        Droplet::create("bear", "tor1", "5gb", "ubuntu-22-04-x64").ssh_keys(vec![123, -456]);
+    match req.validate() {
+        Err(Error::InvalidArgument { ref field, .. }) => assert_eq!(field, "ssh_keys"),
+        other => panic!("expected an invalid SSH key, got {:?}", other),
+    }
+}
+
+#[test]
+fn droplet_image_serializes_slug_as_string_and_id_as_number() {
+    before();
+
+    let slug = DropletImage::from("ubuntu-20-04-x64");
+    assert_eq!(serde_json::to_value(&slug).unwrap(), json!("ubuntu-20-04-x64"));
+
+    let id = DropletImage::from(6372321u64);
+    assert_eq!(serde_json::to_value(&id).unwrap(), json!(6372321));
+}
+
 #[test]
 fn create_many_produces_correct_request() {
     before();
@@ -150,6 +215,84 @@ fn delete_by_tag_produces_correct_request() {
     assert_eq!(*req.body(), Value::Null);
 }
 
+#[test]
+fn list_filtered_produces_correct_request() {
+    before();
+
+    let req: Request<List, Vec<Droplet>> = Droplet::list_filtered(ListFilter {
+        tag: Some("web".to_string()),
+        name: None,
+    });
+    info!("{:#?}", req);
+
+    assert_eq!(
+        req.url().as_str(),
+        "https://api.digitalocean.com/v2/droplets?tag_name=web"
+    );
+    assert!(req.validate().is_ok());
+
+    let req: Request<List, Vec<Droplet>> = Droplet::list_filtered(ListFilter {
+        tag: None,
+        name: Some("app 1".to_string()),
+    });
+    info!("{:#?}", req);
+
+    assert_eq!(
+        req.url().as_str(),
+        "https://api.digitalocean.com/v2/droplets?name=app+1"
+    );
+    assert!(req.validate().is_ok());
+}
+
+#[test]
+fn list_filtered_rejects_tag_and_name_together() {
+    before();
+
+    let req: Request<List, Vec<Droplet>> = Droplet::list_filtered(ListFilter {
+        tag: Some("web".to_string()),
+        name: Some("app-1".to_string()),
+    });
+    info!("{:#?}", req);
+
+    assert_eq!(
+        req.url().as_str(),
+        "https://api.digitalocean.com/v2/droplets?tag_name=web&name=app-1"
+    );
+    match req.validate() {
+        Err(Error::InvalidArgument { ref field, .. }) if field == "name" => (),
+        other => panic!("Expected name to be invalid, got {:?}", other),
+    }
+}
+
+#[test]
+fn delete_by_tag_sends_delete() {
+    before();
+
+    let mock = Mock::new(vec![response("204 No Content", &[], b"")]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let req: Request<Delete, ()> = Droplet::delete_by_tag("foo");
+    req.execute(&client).unwrap();
+
+    let requests = mock.requests();
+    assert!(requests[0].starts_with("DELETE /v2/droplets?tag_name=foo "));
+}
+
+#[test]
+fn delete_by_tag_rejects_empty_tag() {
+    before();
+
+    let req: Request<Delete, ()> = Droplet::delete_by_tag("");
+    info!("{:#?}", req);
+
+    match req.validate() {
+        Err(Error::InvalidArgument { ref field, .. }) if field == "tag_name" => (),
+        other => panic!("Expected tag_name to be invalid, got {:?}", other),
+    }
+}
+
 #[test]
 fn neighbors_produces_correct_request() {
     before();
@@ -213,3 +356,675 @@ fn get_neighbors_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[test]
+fn user_data_is_limited_to_64_kib() {
+    before();
+
+    let fits = "a".repeat(64 * 1024);
+    let req: Request<Create, Droplet> =
+        Droplet::create("bear", "tor1", "5gb", "ubuntu-14-04-x64").user_data(fits.as_str());
+
+    assert!(req.validate().is_ok());
+    assert_eq!(req.body()["user_data"], json!(fits));
+
+    let too_big = "a".repeat(65 * 1024);
+    let req: Request<Create, Droplet> =
+        Droplet::create("bear", "tor1", "5gb", "ubuntu-14-04-x64").user_data(too_big.as_str());
+
+    match req.validate() {
+        Err(Error::InvalidArgument { ref field, .. }) if field == "user_data" => (),
+        other => panic!("Expected user_data to be invalid, got {:?}", other),
+    }
+
+    // The limit applies to the encoded size, not the number of characters.
+    let multibyte = "\u{e9}".repeat(33 * 1024);
+    let req: Request<Create, Droplet> =
+        Droplet::create("bear", "tor1", "5gb", "ubuntu-14-04-x64").user_data(multibyte.as_str());
+
+    match req.validate() {
+        Err(Error::InvalidArgument { ref field, .. }) if field == "user_data" => (),
+        other => panic!("Expected user_data to be invalid, got {:?}", other),
+    }
+}
+
+#[test]
+fn create_rejects_empty_name() {
+    before();
+
+    let req: Request<Create, Droplet> = Droplet::create("", "tor1", "5gb", "ubuntu-14-04-x64");
+    info!("{:#?}", req);
+
+    match req.validate() {
+        Err(Error::InvalidArgument { ref field, .. }) if field == "name" => (),
+        other => panic!("Expected name to be invalid, got {:?}", other),
+    }
+}
+
+#[test]
+fn create_rejects_bad_region_slug() {
+    before();
+
+    for region in &["Toronto", "tor", "TOR1", "1"] {
+        let req: Request<Create, Droplet> =
+            Droplet::create("bear", region, "5gb", "ubuntu-14-04-x64");
+        info!("{:#?}", req);
+
+        match req.validate() {
+            Err(Error::InvalidArgument { ref field, .. }) if field == "region" => (),
+            other => panic!("Expected {} to be invalid, got {:?}", region, other),
+        }
+    }
+}
+
+#[test]
+fn list_rejects_zero_limit() {
+    before();
+
+    let req: Request<List, Vec<Droplet>> = Droplet::list().limit(Some(0));
+    info!("{:#?}", req);
+
+    match req.validate() {
+        Err(Error::InvalidArgument { ref field, .. }) if field == "limit" => (),
+        other => panic!("Expected limit to be invalid, got {:?}", other),
+    }
+}
+
+#[test]
+fn backup_policy_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/droplets/123/backups/policy";
+
+    let req: Request<Get, DropletBackupPolicy> = Droplet::get(123).backup_policy();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn backup_policies_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/droplets/backups/policies";
+
+    let req: Request<List, Vec<DropletBackupPolicy>> = Droplet::backup_policies();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn backup_policies_response_deserializes() {
+    before();
+
+    let body = json!({
+        "policies": {
+            "436444618": {
+                "droplet_id": 436444618,
+                "backup_enabled": false,
+            },
+            "123": {
+                "droplet_id": 123,
+                "backup_enabled": true,
+                "backup_policy": {
+                    "plan": "weekly",
+                    "weekday": "SUN",
+                    "hour": 20,
+                    "window_length_hours": 4,
+                    "retention_period_days": 28,
+                },
+                "next_backup_window": {
+                    "start": "2024-09-15T20:00:00Z",
+                    "end": "2024-09-16T00:00:00Z",
+                },
+            },
+        },
+        "links": {},
+        "meta": { "total": 2 },
+    });
+
+    let response: <Vec<DropletBackupPolicy> as HasResponse>::Response =
+        serde_json::from_value(body).unwrap();
+    let policies = response.value();
+
+    assert_eq!(*policies[0].droplet_id(), 123);
+    assert_eq!(
+        policies[0].backup_policy().as_ref().unwrap().window_length_hours,
+        Some(4)
+    );
+    assert!(!policies[1].backup_enabled());
+}
+
+#[test]
+fn backup_policies_response_without_links_deserializes() {
+    before();
+
+    let body = json!({
+        "policies": {
+            "123": { "droplet_id": 123, "backup_enabled": false },
+        },
+    });
+
+    let response: <Vec<DropletBackupPolicy> as HasResponse>::Response =
+        serde_json::from_value(body).unwrap();
+    assert_eq!(response.value().len(), 1);
+}
+
+#[test]
+fn supported_backup_policies_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/droplets/backups/supported_policies";
+
+    let req: Request<Get, Vec<SupportedBackupPolicy>> = Droplet::supported_backup_policies();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn supported_backup_policies_response_deserializes() {
+    before();
+
+    let body = json!({
+        "supported_policies": [
+            {
+                "name": "weekly",
+                "possible_window_starts": [0, 4, 8, 12, 16, 20],
+                "window_length_hours": 4,
+                "retention_period_days": 28,
+                "possible_days": ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"],
+            },
+            {
+                "name": "daily",
+                "possible_window_starts": [0, 4, 8, 12, 16, 20],
+                "window_length_hours": 4,
+                "retention_period_days": 7,
+                "possible_days": [],
+            },
+        ]
+    });
+
+    let response: <Vec<SupportedBackupPolicy> as HasResponse>::Response =
+        serde_json::from_value(body).unwrap();
+    let policies = response.value();
+
+    assert_eq!(policies.len(), 2);
+    assert_eq!(policies[0].name(), "weekly");
+    assert_eq!(*policies[1].retention_period_days(), 7);
+}
+
+#[test]
+fn status_deserializes_each_variant() {
+    before();
+
+    let statuses = vec![
+        ("new", DropletStatus::New),
+        ("active", DropletStatus::Active),
+        ("off", DropletStatus::Off),
+        ("archive", DropletStatus::Archive),
+        ("migrating", DropletStatus::Other("migrating".to_string())),
+    ];
+
+    for (raw, status) in statuses {
+        let droplet: Droplet = serde_json::from_value(droplet_json(3164444, raw)).unwrap();
+
+        assert_eq!(*droplet.status(), status);
+        assert_eq!(droplet.status(), raw);
+        assert_eq!(droplet.status().is_running(), raw == "active");
+        assert_eq!(serde_json::to_value(droplet.status()).unwrap(), Value::from(raw));
+    }
+}
+
+fn droplet_in(id: usize, region: &str) -> serde_json::Value {
+    let mut droplet = droplet_json(id, "active");
+    droplet["region"]["slug"] = serde_json::json!(region);
+    droplet
+}
+
+#[test]
+fn list_grouped_by_region_groups_every_page() {
+    before();
+
+    let mock = Mock::new(vec![
+        json_response(
+            "200 OK",
+            &serde_json::json!({
+                "droplets": [droplet_in(1, "nyc3"), droplet_in(2, "ams3")],
+                "links": {
+                    "pages": {
+                        "next": "https://api.digitalocean.com/v2/droplets?page=2&per_page=2"
+                    }
+                },
+                "meta": { "total": 3 }
+            }),
+        ),
+        json_response(
+            "200 OK",
+            &serde_json::json!({
+                "droplets": [droplet_in(3, "nyc3")],
+                "links": {},
+                "meta": { "total": 3 }
+            }),
+        ),
+    ]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let groups = Droplet::list_grouped_by_region().execute(&client).unwrap();
+
+    assert_eq!(groups.len(), 2);
+    let nyc3: Vec<usize> = groups["nyc3"].iter().map(|d| *d.id()).collect();
+    let ams3: Vec<usize> = groups["ams3"].iter().map(|d| *d.id()).collect();
+    assert_eq!(nyc3, vec![1, 3]);
+    assert_eq!(ams3, vec![2]);
+}
+
+fn create_response_json() -> Value {
+    serde_json::json!({
+        "droplet": droplet_json(3164494, "new"),
+        "links": {
+            "actions": [{
+                "id": 36805096,
+                "rel": "create",
+                "href": "https://api.digitalocean.com/v2/actions/36805096"
+            }]
+        }
+    })
+}
+
+#[test]
+fn create_response_exposes_create_action() {
+    before();
+
+    let response: DropletResponse = serde_json::from_value(create_response_json()).unwrap();
+
+    assert_eq!(response.create_action_id(), Some(36805096));
+    assert_eq!(*response.droplet().id(), 3164494);
+    assert_eq!(*response.value().id(), 3164494);
+}
+
+#[test]
+fn response_without_links_has_no_create_action() {
+    before();
+
+    let body = serde_json::json!({ "droplet": droplet_json(3164494, "active") });
+    let response: DropletResponse = serde_json::from_value(body).unwrap();
+
+    assert_eq!(response.create_action_id(), None);
+}
+
+#[test]
+fn create_full_response_returns_create_action() {
+    before();
+
+    let mock = Mock::new(vec![json_response("202 Accepted", &create_response_json())]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let Raw(response) = Droplet::create("bear", "tor1", "s-1vcpu-1gb", "ubuntu-18-04-x64")
+        .full_response()
+        .execute(&client)
+        .unwrap();
+
+    assert_eq!(response.create_action_id(), Some(36805096));
+    assert_eq!(*response.droplet().status(), DropletStatus::New);
+}
+
+#[test]
+fn create_with_mixed_ssh_keys() {
+    before();
+
+    let fingerprint = "aa:bb:cc:dd:ee:ff:00:11:22:33:44:55:66:77:88:99";
+    let req = Droplet::create("bear", "tor1", "5gb", "ubuntu-14-04-x64")
+        .ssh_keys(vec![SshKeyIdent::Id(12345), fingerprint.into()]);
+
+    assert_eq!(req.body()["ssh_keys"], serde_json::json!([12345, fingerprint]));
+}
+
+#[test]
+fn create_multiple_with_ssh_key_ids() {
+    before();
+
+    let req = Droplet::create_multiple(vec!["bear", "badger"], "tor1", "5gb", "ubuntu-14-04-x64")
+        .ssh_keys(vec![12345usize, 67890]);
+
+    assert_eq!(req.body()["ssh_keys"], serde_json::json!([12345, 67890]));
+}
+
+#[test]
+fn list_firewalls_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/droplets/123/firewalls";
+
+    let req: Request<List, Vec<Firewall>> = Droplet::list_firewalls(123);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+    assert_eq!(Droplet::get(123).firewalls().url(), req.url());
+}
+
+#[test]
+fn list_firewalls_deserializes() {
+    before();
+
+    let body = r#"{
+        "firewalls": [
+            {
+                "id": "bb4b2611-3d72-467b-8602-280330ecd65c",
+                "status": "succeeded",
+                "created_at": "2020-05-23T21:24:00Z",
+                "pending_changes": [],
+                "name": "firewall",
+                "droplet_ids": [123],
+                "tags": ["web"],
+                "inbound_rules": [
+                    {
+                        "protocol": "tcp",
+                        "ports": "22",
+                        "sources": { "addresses": ["0.0.0.0/0", "::/0"] }
+                    }
+                ],
+                "outbound_rules": [
+                    {
+                        "protocol": "icmp",
+                        "ports": "0",
+                        "destinations": { "addresses": ["0.0.0.0/0", "::/0"] }
+                    }
+                ]
+            }
+        ],
+        "links": {},
+        "meta": { "total": 1 }
+    }"#;
+
+    let response: <Vec<Firewall> as HasResponse>::Response = serde_json::from_str(body).unwrap();
+    let firewalls = response.value();
+
+    assert_eq!(firewalls.len(), 1);
+    assert_eq!(firewalls[0].name(), "firewall");
+    assert_eq!(firewalls[0].droplet_ids(), &vec![123]);
+    assert_eq!(firewalls[0].inbound_rules()[0].ports, "22");
+    assert_eq!(firewalls[0].outbound_rules()[0].protocol, "icmp");
+}
+
+fn distributions_json(slugs: &[&str]) -> Value {
+    let images: Vec<Value> = slugs
+        .iter()
+        .enumerate()
+        .map(|(id, slug)| {
+            serde_json::json!({
+                "id": id,
+                "name": slug,
+                "type": "base",
+                "distribution": "Ubuntu",
+                "slug": slug,
+                "public": true,
+                "regions": ["nyc3"],
+                "min_disk_size": 20,
+                "size_gigabytes": 2.36,
+                "created_at": "2022-04-22T10:00:00Z",
+            })
+        })
+        .collect();
+    serde_json::json!({ "images": images, "links": {}, "meta": { "total": slugs.len() } })
+}
+
+#[test]
+fn image_slug_check_suggests_near_slugs() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "200 OK",
+        &distributions_json(&["ubuntu-22-04-x64", "debian-12-x64"]),
+    )]);
+    let mut client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+    client.set_image_slug_check(true);
+
+    let result = Droplet::create("bear", "tor1", "s-1vcpu-1gb", "ubuntu-22-04-x65").execute(&client);
+
+    match result {
+        Err(Error::InvalidArgument { field, reason }) => {
+            assert_eq!(field, "image");
+            assert!(reason.contains("did you mean `ubuntu-22-04-x64`"), "{}", reason);
+            assert!(!reason.contains("debian"), "{}", reason);
+        }
+        other => panic!("expected an invalid argument, got {:?}", other),
+    }
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with("GET /v2/images?type=distribution"), "{}", requests[0]);
+}
+
+#[test]
+fn image_slug_check_passes_known_slugs_and_caches_them() {
+    before();
+
+    let mock = Mock::new(vec![
+        json_response("200 OK", &distributions_json(&["ubuntu-22-04-x64"])),
+        json_response("202 Accepted", &create_response_json()),
+        json_response("202 Accepted", &create_response_json()),
+    ]);
+    let mut client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+    client.set_image_slug_check(true);
+
+    for _ in 0..2 {
+        Droplet::create("bear", "tor1", "s-1vcpu-1gb", "ubuntu-22-04-x64")
+            .execute(&client)
+            .unwrap();
+    }
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 3);
+    assert!(requests[1].starts_with("POST /v2/droplets"), "{}", requests[1]);
+    assert!(requests[2].starts_with("POST /v2/droplets"), "{}", requests[2]);
+}
+
+fn field(name: &str, kind: &'static str, required: bool) -> FieldDescriptor {
+    FieldDescriptor {
+        name: name.to_string(),
+        kind,
+        required,
+    }
+}
+
+#[test]
+fn droplet_round_trips_through_serde() {
+    before();
+
+    let droplet: Droplet = serde_json::from_value(droplet_json(3164444, "active")).unwrap();
+    let serialized = serde_json::to_value(&droplet).unwrap();
+
+    // Unset optional fields are left out rather than sent as `null`.
+    assert!(serialized.get("kernel").is_none());
+    assert!(serialized.get("next_backup_window").is_none());
+    assert!(serialized["image"].get("slug").is_some());
+
+    let again: Droplet = serde_json::from_value(serialized.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&again).unwrap(), serialized);
+}
+
+#[test]
+fn create_describes_itself() {
+    before();
+
+    let description = Droplet::create("bear", "tor1", "s-1vcpu-1gb", "ubuntu-22-04-x64")
+        .backups(true)
+        .tags(vec!["web".to_string()])
+        .describe();
+
+    assert_eq!(description.method, "POST");
+    assert_eq!(description.path, "/droplets");
+    assert_eq!(
+        description.fields,
+        vec![
+            field("backups", "boolean", false),
+            field("image", "string", true),
+            field("name", "string", true),
+            field("region", "string", true),
+            field("size", "string", true),
+            field("tags", "array", false),
+        ]
+    );
+    assert_eq!(description.response, "Droplet");
+}
+
+#[test]
+fn describe_templates_ids_in_the_path() {
+    before();
+
+    let description = Droplet::get(1234).snapshots().describe();
+
+    assert_eq!(description.method, "GET");
+    assert_eq!(description.path, "/droplets/{id}/snapshots");
+    assert!(description.fields.is_empty());
+    assert_eq!(description.response, "Vec<Snapshot>");
+}
+
+#[test]
+fn describe_names_path_parameters() {
+    before();
+
+    assert_eq!(Tag::get("production").describe().path, "/tags/{name}");
+    assert_eq!(
+        Domain::get("example.com").records().get(3352896).describe().path,
+        "/domains/{name}/records/{record_id}"
+    );
+    assert_eq!(Droplet::get(1234).action(36804636).describe().path, "/droplets/{id}/actions/{action_id}");
+}
+
+#[test]
+fn describe_lists_extra_fields_as_optional() {
+    before();
+
+    let description = Droplet::create("bear", "tor1", "s-1vcpu-1gb", "ubuntu-22-04-x64")
+        .extra_field("with_droplet_agent", json!(true))
+        .describe();
+
+    assert!(description.fields.contains(&field("with_droplet_agent", "boolean", false)));
+    assert!(description.fields.contains(&field("name", "string", true)));
+}
+
+#[test]
+fn instances_creates_many_from_a_template() {
+    before();
+
+    let template = Droplet::create("web", "nyc3", "s-1vcpu-1gb", "ubuntu-22-04-x64").monitoring(true);
+    let req: Request<Create, Vec<Droplet>> = template.instances(vec!["web-1", "web-2", "web-3"]);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), "https://api.digitalocean.com/v2/droplets");
+    assert_eq!(
+        *req.body(),
+        json!({
+            "names": ["web-1", "web-2", "web-3"],
+            "region": "nyc3",
+            "size": "s-1vcpu-1gb",
+            "image": "ubuntu-22-04-x64",
+            "monitoring": true,
+        })
+    );
+    assert!(req.validate().is_ok());
+    assert!(req.describe().fields.iter().any(|f| f.name == "names" && f.required));
+}
+
+#[test]
+fn instances_ignores_the_template_name() {
+    before();
+
+    let req = Droplet::create("", "nyc3", "s-1vcpu-1gb", "ubuntu-22-04-x64").instances(vec!["web-1", "web-2"]);
+    assert!(req.validate().is_ok());
+
+    let req = Droplet::create("", "nyc", "s-1vcpu-1gb", "ubuntu-22-04-x64").instances(vec!["web-1"]);
+    match req.validate() {
+        Err(Error::InvalidArgument { ref field, .. }) => assert_eq!(field, "region"),
+        other => panic!("expected an invalid region, got {:?}", other),
+    }
+}
+
+#[test]
+fn instances_rejects_more_than_ten_names() {
+    before();
+
+    let names: Vec<String> = (1..=11).map(|i| format!("web-{}", i)).collect();
+    let req = Droplet::create("web", "nyc3", "s-1vcpu-1gb", "ubuntu-22-04-x64").instances(names.clone());
+    match req.validate() {
+        Err(Error::InvalidArgument { ref field, .. }) => assert_eq!(field, "names"),
+        other => panic!("expected too many names, got {:?}", other),
+    }
+
+    let req = Droplet::create_multiple(names, "nyc3", "s-1vcpu-1gb".to_string(), "ubuntu-22-04-x64");
+    assert!(req.validate().is_err());
+}
+
+#[test]
+fn instances_rejects_duplicate_and_empty_names() {
+    before();
+
+    let template = || Droplet::create("web", "nyc3", "s-1vcpu-1gb", "ubuntu-22-04-x64");
+
+    for names in [vec![], vec!["web-1", ""], vec!["web-1", "web-2", "web-1"]] {
+        match template().instances(names.clone()).validate() {
+            Err(Error::InvalidArgument { field, .. }) => assert_eq!(field, "names"),
+            other => panic!("expected {:?} to be rejected, got {:?}", names, other),
+        }
+    }
+}
+
+#[test]
+fn get_missing_droplet_is_not_found() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "404 Not Found",
+        &serde_json::json!({
+            "id": "not_found",
+            "message": "The resource you were accessing could not be found."
+        }),
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let error = Droplet::get(999999).execute(&client).unwrap_err();
+
+    match error {
+        Error::NotFound { ref resource, ref id } => {
+            assert_eq!(resource, "droplets");
+            assert_eq!(id.as_deref(), Some("999999"));
+        }
+        ref other => panic!("Unexpected error: {:?}", other),
+    }
+    assert_eq!(error.to_string(), "Not Found: droplets `999999`");
+    mock.requests();
+}
+
+#[test]
+fn list_of_missing_droplet_is_not_found_without_id() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "404 Not Found",
+        &serde_json::json!({ "id": "not_found", "message": "Not found." }),
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    match Droplet::get(999999).snapshots().execute(&client) {
+        Err(Error::NotFound { resource, id: None }) => assert_eq!(resource, "snapshots"),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
@@ -10,7 +10,9 @@ mod utils;
 
 use serde_json::Value;
 
-use digitalocean::api::{Action, Droplet};
+use digitalocean::api::droplet_fields::{BackupPolicy, BulkAction, ResizeKind};
+use digitalocean::api::{Action, Droplet, Target};
+use digitalocean::error::Error;
 use digitalocean::method::{Create, Get, List};
 use digitalocean::request::Request;
 
@@ -43,7 +45,7 @@ fn enable_backups_produces_correct_request() {
         droplet_id
     );
 
-    let req: Request<Create, Action> = Droplet::get(droplet_id).enable_backups();
+    let req: Request<Create, Action> = Droplet::get(droplet_id).enable_backups(None);
     info!("{:#?}", req);
 
     assert_eq!(req.url().as_str(), correct_url);
@@ -55,6 +57,47 @@ fn enable_backups_produces_correct_request() {
     );
 }
 
+#[test]
+fn enable_backups_with_policy_produces_correct_request() {
+    before();
+
+    let policy = BackupPolicy::new("weekly").weekday("SUN").hour(20);
+    let req: Request<Create, Action> = Droplet::get(123).enable_backups(Some(policy));
+    info!("{:#?}", req);
+
+    assert!(req.validate().is_ok());
+    assert_eq!(
+        *req.body(),
+        json!({
+            "type": "enable_backups",
+            "backup_policy": {
+                "plan": "weekly",
+                "weekday": "SUN",
+                "hour": 20,
+            },
+        })
+    );
+}
+
+#[test]
+fn enable_backups_rejects_bad_policy() {
+    before();
+
+    for policy in [
+        BackupPolicy::new("weekly").weekday("Sunday"),
+        BackupPolicy::new("daily").hour(3),
+        BackupPolicy::new("daily").hour(24),
+    ] {
+        let req: Request<Create, Action> = Droplet::get(123).enable_backups(Some(policy));
+        info!("{:#?}", req);
+
+        match req.validate() {
+            Err(Error::InvalidArgument { ref field, .. }) if field == "backup_policy" => (),
+            other => panic!("Expected backup_policy to be invalid, got {:?}", other),
+        }
+    }
+}
+
 #[test]
 fn disable_backups_produces_correct_request() {
     before();
@@ -203,8 +246,7 @@ fn restore_produces_correct_request() {
     );
 
     // As id
-    let image_id = 456;
-    let req: Request<Create, Action> = Droplet::get(droplet_id).restore(image_id);
+    let req: Request<Create, Action> = Droplet::get(droplet_id).restore(456);
     info!("{:#?}", req);
 
     assert_eq!(req.url().as_str(), correct_url);
@@ -212,11 +254,26 @@ fn restore_produces_correct_request() {
         *req.body(),
         json!({
             "type": "restore",
-            "image": image_id.to_string(),
+            "image": 456,
         })
     );
 }
 
+#[test]
+fn restore_rejects_non_positive_image_id() {
+    before();
+
+    for image_id in &[0, -456] {
+        let req: Request<Create, Action> = Droplet::get(123).restore(*image_id);
+        info!("{:#?}", req);
+
+        match req.validate() {
+            Err(Error::InvalidArgument { ref field, .. }) if field == "image" => (),
+            other => panic!("Expected image to be invalid, got {:?}", other),
+        }
+    }
+}
+
 #[test]
 fn password_reset_produces_correct_request() {
     before();
@@ -240,7 +297,7 @@ fn password_reset_produces_correct_request() {
 }
 
 #[test]
-fn resize_produces_correct_request() {
+fn resize_cpu_only_produces_correct_request() {
     before();
 
     let droplet_id = 123;
@@ -248,9 +305,9 @@ fn resize_produces_correct_request() {
         "https://api.digitalocean.com/v2/droplets/{}/actions",
         droplet_id
     );
-    let (size, disk) = ("1gb", false);
+    let size = "s-2vcpu-2gb";
 
-    let req: Request<Create, Action> = Droplet::get(droplet_id).resize(size, disk);
+    let req: Request<Create, Action> = Droplet::get(droplet_id).resize(size, ResizeKind::CpuOnly);
     info!("{:#?}", req);
 
     assert_eq!(req.url().as_str(), correct_url);
@@ -259,11 +316,46 @@ fn resize_produces_correct_request() {
         json!({
             "type": "resize",
             "size": size,
-            "disk": disk,
+            "disk": false,
+        })
+    );
+    assert!(req.validate().is_ok());
+}
+
+#[test]
+fn resize_with_disk_produces_correct_request() {
+    before();
+
+    let droplet_id = 123;
+    let size = "s-2vcpu-2gb";
+
+    let req: Request<Create, Action> =
+        Droplet::get(droplet_id).resize(size, ResizeKind::PermanentWithDisk);
+    info!("{:#?}", req);
+
+    assert_eq!(
+        *req.body(),
+        json!({
+            "type": "resize",
+            "size": size,
+            "disk": true,
         })
     );
 }
 
+#[test]
+fn resize_rejects_invalid_size() {
+    before();
+
+    let req: Request<Create, Action> = Droplet::get(123).resize("2 GB", ResizeKind::CpuOnly);
+    info!("{:#?}", req);
+
+    match req.validate() {
+        Err(Error::InvalidArgument { ref field, .. }) if field == "size" => (),
+        other => panic!("Expected size to be invalid, got {:?}", other),
+    }
+}
+
 #[test]
 fn rebuild_produces_correct_request() {
     before();
@@ -421,3 +513,39 @@ fn get_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[test]
+fn bulk_action_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/droplets/actions?tag_name=staging";
+
+    let req: Request<Create, Vec<Action>> =
+        Droplet::bulk_action(Target::tag("staging"), BulkAction::PowerOff);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), json!({ "type": "power_off" }));
+    assert!(req.validate().is_ok());
+
+    let req = Droplet::bulk_action(Target::tag("staging"), BulkAction::Snapshot("nightly".into()));
+    assert_eq!(*req.body(), json!({ "type": "snapshot", "name": "nightly" }));
+}
+
+#[test]
+fn bulk_action_requires_a_single_tag() {
+    before();
+
+    let targets = [
+        Target::Droplets(vec![1, 2]),
+        Target::Tags(vec!["a".into(), "b".into()]),
+        Target::Tags(vec![]),
+    ];
+
+    for target in targets.iter() {
+        match Droplet::bulk_action(target.clone(), BulkAction::PowerOn).validate() {
+            Err(Error::InvalidArgument { field, .. }) => assert_eq!(field, "target"),
+            other => panic!("expected {:?} to be rejected, got {:?}", target, other),
+        }
+    }
+}
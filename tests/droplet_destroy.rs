@@ -0,0 +1,209 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate url;
+extern crate url_serde;
+
+mod utils;
+
+use serde_json::Value;
+
+use digitalocean::api::destroy_fields::SelectiveDestroy;
+use digitalocean::api::{AssociatedResources, DestroyStatus, Droplet, HasResponse, HasValue};
+use digitalocean::error::Error;
+use digitalocean::method::{Create, Delete, Get};
+use digitalocean::request::{Executable, Request};
+use digitalocean::DigitalOcean;
+
+use crate::utils::{before, response, Mock};
+
+fn selection() -> SelectiveDestroy {
+    SelectiveDestroy {
+        reserved_ips: vec!["6186916".to_string()],
+        snapshots: vec!["61486916".to_string()],
+        volumes: vec!["ba49449a-7435-11ea-b89e-0a58ac14480f".to_string()],
+        volume_snapshots: vec![],
+    }
+}
+
+#[test]
+fn associated_resources_produces_correct_request() {
+    before();
+
+    let correct_url =
+        "https://api.digitalocean.com/v2/droplets/123/destroy_with_associated_resources";
+
+    let req: Request<Get, AssociatedResources> = Droplet::get(123).associated_resources();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn associated_resources_response_deserializes() {
+    before();
+
+    let body = json!({
+        "reserved_ips": [{ "id": "6186916", "name": "45.55.96.47", "cost": "4.00" }],
+        "floating_ips": [],
+        "snapshots": [{ "id": "61486916", "name": "ubuntu-s-1vcpu-1gb-nyc1-01-1585758823330", "cost": "0.05" }],
+        "volumes": [],
+        "volume_snapshots": [],
+    });
+
+    let response: <AssociatedResources as HasResponse>::Response =
+        serde_json::from_value(body).unwrap();
+    let resources = response.value();
+
+    assert_eq!(resources.reserved_ips()[0].id, "6186916");
+    assert_eq!(resources.snapshots()[0].cost, Some("0.05".to_string()));
+}
+
+#[test]
+fn destroy_selective_produces_correct_request() {
+    before();
+
+    let correct_url =
+        "https://api.digitalocean.com/v2/droplets/123/destroy_with_associated_resources/selective";
+
+    let req: Request<Delete, ()> = Droplet::get(123).destroy_selective(selection(), true);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "reserved_ips": ["6186916"],
+            "snapshots": ["61486916"],
+            "volumes": ["ba49449a-7435-11ea-b89e-0a58ac14480f"],
+        })
+    );
+    assert!(req.validate().is_ok());
+}
+
+#[test]
+fn destroy_selective_requires_confirmation() {
+    before();
+
+    let req: Request<Delete, ()> = Droplet::get(123).destroy_selective(selection(), false);
+    info!("{:#?}", req);
+
+    match req.validate() {
+        Err(Error::InvalidArgument { ref field, .. }) if field == "confirm" => (),
+        other => panic!("Expected confirm to be invalid, got {:?}", other),
+    }
+}
+
+#[test]
+fn destroy_selective_requires_resources() {
+    before();
+
+    let req: Request<Delete, ()> =
+        Droplet::get(123).destroy_selective(SelectiveDestroy::default(), true);
+    info!("{:#?}", req);
+
+    match req.validate() {
+        Err(Error::InvalidArgument { ref field, .. }) if field == "resources" => (),
+        other => panic!("Expected resources to be invalid, got {:?}", other),
+    }
+}
+
+#[test]
+fn destroy_selective_sends_body() {
+    before();
+
+    let mock = Mock::new(vec![response("202 Accepted", &[], b"")]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let req: Request<Delete, ()> = Droplet::get(123).destroy_selective(selection(), true);
+    req.execute(&client).unwrap();
+
+    let requests = mock.requests();
+    assert!(requests[0].starts_with(
+        "DELETE /v2/droplets/123/destroy_with_associated_resources/selective "
+    ));
+    assert!(requests[0].contains(r#""reserved_ips":["6186916"]"#));
+}
+
+#[test]
+fn destroy_dangerous_produces_correct_request() {
+    before();
+
+    let correct_url =
+        "https://api.digitalocean.com/v2/droplets/123/destroy_with_associated_resources/dangerous";
+
+    let req: Request<Delete, ()> = Droplet::get(123).destroy_dangerous(true);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+    assert!(req
+        .headers()
+        .contains(&("X-Dangerous".to_string(), "true".to_string())));
+    assert!(req.validate().is_ok());
+}
+
+#[test]
+fn destroy_dangerous_requires_confirmation() {
+    before();
+
+    let req: Request<Delete, ()> = Droplet::get(123).destroy_dangerous(false);
+    info!("{:#?}", req);
+
+    match req.validate() {
+        Err(Error::InvalidArgument { ref field, .. }) if field == "confirm" => (),
+        other => panic!("Expected confirm to be invalid, got {:?}", other),
+    }
+}
+
+#[test]
+fn destroy_status_response_deserializes() {
+    before();
+
+    let body = json!({
+        "droplet": {
+            "id": "187000742",
+            "name": "ubuntu-s-1vcpu-1gb-nyc1-01",
+            "destroyed_at": "2020-04-01T18:11:49Z",
+        },
+        "resources": {
+            "reserved_ips": [{
+                "id": "6186916",
+                "name": "45.55.96.47",
+                "destroyed_at": "2020-04-01T18:11:44Z",
+            }],
+            "floating_ips": [],
+            "snapshots": [],
+            "volumes": [],
+            "volume_snapshots": [],
+        },
+        "completed_at": "2020-04-01T18:11:49Z",
+        "failures": 0,
+    });
+
+    let response: <DestroyStatus as HasResponse>::Response = serde_json::from_value(body).unwrap();
+    let status = response.value();
+
+    assert!(status.completed_at().is_some());
+    assert_eq!(*status.failures(), 0);
+    assert_eq!(status.resources().reserved_ips[0].name, "45.55.96.47");
+}
+
+#[test]
+fn retry_destroy_produces_correct_request() {
+    before();
+
+    let correct_url =
+        "https://api.digitalocean.com/v2/droplets/123/destroy_with_associated_resources/retry";
+
+    let req: Request<Create, ()> = Droplet::get(123).retry_destroy();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
@@ -0,0 +1,155 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate url;
+extern crate url_serde;
+
+mod utils;
+
+use serde_json::Value;
+
+use digitalocean::api::firewall_fields::{Endpoints, InboundRule, OutboundRule};
+use digitalocean::api::{Firewall, Target};
+use digitalocean::error::Error;
+use digitalocean::method::{Create, Delete, Get, List};
+use digitalocean::request::Request;
+
+use crate::utils::before;
+
+fn ssh_from_anywhere() -> InboundRule {
+    InboundRule {
+        protocol: "tcp".to_string(),
+        ports: "22".to_string(),
+        sources: Endpoints {
+            addresses: vec!["0.0.0.0/0".to_string(), "::/0".to_string()],
+            ..Endpoints::default()
+        },
+    }
+}
+
+fn https_to_tag(tag: &str) -> OutboundRule {
+    OutboundRule {
+        protocol: "tcp".to_string(),
+        ports: "443".to_string(),
+        destinations: Target::tag(tag).into(),
+    }
+}
+
+#[test]
+fn list_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/firewalls";
+
+    let req: Request<List, Vec<Firewall>> = Firewall::list();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn get_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/firewalls/bb4b2611";
+
+    let req: Request<Get, Firewall> = Firewall::get("bb4b2611");
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn delete_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/firewalls/bb4b2611";
+
+    let req: Request<Delete, ()> = Firewall::delete("bb4b2611");
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn create_for_tag_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/firewalls";
+
+    let req: Request<Create, Firewall> = Firewall::create_for_tag(
+        "web",
+        "frontend",
+        vec![ssh_from_anywhere()],
+        vec![https_to_tag("backend")],
+    );
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "name": "web",
+            "tags": ["frontend"],
+            "inbound_rules": [{
+                "protocol": "tcp",
+                "ports": "22",
+                "sources": { "addresses": ["0.0.0.0/0", "::/0"] },
+            }],
+            "outbound_rules": [{
+                "protocol": "tcp",
+                "ports": "443",
+                "destinations": { "tags": ["backend"] },
+            }],
+        })
+    );
+}
+
+#[test]
+fn target_droplets_produces_droplet_ids() {
+    before();
+
+    let req: Request<Create, Firewall> =
+        Firewall::create("web").target(Target::Droplets(vec![8043964, 8043972]));
+    info!("{:#?}", req);
+
+    assert_eq!(
+        *req.body(),
+        json!({
+            "name": "web",
+            "droplet_ids": [8043964, 8043972],
+        })
+    );
+    assert!(req.validate().is_ok());
+}
+
+#[test]
+fn target_rejects_bad_tag() {
+    before();
+
+    let req: Request<Create, Firewall> = Firewall::create_for_tag("web", "not a tag", vec![], vec![]);
+
+    match req.validate() {
+        Err(Error::InvalidArgument { ref field, .. }) if field == "tags" => (),
+        other => panic!("Expected tags to be invalid, got {:?}", other),
+    }
+}
+
+#[test]
+fn target_serializes_to_its_key() {
+    before();
+
+    assert_eq!(
+        serde_json::to_value(Target::Droplets(vec![3164444])).unwrap(),
+        json!({ "droplet_ids": [3164444] })
+    );
+    assert_eq!(
+        serde_json::to_value(Target::tag("web")).unwrap(),
+        json!({ "tags": ["web"] })
+    );
+}
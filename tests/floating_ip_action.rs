@@ -11,12 +11,15 @@ mod utils;
 use serde_json::Value;
 use std::net::IpAddr;
 use std::str::FromStr;
+use std::time::Duration;
 
 use digitalocean::api::{Action, FloatingIp};
+use digitalocean::error::Error;
 use digitalocean::method::{Create, Get, List};
 use digitalocean::request::Request;
+use digitalocean::DigitalOcean;
 
-use crate::utils::before;
+use crate::utils::{action_json, before, droplet_json, json_response, Mock};
 
 #[test]
 fn list_produces_correct_request() {
@@ -98,3 +101,69 @@ fn unassign_produces_correct_request() {
         })
     );
 }
+
+fn floating_ip_json(droplet: Value) -> Value {
+    json!({
+        "floating_ip": {
+            "ip": "45.55.96.47",
+            "region": {
+                "name": "New York 3",
+                "slug": "nyc3",
+                "features": ["private_networking"],
+                "available": true,
+                "sizes": ["s-1vcpu-1gb"],
+            },
+            "droplet": droplet,
+        }
+    })
+}
+
+#[test]
+fn assign_and_wait_returns_assigned_floating_ip() {
+    before();
+
+    let mock = Mock::new(vec![
+        json_response("201 Created", &json!({ "action": action_json(68212728, "in-progress") })),
+        json_response("200 OK", &json!({ "action": action_json(68212728, "in-progress") })),
+        json_response("200 OK", &json!({ "action": action_json(68212728, "completed") })),
+        json_response("200 OK", &floating_ip_json(droplet_json(3164444, "active"))),
+    ]);
+    let mut client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+    client.set_poll_interval(Duration::from_millis(1));
+
+    let ip = IpAddr::from_str("45.55.96.47").unwrap();
+    let floating_ip = client
+        .assign_floating_ip_and_wait(ip, 3164444, Duration::from_secs(10))
+        .unwrap();
+
+    assert_eq!(*floating_ip.droplet().as_ref().unwrap().id(), 3164444);
+
+    let requests = mock.requests();
+    assert!(requests[0].starts_with("POST /v2/floating_ips/45.55.96.47/actions "));
+    assert!(requests[1].starts_with("GET /v2/actions/68212728 "));
+    assert!(requests[2].starts_with("GET /v2/actions/68212728 "));
+    assert!(requests[3].starts_with("GET /v2/floating_ips/45.55.96.47 "));
+}
+
+#[test]
+fn assign_and_wait_fails_on_errored_action() {
+    before();
+
+    let mock = Mock::new(vec![
+        json_response("201 Created", &json!({ "action": action_json(68212728, "in-progress") })),
+        json_response("200 OK", &json!({ "action": action_json(68212728, "errored") })),
+    ]);
+    let mut client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+    client.set_poll_interval(Duration::from_millis(1));
+
+    let ip = IpAddr::from_str("45.55.96.47").unwrap();
+    match client.assign_floating_ip_and_wait(ip, 3164444, Duration::from_secs(10)) {
+        Err(Error::ActionErrored { id }) => assert_eq!(id, 68212728),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+    assert_eq!(mock.requests().len(), 2);
+}
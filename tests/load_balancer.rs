@@ -10,7 +10,8 @@ mod utils;
 
 use serde_json::Value;
 
-use digitalocean::api::LoadBalancer;
+use digitalocean::api::{LoadBalancer, Target};
+use digitalocean::error::Error;
 use digitalocean::method::{Create, Delete, Get, List, Update};
 use digitalocean::request::Request;
 
@@ -261,3 +262,34 @@ fn remove_forwarding_rule_produces_correct_request() {
         })
     );
 }
+
+#[test]
+fn target_sets_droplet_ids_or_tag() {
+    before();
+
+    let req: Request<Create, LoadBalancer> =
+        LoadBalancer::create("example-lb-01", "nyc3").target(Target::Droplets(vec![3164444]));
+    info!("{:#?}", req);
+
+    assert_eq!(req.body()["droplet_ids"], json!([3164444]));
+
+    let req: Request<Update, LoadBalancer> =
+        LoadBalancer::update("4de7ac8b").target(Target::tag("web"));
+    info!("{:#?}", req);
+
+    assert_eq!(req.body()["tag"], json!("web"));
+    assert_eq!(req.body().get("tags"), None);
+}
+
+#[test]
+fn target_rejects_several_tags() {
+    before();
+
+    let req: Request<Create, LoadBalancer> = LoadBalancer::create("example-lb-01", "nyc3")
+        .target(Target::Tags(vec!["web".to_string(), "api".to_string()]));
+
+    match req.validate() {
+        Err(Error::InvalidArgument { ref field, .. }) if field == "target" => (),
+        other => panic!("Expected target to be invalid, got {:?}", other),
+    }
+}
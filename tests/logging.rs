@@ -0,0 +1,104 @@
+extern crate digitalocean;
+extern crate log;
+extern crate serde_json;
+extern crate url;
+
+mod utils;
+
+use log::{LevelFilter, Log, Metadata, Record};
+use std::sync::{Mutex, Once};
+use std::thread::{self, ThreadId};
+
+use digitalocean::api::Droplet;
+use digitalocean::request::Executable;
+use digitalocean::DigitalOcean;
+
+use crate::utils::{droplet_json, json_response, Mock};
+
+const SECRET_TOKEN: &str = "dop_v1_5ecre7t0ken";
+const SECRET_USER_DATA: &str = "#cloud-config\npassword: hunter2";
+
+/// Records this crate's messages along with the thread which logged them,
+/// since tests run in parallel.
+struct Capture(Mutex<Vec<(ThreadId, String)>>);
+
+impl Log for Capture {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.target().starts_with("digitalocean")
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        self.0
+            .lock()
+            .unwrap()
+            .push((thread::current().id(), record.args().to_string()));
+    }
+
+    fn flush(&self) {}
+}
+
+static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+static INIT: Once = Once::new();
+
+fn logs_of(f: impl FnOnce()) -> Vec<String> {
+    INIT.call_once(|| {
+        log::set_logger(&CAPTURE).expect("Logger already set.");
+        log::set_max_level(LevelFilter::Trace);
+    });
+
+    f();
+
+    let id = thread::current().id();
+    CAPTURE
+        .0
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(thread, _)| *thread == id)
+        .map(|(_, message)| message.clone())
+        .collect()
+}
+
+fn create_droplet(logging: bool) -> Vec<String> {
+    let mock = Mock::new(vec![json_response(
+        "202 Accepted",
+        &serde_json::json!({ "droplet": droplet_json(3164444, "new") }),
+    )]);
+    let mut client = DigitalOcean::new(SECRET_TOKEN)
+        .unwrap()
+        .with_root(mock.url().clone());
+    client.set_logging(logging);
+
+    let logs = logs_of(|| {
+        Droplet::create("example.com", "nyc3", "s-1vcpu-1gb", "ubuntu-20-04-x64")
+            .user_data(SECRET_USER_DATA)
+            .execute(&client)
+            .unwrap();
+    });
+
+    let requests = mock.requests();
+    assert!(requests[0].contains("hunter2"));
+    logs
+}
+
+#[test]
+fn logs_contain_no_secrets() {
+    let logs = create_droplet(true);
+
+    assert!(!logs.is_empty());
+    for message in logs {
+        assert!(!message.contains(SECRET_TOKEN), "Token logged: {}", message);
+        assert!(!message.contains("hunter2"), "User data logged: {}", message);
+    }
+}
+
+#[test]
+fn logging_can_be_disabled() {
+    let logs = create_droplet(false);
+
+    assert!(logs.is_empty(), "Unexpected logs: {:?}", logs);
+}
@@ -0,0 +1,343 @@
+extern crate chrono;
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+extern crate serde_json;
+extern crate url;
+extern crate url_serde;
+
+mod utils;
+
+use chrono::{TimeZone, Utc};
+use serde_json::Value;
+use std::time::Duration;
+
+use digitalocean::api::monitoring_fields::{Direction, Interface};
+use digitalocean::api::{HasResponse, HasValue, Metrics};
+use digitalocean::error::Error;
+use digitalocean::method::Get;
+use digitalocean::request::{Executable, Request};
+use digitalocean::DigitalOcean;
+
+use crate::utils::{before, json_response, Mock};
+
+#[test]
+fn droplet_bandwidth_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/monitoring/metrics/droplet/bandwidth?host_id=123&start=1620683817&end=1620705417&interface=public&direction=inbound";
+
+    let req: Request<Get, Metrics> = Metrics::droplet_bandwidth(
+        123,
+        Interface::Public,
+        Direction::Inbound,
+        Utc.timestamp_opt(1620683817, 0).unwrap(),
+        Utc.timestamp_opt(1620705417, 0).unwrap(),
+    );
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+    assert!(req.validate().is_ok());
+}
+
+#[test]
+fn droplet_bandwidth_rejects_reversed_range() {
+    before();
+
+    let req: Request<Get, Metrics> = Metrics::droplet_bandwidth(
+        123,
+        Interface::Private,
+        Direction::Outbound,
+        Utc.timestamp_opt(1620705417, 0).unwrap(),
+        Utc.timestamp_opt(1620683817, 0).unwrap(),
+    );
+    info!("{:#?}", req);
+
+    match req.validate() {
+        Err(Error::InvalidArgument { field, .. }) => assert_eq!(field, "end"),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn metrics_response_deserializes() {
+    before();
+
+    let body = r#"{
+        "status": "success",
+        "data": {
+            "resultType": "matrix",
+            "result": [
+                {
+                    "metric": {
+                        "direction": "inbound",
+                        "host_id": "123",
+                        "interface": "public"
+                    },
+                    "values": [
+                        [1620683817, "0.016600450090265357"],
+                        [1620684117, "0.015085955677299055"]
+                    ]
+                }
+            ]
+        }
+    }"#;
+
+    let response: <Metrics as HasResponse>::Response = serde_json::from_str(body).unwrap();
+    let metrics = response.value();
+
+    assert_eq!(metrics.result_type(), "matrix");
+    assert_eq!(metrics.result().len(), 1);
+    assert_eq!(metrics.result()[0].metric["direction"], "inbound");
+    assert_eq!(metrics.result()[0].values[1].0, 1620684117);
+}
+
+#[test]
+fn droplet_load_5_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/monitoring/metrics/droplet/load_5?host_id=123&start=1620683817&end=1620705417";
+
+    let req: Request<Get, Metrics> = Metrics::droplet_load_5(
+        123,
+        Utc.timestamp_opt(1620683817, 0).unwrap(),
+        Utc.timestamp_opt(1620705417, 0).unwrap(),
+    );
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+    assert!(req.validate().is_ok());
+}
+
+#[test]
+fn memory_metrics_response_deserializes() {
+    before();
+
+    let body = r#"{
+        "status": "success",
+        "data": {
+            "resultType": "matrix",
+            "result": [
+                {
+                    "metric": {
+                        "host_id": "123"
+                    },
+                    "values": [
+                        [1620683817, "1019179008"],
+                        [1620684117, "1019179008"],
+                        [1620684417, "1019183104"]
+                    ]
+                }
+            ]
+        }
+    }"#;
+
+    let response: <Metrics as HasResponse>::Response = serde_json::from_str(body).unwrap();
+    let metrics = response.value();
+
+    assert_eq!(metrics.result()[0].metric["host_id"], "123");
+    assert_eq!(metrics.result()[0].values.len(), 3);
+    assert_eq!(metrics.result()[0].values[2].1, "1019183104");
+}
+
+fn sample_metrics() -> Metrics {
+    let body = r#"{
+        "status": "success",
+        "data": {
+            "resultType": "matrix",
+            "result": [
+                {
+                    "metric": { "host_id": "123" },
+                    "values": [
+                        [1620683817, "0.5"],
+                        [1620684117, "NaN"],
+                        [1620684417, "2.5"],
+                        [1620684717, "garbage"],
+                        [1620685017, "1.5"]
+                    ]
+                }
+            ]
+        }
+    }"#;
+
+    let response: <Metrics as HasResponse>::Response = serde_json::from_str(body).unwrap();
+    response.value()
+}
+
+#[test]
+fn metrics_aggregate_samples() {
+    before();
+
+    let metrics = sample_metrics();
+
+    assert_eq!(metrics.average(), Some(1.5));
+    assert_eq!(metrics.max(), Some(2.5));
+    assert_eq!(metrics.min(), Some(0.5));
+    assert_eq!(metrics.latest(), Some(1.5));
+}
+
+#[test]
+fn metrics_aggregates_of_empty_series_are_none() {
+    before();
+
+    let body = r#"{ "status": "success", "data": { "resultType": "matrix", "result": [] } }"#;
+    let response: <Metrics as HasResponse>::Response = serde_json::from_str(body).unwrap();
+    let metrics = response.value();
+
+    assert_eq!(metrics.average(), None);
+    assert_eq!(metrics.max(), None);
+    assert_eq!(metrics.min(), None);
+    assert_eq!(metrics.latest(), None);
+}
+
+#[test]
+fn bandwidth_metrics_total_transfer() {
+    before();
+
+    // Usable samples of 0.5, 2.5 and 1.5 Mbps, ten minutes apart.
+    let (bytes, samples) = sample_metrics().transfer_bytes();
+
+    assert_eq!(samples, 3);
+    assert_eq!(bytes, (600.0 * 1.5 + 600.0 * 2.0) * 1_000_000.0 / 8.0);
+}
+
+#[test]
+fn droplet_transfer_sums_public_outbound_bandwidth() {
+    before();
+
+    // 8.589934592 Mbps for 1000 seconds is exactly 1 GiB.
+    let mock = Mock::new(vec![json_response(
+        "200 OK",
+        &serde_json::json!({
+            "status": "success",
+            "data": {
+                "resultType": "matrix",
+                "result": [{
+                    "metric": { "host_id": "123", "direction": "outbound", "interface": "public" },
+                    "values": [
+                        [1620683000, "8.589934592"],
+                        [1620683500, "8.589934592"],
+                        [1620684000, "8.589934592"]
+                    ]
+                }]
+            }
+        }),
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let start = Utc.timestamp_opt(1620683000, 0).unwrap();
+    let end = Utc.timestamp_opt(1620684000, 0).unwrap();
+    let transfer = client.droplet_transfer(123, start, end).unwrap();
+
+    assert_eq!(transfer.start, start);
+    assert_eq!(transfer.end, end);
+    assert_eq!(transfer.samples, 3);
+    assert!((transfer.gib() - 1.0).abs() < 1e-9);
+
+    let requests = mock.requests();
+    assert!(requests[0].contains("interface=public&direction=outbound"));
+}
+
+#[test]
+fn droplet_transfer_without_data_is_zero() {
+    before();
+
+    let mock = Mock::new(vec![json_response(
+        "200 OK",
+        &serde_json::json!({
+            "status": "success",
+            "data": { "resultType": "matrix", "result": [] }
+        }),
+    )]);
+    let client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+
+    let transfer = client.droplet_transfer_this_month(123).unwrap();
+
+    assert_eq!(transfer.samples, 0);
+    assert_eq!(transfer.bytes, 0.0);
+    assert!(transfer.start <= transfer.end);
+    assert_eq!(transfer.start.format("%d %H:%M:%S").to_string(), "01 00:00:00");
+}
+
+fn load_response() -> Vec<u8> {
+    json_response(
+        "200 OK",
+        &serde_json::json!({
+            "status": "success",
+            "data": {
+                "resultType": "matrix",
+                "result": [{
+                    "metric": { "host_id": "123" },
+                    "values": [[1620683817, "0.25"]]
+                }]
+            }
+        }),
+    )
+}
+
+#[test]
+fn metrics_cache_answers_repeated_queries() {
+    before();
+
+    let mock = Mock::new(vec![load_response(), load_response()]);
+    let mut client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+    client.set_metrics_cache(Some(Duration::from_secs(60)));
+
+    let start = Utc.timestamp_opt(1620683817, 0).unwrap();
+    let end = Utc.timestamp_opt(1620705417, 0).unwrap();
+
+    let first = Metrics::droplet_load_5(123, start, end).execute(&client).unwrap();
+    let second = Metrics::droplet_load_5(123, start, end).execute(&client).unwrap();
+    assert_eq!(first.latest(), Some(0.25));
+    assert_eq!(second.latest(), Some(0.25));
+    assert_eq!(client.metrics().requests, 1);
+
+    // A different range is a different query.
+    let later = Utc.timestamp_opt(1620705418, 0).unwrap();
+    Metrics::droplet_load_5(123, start, later).execute(&client).unwrap();
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[1].contains("end=1620705418"), "{}", requests[1]);
+}
+
+#[test]
+fn metrics_cache_keeps_requests_with_different_headers_apart() {
+    before();
+
+    let mock = Mock::new(vec![load_response(), load_response()]);
+    let mut client = DigitalOcean::new("token")
+        .unwrap()
+        .with_root(mock.url().clone());
+    client.set_metrics_cache(Some(Duration::from_secs(60)));
+
+    let start = Utc.timestamp_opt(1620683817, 0).unwrap();
+    let end = Utc.timestamp_opt(1620705417, 0).unwrap();
+
+    for id in &["trace-a", "trace-b"] {
+        Metrics::droplet_load_5(123, start, end)
+            .correlation_id(*id)
+            .execute(&client)
+            .unwrap();
+    }
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[1].to_lowercase().contains("x-correlation-id: trace-b"));
+}
+
+#[test]
+fn metrics_cache_is_disabled_by_default() {
+    before();
+
+    let client = DigitalOcean::new("token").unwrap();
+    assert_eq!(client.metrics_cache(), None);
+}
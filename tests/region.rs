@@ -9,7 +9,8 @@ mod utils;
 
 use serde_json::Value;
 
-use digitalocean::api::Region;
+use digitalocean::api::region_fields::RegionSlug;
+use digitalocean::api::{Droplet, LoadBalancer, Region, Volume};
 use digitalocean::method::List;
 use digitalocean::request::Request;
 
@@ -27,3 +28,54 @@ fn list_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[test]
+fn known_slugs_map_to_variants() {
+    before();
+
+    assert_eq!(RegionSlug::from("nyc3"), RegionSlug::Nyc3);
+    assert_eq!("fra1".parse::<RegionSlug>().unwrap(), RegionSlug::Fra1);
+
+    let slug: RegionSlug = serde_json::from_value(serde_json::json!("sgp1")).unwrap();
+    assert_eq!(slug, RegionSlug::Sgp1);
+    assert_eq!(serde_json::to_value(&RegionSlug::Lon1).unwrap(), serde_json::json!("lon1"));
+    assert_eq!(RegionSlug::Tor1.to_string(), "tor1");
+}
+
+#[test]
+fn unknown_slug_round_trips_as_other() {
+    before();
+
+    let slug: RegionSlug = serde_json::from_value(serde_json::json!("mars1")).unwrap();
+    assert_eq!(slug, RegionSlug::Other("mars1".to_string()));
+    assert_eq!(serde_json::to_value(&slug).unwrap(), serde_json::json!("mars1"));
+}
+
+#[test]
+fn slug_is_accepted_by_builders() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/volumes?region=ams3";
+
+    let req = Volume::list().region(RegionSlug::Ams3);
+
+    assert_eq!(req.url().as_str(), correct_url);
+}
+
+#[test]
+fn slug_is_accepted_by_constructors() {
+    before();
+
+    let droplet = Droplet::create("web", RegionSlug::Nyc3, "s-1vcpu-1gb", "ubuntu-22-04-x64");
+    assert_eq!(droplet.body()["region"], "nyc3");
+    assert!(droplet.validate().is_ok());
+
+    let droplets = Droplet::create_multiple(vec!["web-1", "web-2"], RegionSlug::Nyc3, "s-1vcpu-1gb", "ubuntu-22-04-x64");
+    assert_eq!(droplets.body()["region"], "nyc3");
+
+    let balancer = LoadBalancer::create("lb", RegionSlug::Ams3);
+    assert_eq!(balancer.body()["region"], "ams3");
+
+    let volume = Volume::get_by_name("data", RegionSlug::Fra1);
+    assert_eq!(volume.url().as_str(), "https://api.digitalocean.com/v2/volumes?name=data&region=fra1");
+}
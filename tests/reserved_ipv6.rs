@@ -0,0 +1,127 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate url;
+extern crate url_serde;
+
+mod utils;
+
+use serde_json::Value;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use digitalocean::api::{Action, ReservedIpv6};
+use digitalocean::error::Error;
+use digitalocean::method::{Create, Delete, Get, List};
+use digitalocean::request::Request;
+
+use crate::utils::before;
+
+fn address() -> Ipv6Addr {
+    "2409:40d0:f7:1017:74b4:3a96:105e:4c6e".parse().unwrap()
+}
+
+#[test]
+fn list_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/reserved_ipv6";
+
+    let req: Request<List, Vec<ReservedIpv6>> = ReservedIpv6::list();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn create_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/reserved_ipv6";
+
+    let req: Request<Create, ReservedIpv6> = ReservedIpv6::create("nyc3");
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), json!({ "region_slug": "nyc3" }));
+    assert!(req.validate().is_ok());
+}
+
+#[test]
+fn get_produces_correct_request() {
+    before();
+
+    let correct_url = format!("https://api.digitalocean.com/v2/reserved_ipv6/{}", address());
+
+    let req: Request<Get, ReservedIpv6> = ReservedIpv6::get(address());
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+    assert!(req.validate().is_ok());
+}
+
+#[test]
+fn delete_produces_correct_request() {
+    before();
+
+    let correct_url = format!("https://api.digitalocean.com/v2/reserved_ipv6/{}", address());
+
+    let req: Request<Delete, ()> = ReservedIpv6::delete(address());
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn ipv4_addresses_are_rejected() {
+    before();
+
+    let ip = IpAddr::V4(Ipv4Addr::new(45, 55, 96, 47));
+
+    let get: Request<Get, ReservedIpv6> = ReservedIpv6::get(ip);
+    let delete: Request<Delete, ()> = ReservedIpv6::delete(ip);
+    info!("{:#?} {:#?}", get, delete);
+
+    for result in [get.validate(), delete.validate()] {
+        match result {
+            Err(Error::InvalidArgument { ref field, .. }) if field == "ip" => (),
+            other => panic!("Expected ip to be invalid, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn assign_produces_correct_request() {
+    before();
+
+    let correct_url = format!(
+        "https://api.digitalocean.com/v2/reserved_ipv6/{}/actions",
+        address()
+    );
+
+    let req: Request<Create, Action> = ReservedIpv6::get(address()).assign(123);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), json!({ "type": "assign", "droplet_id": 123 }));
+}
+
+#[test]
+fn unassign_produces_correct_request() {
+    before();
+
+    let correct_url = format!(
+        "https://api.digitalocean.com/v2/reserved_ipv6/{}/actions",
+        address()
+    );
+
+    let req: Request<Create, Action> = ReservedIpv6::get(address()).unassign();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), json!({ "type": "unassign" }));
+}
@@ -9,7 +9,8 @@ mod utils;
 
 use serde_json::Value;
 
-use digitalocean::api::Size;
+use digitalocean::api::size_fields::{GpuInfo, Vram};
+use digitalocean::api::{HasResponse, HasValue, Size};
 use digitalocean::method::List;
 use digitalocean::request::Request;
 
@@ -27,3 +28,82 @@ fn list_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[test]
+fn list_response_deserializes_gpu_sizes() {
+    before();
+
+    let body = r#"{
+        "sizes": [
+            {
+                "slug": "s-1vcpu-1gb",
+                "memory": 1024,
+                "vcpus": 1,
+                "disk": 25,
+                "transfer": 1.0,
+                "price_monthly": 6.0,
+                "price_hourly": 0.00893,
+                "regions": ["nyc1"],
+                "available": true,
+                "description": "Basic"
+            },
+            {
+                "slug": "gpu-h100x1-80gb",
+                "memory": 245760,
+                "vcpus": 20,
+                "disk": 720,
+                "transfer": 15.0,
+                "price_monthly": 2532.0,
+                "price_hourly": 3.39,
+                "regions": ["tor1"],
+                "available": true,
+                "description": "H100 GPU - 1X",
+                "gpu_info": {
+                    "count": 1,
+                    "vram": { "amount": 80, "unit": "gib" },
+                    "model": "nvidia_h100"
+                },
+                "disk_info": [
+                    { "type": "local", "size": { "amount": 720, "unit": "gib" } },
+                    { "type": "scratch", "size": { "amount": 5120, "unit": "gib" } }
+                ]
+            },
+            {
+                "slug": "s-1vcpu-512mb-10gb",
+                "memory": 512,
+                "vcpus": 1,
+                "disk": 10,
+                "transfer": 0.5,
+                "price_monthly": 4.0,
+                "price_hourly": 0.00595,
+                "regions": [],
+                "available": false
+            }
+        ],
+        "links": {},
+        "meta": { "total": 3 }
+    }"#;
+
+    let response: <Vec<Size> as HasResponse>::Response = serde_json::from_str(body).unwrap();
+    let sizes = response.value();
+
+    assert_eq!(sizes.len(), 3);
+    assert_eq!(*sizes[0].gpu_info(), None);
+    assert_eq!(sizes[0].description().as_ref().map(String::as_str), Some("Basic"));
+
+    assert_eq!(
+        *sizes[1].gpu_info(),
+        Some(GpuInfo {
+            count: 1,
+            model: "nvidia_h100".into(),
+            vram: Some(Vram {
+                amount: 80,
+                unit: "gib".into(),
+            }),
+        })
+    );
+
+    assert_eq!(sizes[2].slug(), "s-1vcpu-512mb-10gb");
+    assert_eq!(*sizes[2].description(), None);
+    assert_eq!(*sizes[2].gpu_info(), None);
+}
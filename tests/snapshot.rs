@@ -1,3 +1,4 @@
+extern crate chrono;
 extern crate digitalocean;
 #[macro_use]
 extern crate log;
@@ -7,9 +8,10 @@ extern crate url_serde;
 
 mod utils;
 
+use chrono::{TimeZone, Utc};
 use serde_json::Value;
 
-use digitalocean::api::Snapshot;
+use digitalocean::api::{HasResponse, HasValue, Snapshot};
 use digitalocean::method::{Delete, Get, List};
 use digitalocean::request::Request;
 
@@ -81,3 +83,31 @@ fn delete_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[test]
+fn created_at_is_parsed_into_a_timestamp() {
+    before();
+
+    for created_at in &["2018-09-28T19:26:37Z", "2018-09-28T21:26:37+02:00"] {
+        let body = serde_json::json!({
+            "snapshot": {
+                "id": "7724db7c-e098-11e5-b522-000f53304e51",
+                "name": "Ubuntu Foo",
+                "created_at": created_at,
+                "regions": ["nyc1"],
+                "resource_id": "123",
+                "resource_type": "droplet",
+                "min_disk_size": 20,
+                "size_gigabytes": 1.0,
+            }
+        });
+
+        let response: <Snapshot as HasResponse>::Response = serde_json::from_value(body).unwrap();
+        let snapshot = response.value();
+
+        assert_eq!(
+            *snapshot.created_at(),
+            Utc.with_ymd_and_hms(2018, 9, 28, 19, 26, 37).unwrap()
+        );
+    }
+}
@@ -0,0 +1,86 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+extern crate serde_json;
+extern crate url;
+extern crate url_serde;
+
+mod utils;
+
+use serde_json::Value;
+
+use digitalocean::api::{spaces_endpoint, CdnEndpoint, SpacesKey};
+use digitalocean::error::Error;
+use digitalocean::method::{Get, List};
+use digitalocean::request::Request;
+
+use crate::utils::before;
+
+#[test]
+fn cdn_list_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/cdn/endpoints";
+
+    let req: Request<List, Vec<CdnEndpoint>> = CdnEndpoint::list();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn cdn_get_produces_correct_request() {
+    before();
+
+    let correct_url =
+        "https://api.digitalocean.com/v2/cdn/endpoints/19f06b6a-3ace-4315-b086-499a0e521b76";
+
+    let req: Request<Get, CdnEndpoint> = CdnEndpoint::get("19f06b6a-3ace-4315-b086-499a0e521b76");
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn keys_list_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/spaces/keys";
+
+    let req: Request<List, Vec<SpacesKey>> = SpacesKey::list();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn endpoint_maps_regions_to_hosts() {
+    before();
+
+    let cases = [
+        ("nyc3", "https://nyc3.digitaloceanspaces.com/"),
+        ("ams3", "https://ams3.digitaloceanspaces.com/"),
+        ("sgp1", "https://sgp1.digitaloceanspaces.com/"),
+        ("fra1", "https://fra1.digitaloceanspaces.com/"),
+        ("syd1", "https://syd1.digitaloceanspaces.com/"),
+    ];
+
+    for &(region, correct_url) in &cases {
+        assert_eq!(spaces_endpoint(region).unwrap().as_str(), correct_url);
+    }
+}
+
+#[test]
+fn endpoint_rejects_non_region_slugs() {
+    before();
+
+    for region in ["", "NYC3", "nyc", "evil.com/nyc3"] {
+        match spaces_endpoint(region) {
+            Err(Error::InvalidArgument { ref field, .. }) if field == "region" => (),
+            other => panic!("Expected region to be invalid, got {:?}", other),
+        }
+    }
+}
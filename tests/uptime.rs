@@ -0,0 +1,229 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate url;
+extern crate url_serde;
+
+mod utils;
+
+use serde_json::Value;
+
+use digitalocean::api::uptime_fields::{CheckKind, Notifications};
+use digitalocean::api::{CheckState, HasResponse, HasValue, UptimeAlert, UptimeCheck};
+use digitalocean::error::Error;
+use digitalocean::method::{Create, Delete, Get, List, Update};
+use digitalocean::request::Request;
+
+use crate::utils::before;
+
+const CHECK_ID: &str = "5a4981aa-9653-4bd1-bef5-d6bff52042e4";
+
+#[test]
+fn list_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/uptime/checks";
+
+    let req: Request<List, Vec<UptimeCheck>> = UptimeCheck::list();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn create_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/uptime/checks";
+
+    let req: Request<Create, UptimeCheck> = UptimeCheck::create(
+        "Landing page check",
+        CheckKind::Https,
+        "https://www.landingpage.com",
+        vec!["us_east", "eu_west"],
+        true,
+    );
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "name": "Landing page check",
+            "type": "https",
+            "target": "https://www.landingpage.com",
+            "regions": ["us_east", "eu_west"],
+            "enabled": true,
+        })
+    );
+}
+
+#[test]
+fn create_rejects_empty_target() {
+    before();
+
+    let req: Request<Create, UptimeCheck> =
+        UptimeCheck::create("Landing page check", CheckKind::Ping, "", vec![], true);
+    info!("{:#?}", req);
+
+    match req.validate() {
+        Err(Error::InvalidArgument { ref field, .. }) if field == "target" => (),
+        other => panic!("Expected target to be invalid, got {:?}", other),
+    }
+}
+
+#[test]
+fn get_produces_correct_request() {
+    before();
+
+    let correct_url = format!("https://api.digitalocean.com/v2/uptime/checks/{}", CHECK_ID);
+
+    let req: Request<Get, UptimeCheck> = UptimeCheck::get(CHECK_ID);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn update_produces_correct_request() {
+    before();
+
+    let correct_url = format!("https://api.digitalocean.com/v2/uptime/checks/{}", CHECK_ID);
+
+    let req: Request<Update, UptimeCheck> = UptimeCheck::update(CHECK_ID)
+        .name("Renamed")
+        .enabled(false);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "name": "Renamed",
+            "enabled": false,
+        })
+    );
+}
+
+#[test]
+fn delete_produces_correct_request() {
+    before();
+
+    let correct_url = format!("https://api.digitalocean.com/v2/uptime/checks/{}", CHECK_ID);
+
+    let req: Request<Delete, ()> = UptimeCheck::delete(CHECK_ID);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn state_produces_correct_request() {
+    before();
+
+    let correct_url = format!(
+        "https://api.digitalocean.com/v2/uptime/checks/{}/state",
+        CHECK_ID
+    );
+
+    let req: Request<Get, CheckState> = UptimeCheck::get(CHECK_ID).state();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn state_response_deserializes() {
+    before();
+
+    let body = json!({
+        "state": {
+            "regions": {
+                "us_east": {
+                    "status": "UP",
+                    "status_changed_at": "2022-03-17T22:28:51Z",
+                    "thirty_day_uptime_percentage": 97.99,
+                },
+                "eu_west": {
+                    "status": "UP",
+                    "status_changed_at": "2022-03-17T22:28:51Z",
+                    "thirty_day_uptime_percentage": 97.99,
+                },
+            },
+            "previous_outage": {
+                "region": "us_east",
+                "started_at": "2022-03-17T18:04:55Z",
+                "ended_at": "2022-03-17T18:06:55Z",
+                "duration_seconds": 120,
+            },
+        }
+    });
+
+    let response: <CheckState as HasResponse>::Response = serde_json::from_value(body).unwrap();
+    let state = response.value();
+
+    assert_eq!(state.regions()["us_east"].status, "UP");
+    assert_eq!(
+        state.previous_outage().as_ref().unwrap().duration_seconds,
+        120
+    );
+}
+
+#[test]
+fn alerts_produces_correct_request() {
+    before();
+
+    let correct_url = format!(
+        "https://api.digitalocean.com/v2/uptime/checks/{}/alerts",
+        CHECK_ID
+    );
+
+    let req: Request<List, Vec<UptimeAlert>> = UptimeCheck::get(CHECK_ID).alerts();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn create_alert_produces_correct_request() {
+    before();
+
+    let correct_url = format!(
+        "https://api.digitalocean.com/v2/uptime/checks/{}/alerts",
+        CHECK_ID
+    );
+
+    let notifications = Notifications {
+        email: vec!["bob@example.com".to_string()],
+        slack: vec![],
+    };
+    let req: Request<Create, UptimeAlert> = UptimeCheck::get(CHECK_ID)
+        .alerts()
+        .create("Landing page degraded performance", "latency", notifications, "2m")
+        .threshold(300)
+        .comparison("greater_than");
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "name": "Landing page degraded performance",
+            "type": "latency",
+            "notifications": {
+                "email": ["bob@example.com"],
+                "slack": [],
+            },
+            "period": "2m",
+            "threshold": 300,
+            "comparison": "greater_than",
+        })
+    );
+}
@@ -1,8 +1,201 @@
+#![allow(dead_code)]
+
 extern crate dotenv;
 extern crate env_logger;
 
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Mutex;
+use std::thread::{self, JoinHandle};
+use url::Url;
+
 pub fn before() {
     // Setup for tests
     dotenv::dotenv().ok();
     env_logger::try_init().ok();
 }
+
+/// A tiny HTTP server which answers each connection with the next canned
+/// response, recording the raw requests it received.
+pub struct Mock {
+    url: Url,
+    handle: JoinHandle<Vec<String>>,
+}
+
+impl Mock {
+    pub fn new(responses: Vec<Vec<u8>>) -> Self {
+        let count = responses.len();
+        let responses = Mutex::new(responses.into_iter());
+        Mock::with_handler(count, move |_| {
+            responses.lock().unwrap().next().expect("Mock ran out of responses.")
+        })
+    }
+
+    /// Serve `count` connections, answering each with `handler` applied to
+    /// the raw request.
+    pub fn with_handler<F>(count: usize, handler: F) -> Self
+    where
+        F: Fn(&str) -> Vec<u8> + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Could not bind mock server.");
+        let address = listener.local_addr().expect("Mock server has no address.");
+        let url = Url::parse(&format!("http://{}/v2", address)).expect("Mock URL is malformed.");
+
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for _ in 0..count {
+                let (mut stream, _) = listener.accept().expect("Mock server failed to accept.");
+                let request = read_request(&mut stream);
+                stream
+                    .write_all(&handler(&request))
+                    .expect("Mock server failed to respond.");
+                requests.push(request);
+            }
+            requests
+        });
+
+        Mock { url, handle }
+    }
+
+    /// The root of the mock API, equivalent to `https://api.digitalocean.com/v2`.
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Wait for every response to be served and return the raw requests.
+    pub fn requests(self) -> Vec<String> {
+        self.handle.join().expect("Mock server panicked.")
+    }
+}
+
+fn read_request(stream: &mut TcpStream) -> String {
+    let mut reader = BufReader::new(stream);
+    let mut request = String::new();
+    let mut content_length = 0;
+
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).expect("Could not read request.");
+        if line.to_lowercase().starts_with("content-length:") {
+            content_length = line[15..].trim().parse().expect("Bad Content-Length.");
+        }
+        request.push_str(&line);
+        if line == "\r\n" || line.is_empty() {
+            break;
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).expect("Could not read request body.");
+    request.push_str(&String::from_utf8_lossy(&body));
+    request
+}
+
+/// Build a raw HTTP response.
+pub fn response(status: &str, headers: &[(&str, &str)], body: &[u8]) -> Vec<u8> {
+    let mut head = format!(
+        "HTTP/1.1 {}\r\nConnection: close\r\nContent-Length: {}\r\n",
+        status,
+        body.len()
+    );
+    for (name, value) in headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+
+    let mut raw = head.into_bytes();
+    raw.extend_from_slice(body);
+    raw
+}
+
+/// Build a raw HTTP response carrying a JSON body.
+pub fn json_response(status: &str, body: &serde_json::Value) -> Vec<u8> {
+    response(
+        status,
+        &[("Content-Type", "application/json")],
+        body.to_string().as_bytes(),
+    )
+}
+
+/// A complete Droplet as returned by the API.
+pub fn droplet_json(id: usize, status: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "name": "example.com",
+        "memory": 1024,
+        "vcpus": 1,
+        "disk": 25,
+        "locked": false,
+        "status": status,
+        "kernel": null,
+        "created_at": "2020-07-21T18:37:44Z",
+        "features": ["monitoring"],
+        "backup_ids": [],
+        "next_backup_window": null,
+        "snapshot_ids": [],
+        "image": {
+            "id": 63663980,
+            "name": "20.04 (LTS) x64",
+            "distribution": "Ubuntu",
+            "slug": "ubuntu-20-04-x64",
+            "public": true,
+            "regions": ["nyc3"],
+            "created_at": "2020-05-15T05:47:50Z",
+            "type": "snapshot",
+            "min_disk_size": 20,
+            "size_gigabytes": 2.36,
+        },
+        "volume_ids": [],
+        "size": {
+            "slug": "s-1vcpu-1gb",
+            "memory": 1024,
+            "vcpus": 1,
+            "disk": 25,
+            "transfer": 1.0,
+            "price_monthly": 5.0,
+            "price_hourly": 0.00743999984115362,
+            "regions": ["nyc3"],
+            "available": true,
+        },
+        "size_slug": "s-1vcpu-1gb",
+        "networks": {
+            "v4": if status == "new" {
+                serde_json::json!([])
+            } else {
+                serde_json::json!([{
+                    "ip_address": "104.236.32.182",
+                    "netmask": "255.255.192.0",
+                    "gateway": "104.236.0.1",
+                    "type": "public",
+                }])
+            },
+            "v6": [],
+        },
+        "region": {
+            "name": "New York 3",
+            "slug": "nyc3",
+            "features": ["private_networking"],
+            "available": true,
+            "sizes": ["s-1vcpu-1gb"],
+        },
+        "tags": [],
+    })
+}
+
+/// An action as returned by the API.
+pub fn action_json(id: usize, status: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "status": status,
+        "type": "create",
+        "started_at": "2020-07-21T18:37:44Z",
+        "completed_at": if status == "in-progress" {
+            serde_json::Value::Null
+        } else {
+            serde_json::json!("2020-07-21T18:38:44Z")
+        },
+        "resource_id": 3164444,
+        "resource_type": "droplet",
+        "region_slug": "nyc3",
+    })
+}